@@ -0,0 +1,434 @@
+use super::{Backend, BackendError, Value};
+
+// Bit 0 is the most significant bit of the first byte, like redis.
+
+/// The unit of the BITCOUNT / BITPOS ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitUnit {
+    #[default]
+    Byte,
+    Bit,
+}
+
+/// The bitwise operation of BITOP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitwiseOp {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+/// The overflow behavior of BITFIELD SET / INCRBY.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOverflow {
+    #[default]
+    Wrap,
+    Sat,
+    Fail, // don't write and reply nil
+}
+
+/// An integer type of BITFIELD, `i1` to `i64` or `u1` to `u63`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitfieldType {
+    pub signed: bool,
+    pub bits: u32,
+}
+
+/// A subcommand of BITFIELD, the offset is in bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitfieldOp {
+    Get {
+        ty: BitfieldType,
+        offset: usize,
+    },
+    Set {
+        ty: BitfieldType,
+        offset: usize,
+        value: i64,
+        overflow: BitOverflow,
+    },
+    IncrBy {
+        ty: BitfieldType,
+        offset: usize,
+        increment: i64,
+        overflow: BitOverflow,
+    },
+}
+
+fn get_bit(buf: &[u8], offset: usize) -> u8 {
+    buf.get(offset / 8)
+        .map_or(0, |byte| (byte >> (7 - offset % 8)) & 1)
+}
+
+fn set_bit(buf: &mut Vec<u8>, offset: usize, bit: bool) -> u8 {
+    let idx = offset / 8;
+    if buf.len() <= idx {
+        buf.resize(idx + 1, 0);
+    }
+    let mask = 1 << (7 - offset % 8);
+    let old = (buf[idx] & mask != 0) as u8;
+    match bit {
+        true => buf[idx] |= mask,
+        false => buf[idx] &= !mask,
+    }
+    old
+}
+
+/// Normalize the inclusive range, negative offsets count from the end.
+fn normalize(start: i64, end: i64, len: i64) -> Option<(usize, usize)> {
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let end = if end < 0 { len + end } else { end.min(len - 1) };
+    (start <= end && start < len && end >= 0).then_some((start as usize, end as usize))
+}
+
+/// The bit range of the BYTE / BIT range, None if the range is empty.
+fn bit_range(buf: &[u8], range: Option<(i64, i64, BitUnit)>) -> Option<(usize, usize)> {
+    let bits = buf.len() as i64 * 8;
+    match range {
+        None => (bits > 0).then(|| (0, bits as usize - 1)),
+        Some((start, end, BitUnit::Byte)) => {
+            let (start, end) = normalize(start, end, buf.len() as i64)?;
+            Some((start * 8, end * 8 + 7))
+        }
+        Some((start, end, BitUnit::Bit)) => normalize(start, end, bits),
+    }
+}
+
+impl BitfieldType {
+    fn min(&self) -> i128 {
+        match self.signed {
+            true => -(1i128 << (self.bits - 1)),
+            false => 0,
+        }
+    }
+
+    fn max(&self) -> i128 {
+        match self.signed {
+            true => (1i128 << (self.bits - 1)) - 1,
+            false => (1i128 << self.bits) - 1,
+        }
+    }
+
+    fn read(&self, buf: &[u8], offset: usize) -> i64 {
+        let mut value = 0u64;
+        for i in 0..self.bits as usize {
+            value = (value << 1) | get_bit(buf, offset + i) as u64;
+        }
+        // sign extension
+        if self.signed && self.bits < 64 && value >> (self.bits - 1) & 1 == 1 {
+            value |= u64::MAX << self.bits;
+        }
+        value as i64
+    }
+
+    fn write(&self, buf: &mut Vec<u8>, offset: usize, value: i64) {
+        let value = value as u64;
+        for i in 0..self.bits as usize {
+            let bit = value >> (self.bits as usize - 1 - i) & 1;
+            set_bit(buf, offset + i, bit == 1);
+        }
+    }
+
+    /// Fit the value into the type, None if it overflows with FAIL.
+    fn fit(&self, value: i128, overflow: BitOverflow) -> Option<i64> {
+        if (self.min()..=self.max()).contains(&value) {
+            return Some(value as i64);
+        }
+        match overflow {
+            BitOverflow::Fail => None,
+            BitOverflow::Sat if value < self.min() => Some(self.min() as i64),
+            BitOverflow::Sat => Some(self.max() as i64),
+            BitOverflow::Wrap => {
+                let wrapped = value.rem_euclid(1i128 << self.bits);
+                match self.signed && wrapped > self.max() {
+                    true => Some((wrapped - (1i128 << self.bits)) as i64),
+                    false => Some(wrapped as i64),
+                }
+            }
+        }
+    }
+}
+
+impl BitfieldOp {
+    fn is_write(&self) -> bool {
+        !matches!(self, BitfieldOp::Get { .. })
+    }
+
+    /// The byte length needed by the operation.
+    fn end(&self) -> usize {
+        let (ty, offset) = match self {
+            BitfieldOp::Get { ty, offset }
+            | BitfieldOp::Set { ty, offset, .. }
+            | BitfieldOp::IncrBy { ty, offset, .. } => (ty, offset),
+        };
+        (offset + ty.bits as usize).div_ceil(8)
+    }
+
+    fn apply(&self, buf: &mut Vec<u8>) -> Option<i64> {
+        match *self {
+            BitfieldOp::Get { ty, offset } => Some(ty.read(buf, offset)),
+            BitfieldOp::Set {
+                ty,
+                offset,
+                value,
+                overflow,
+            } => {
+                let old = ty.read(buf, offset);
+                ty.write(buf, offset, ty.fit(value as i128, overflow)?);
+                Some(old)
+            }
+            BitfieldOp::IncrBy {
+                ty,
+                offset,
+                increment,
+                overflow,
+            } => {
+                let value = ty.read(buf, offset) as i128 + increment as i128;
+                let value = ty.fit(value, overflow)?;
+                ty.write(buf, offset, value);
+                Some(value)
+            }
+        }
+    }
+}
+
+impl Backend {
+    /// Set or clear the bit, returns the original bit. The string grows as needed.
+    pub fn setbit(&self, key: &str, offset: usize, bit: bool) -> Result<u8, BackendError> {
+        self.write_string(key, |value, _| Ok(set_bit(value, offset, bit)))
+    }
+
+    pub fn getbit(&self, key: &str, offset: usize) -> Result<u8, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| get_bit(value, offset))?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// The number of set bits in the range (start, end, unit), or in the whole string.
+    pub fn bitcount(
+        &self,
+        key: &str,
+        range: Option<(i64, i64, BitUnit)>,
+    ) -> Result<i64, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| {
+            let Some((start, end)) = bit_range(value, range) else {
+                return 0;
+            };
+            // count the whole bytes at once, the partial bytes bit by bit
+            let (first, last) = (start.div_ceil(8), (end + 1) / 8);
+            if first >= last {
+                return (start..=end).map(|i| get_bit(value, i) as i64).sum();
+            }
+            let head = (start..first * 8)
+                .map(|i| get_bit(value, i) as i64)
+                .sum::<i64>();
+            let tail = (last * 8..=end)
+                .map(|i| get_bit(value, i) as i64)
+                .sum::<i64>();
+            let body = value[first..last]
+                .iter()
+                .map(|b| b.count_ones() as i64)
+                .sum::<i64>();
+            head + body + tail
+        })?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// The position of the first bit set to `bit` in the range, -1 if not found.
+    /// Without an explicit end, looking for a clear bit in a string full of set bits
+    /// returns the first bit after the string, as if it was padded with zeros.
+    pub fn bitpos(
+        &self,
+        key: &str,
+        bit: bool,
+        start: Option<i64>,
+        end: Option<i64>,
+        unit: BitUnit,
+    ) -> Result<i64, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| {
+            let range = start.map(|start| (start, end.unwrap_or(-1), unit));
+            let Some((first, last)) = bit_range(value, range) else {
+                return -1;
+            };
+            match (first..=last).find(|i| get_bit(value, *i) == bit as u8) {
+                Some(pos) => pos as i64,
+                None if !bit && end.is_none() => last as i64 + 1,
+                None => -1,
+            }
+        })?;
+        // a missing key is an empty string, so the first clear bit is at 0
+        Ok(ret.unwrap_or(if bit { -1 } else { 0 }))
+    }
+
+    /// Store the bitwise operation of the source keys in `dst`, missing keys are
+    /// treated as strings of zeros. Returns the length of the result.
+    pub fn bitop(&self, op: BitwiseOp, dst: &str, keys: &[String]) -> Result<i64, BackendError> {
+        let mut sources = Vec::with_capacity(keys.len());
+        for key in keys {
+            sources.push(
+                self.read(key, |value: &Vec<u8>| value.clone())?
+                    .unwrap_or_default(),
+            );
+        }
+        let len = sources.iter().map(Vec::len).max().unwrap_or(0);
+        let byte = |source: &Vec<u8>, i: usize| source.get(i).copied().unwrap_or(0);
+        let result = (0..len)
+            .map(|i| {
+                let mut bytes = sources.iter().map(|source| byte(source, i));
+                let first = bytes.next().unwrap_or(0);
+                match op {
+                    BitwiseOp::And => bytes.fold(first, |acc, b| acc & b),
+                    BitwiseOp::Or => bytes.fold(first, |acc, b| acc | b),
+                    BitwiseOp::Xor => bytes.fold(first, |acc, b| acc ^ b),
+                    BitwiseOp::Not => !first,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // an empty result deletes the destination
+        match result.is_empty() {
+            true => {
+                self.remove_key(dst);
+            }
+            false => self.store(dst, Value::String(result)),
+        }
+        Ok(len as i64)
+    }
+
+    /// Run the BITFIELD operations in order, None is the reply of a failed overflow.
+    pub fn bitfield(
+        &self,
+        key: &str,
+        ops: &[BitfieldOp],
+    ) -> Result<Vec<Option<i64>>, BackendError> {
+        if !ops.iter().any(BitfieldOp::is_write) {
+            let ret = self.read(key, |value: &Vec<u8>| {
+                let mut value = value.clone();
+                ops.iter().map(|op| op.apply(&mut value)).collect()
+            })?;
+            return Ok(ret.unwrap_or_else(|| vec![Some(0); ops.len()]));
+        }
+
+        self.write_string(key, |value, _| {
+            let end = ops
+                .iter()
+                .filter(|op| op.is_write())
+                .map(BitfieldOp::end)
+                .max()
+                .unwrap_or(0);
+            if value.len() < end {
+                value.resize(end, 0);
+            }
+            Ok(ops.iter().map(|op| op.apply(value)).collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(backend.setbit("b", 7, true)?, 0);
+        assert_eq!(backend.setbit("b", 7, true)?, 1);
+        assert_eq!(backend.get("b")?, Some(vec![1]));
+        assert_eq!(backend.getbit("b", 7)?, 1);
+        assert_eq!(backend.getbit("b", 100)?, 0);
+
+        backend.set("s".to_string(), "foobar");
+        assert_eq!(backend.bitcount("s", None)?, 26);
+        assert_eq!(backend.bitcount("s", Some((1, 1, BitUnit::Byte)))?, 6);
+        assert_eq!(backend.bitcount("s", Some((5, 30, BitUnit::Bit)))?, 17);
+
+        backend.set("p".to_string(), vec![0xff, 0xf0, 0x00]);
+        assert_eq!(backend.bitpos("p", false, None, None, BitUnit::Byte)?, 12);
+        assert_eq!(backend.bitpos("p", true, Some(2), None, BitUnit::Byte)?, -1);
+        assert_eq!(
+            backend.bitpos("p", true, Some(7), Some(15), BitUnit::Bit)?,
+            7
+        );
+        backend.set("full".to_string(), vec![0xff]);
+        assert_eq!(backend.bitpos("full", false, None, None, BitUnit::Byte)?, 8);
+        assert_eq!(
+            backend.bitpos("full", false, Some(0), Some(0), BitUnit::Byte)?,
+            -1
+        );
+        assert_eq!(backend.bitpos("none", false, None, None, BitUnit::Byte)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitop() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        backend.set("a".to_string(), "foobar");
+        backend.set("b".to_string(), "abcdef");
+        let keys = ["a".to_string(), "b".to_string()];
+        assert_eq!(backend.bitop(BitwiseOp::And, "dst", &keys)?, 6);
+        assert_eq!(backend.get("dst")?, Some(b"`bc`ab".to_vec()));
+        assert_eq!(backend.bitop(BitwiseOp::Not, "dst", &keys[..1])?, 6);
+        assert_eq!(backend.getrange("dst", 0, 0)?, vec![!b'f']);
+        assert_eq!(
+            backend.bitop(BitwiseOp::Or, "dst", &["none".to_string()])?,
+            0
+        );
+        assert!(!backend.exists("dst"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitfield_overflow() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let u8 = BitfieldType {
+            signed: false,
+            bits: 8,
+        };
+        let i8 = BitfieldType {
+            signed: true,
+            bits: 8,
+        };
+        let incr = |ty, increment, overflow| BitfieldOp::IncrBy {
+            ty,
+            offset: 0,
+            increment,
+            overflow,
+        };
+        let ops = [
+            BitfieldOp::Set {
+                ty: u8,
+                offset: 0,
+                value: 250,
+                overflow: BitOverflow::Wrap,
+            },
+            incr(u8, 10, BitOverflow::Wrap),
+            incr(u8, 300, BitOverflow::Sat),
+            incr(u8, 1, BitOverflow::Fail),
+            BitfieldOp::Get { ty: i8, offset: 0 },
+            incr(i8, -200, BitOverflow::Sat),
+            incr(i8, 200, BitOverflow::Wrap),
+        ];
+        assert_eq!(
+            backend.bitfield("bf", &ops)?,
+            vec![
+                Some(0),
+                Some(4),
+                Some(255),
+                None,
+                Some(-1),
+                Some(-128),
+                Some(72)
+            ]
+        );
+        assert_eq!(
+            backend.bitfield("none", &[BitfieldOp::Get { ty: u8, offset: 0 }])?,
+            vec![Some(0)]
+        );
+        assert!(!backend.exists("none"));
+        Ok(())
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::{oneshot, Notify};
+
+use crate::{BulkString, RespArray, RespFrame};
+
+use super::{Backend, BackendError, ListEnd};
+
+// Clients blocked by BLPOP / BRPOP / BLMOVE wait in a FIFO queue per key.
+// When a key becomes ready (an element is pushed), the pusher serves the waiters
+// of that key in order, handing the popped element directly to the waiter, so a
+// client blocked earlier is always served first.
+
+/// What a blocked client does once a key has elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockOp {
+    Pop(ListEnd),
+    Move {
+        destination: String,
+        from: ListEnd,
+        to: ListEnd,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct Waiter {
+    op: BlockOp,
+    // taken by whoever completes the wait: a serving pusher, or the waiter itself on timeout
+    tx: Mutex<Option<oneshot::Sender<RespFrame>>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct BlockedClients(Mutex<HashMap<String, VecDeque<Arc<Waiter>>>>);
+
+type Queues = HashMap<String, VecDeque<Arc<Waiter>>>;
+
+/// The clients blocked by XREAD, by key.
+#[derive(Debug, Default)]
+pub(crate) struct BlockedReaders(Mutex<HashMap<String, Vec<Arc<Notify>>>>);
+
+impl Backend {
+    /// Pop from the first non-empty list of `keys`, or block until an element is
+    /// pushed to one of them. `timeout` None blocks forever.
+    /// Returns the reply of the blocking command, None on timeout.
+    pub async fn block_pop(
+        &self,
+        keys: &[String],
+        op: BlockOp,
+        timeout: Option<Duration>,
+    ) -> Result<Option<RespFrame>, BackendError> {
+        let mut blocked = {
+            // NOTE: like any other command the pop holds the keyspace shared, so
+            // it doesn't run in the middle of a transaction, it's released before
+            // waiting. Take it before the queues, in the order of a pusher.
+            let _guard = self.lock_shared();
+            // NOTE: try and register with the lock held, so no push is missed in between
+            let mut queues = self.blocked.0.lock().unwrap_or_else(|e| e.into_inner());
+            for key in keys {
+                if let Some(reply) = self.serve_op(&mut queues, key, &op)? {
+                    return Ok(Some(reply));
+                }
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let waiter = Arc::new(Waiter {
+                op,
+                tx: Mutex::new(Some(tx)),
+            });
+            for key in keys {
+                queues
+                    .entry(key.clone())
+                    .or_default()
+                    .push_back(waiter.clone());
+            }
+            BlockedPop {
+                backend: self,
+                keys,
+                waiter,
+                rx,
+            }
+        };
+
+        let reply = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, &mut blocked.rx).await {
+                Ok(reply) => reply.ok(),
+                // if the sender is already taken, a pusher is serving us right now
+                Err(_) if blocked.waiter.take_tx().is_some() => None,
+                Err(_) => (&mut blocked.rx).await.ok(),
+            },
+            None => (&mut blocked.rx).await.ok(),
+        };
+        Ok(reply)
+    }
+
+    /// The non-blocking version of `block_pop`, pop from the first non-empty list of `keys`.
+    pub fn try_pop_first(
+        &self,
+        keys: &[String],
+        op: &BlockOp,
+    ) -> Result<Option<RespFrame>, BackendError> {
+        let mut queues = self.blocked.0.lock().unwrap_or_else(|e| e.into_inner());
+        for key in keys {
+            if let Some(reply) = self.serve_op(&mut queues, key, op)? {
+                return Ok(Some(reply));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Serve the clients blocked on the key, called after elements are pushed to it.
+    pub(crate) fn signal_ready(&self, key: &str) {
+        let mut queues = self.blocked.0.lock().unwrap_or_else(|e| e.into_inner());
+        self.serve_ready(&mut queues, key);
+        drop(queues);
+
+        let readers = self.readers.0.lock().unwrap_or_else(|e| e.into_inner());
+        for reader in readers.get(key).into_iter().flatten() {
+            reader.notify_one();
+        }
+    }
+
+    /// Call `f` until it returns a value, waiting for a change of the keys in between.
+    /// `timeout` None waits forever, returns None on timeout.
+    pub(crate) async fn block_read<R>(
+        &self,
+        keys: &[String],
+        timeout: Option<Duration>,
+        mut f: impl FnMut() -> Result<Option<R>, BackendError>,
+    ) -> Result<Option<R>, BackendError> {
+        // NOTE: register before the first try, a notification in between is kept as a permit
+        let reader = Arc::new(Notify::new());
+        {
+            let mut readers = self.readers.0.lock().unwrap_or_else(|e| e.into_inner());
+            for key in keys {
+                readers.entry(key.clone()).or_default().push(reader.clone());
+            }
+        }
+        let _blocked = BlockedReader {
+            backend: self,
+            keys,
+            reader: reader.clone(),
+        };
+
+        let deadline = timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+        let ret = loop {
+            // hold the keyspace shared for each read, but not while waiting
+            let read = {
+                let _guard = self.lock_shared();
+                f()
+            };
+            match read {
+                Ok(None) => {}
+                ret => break ret,
+            }
+            match deadline {
+                Some(deadline) => {
+                    let notified = tokio::time::timeout_at(deadline, reader.notified());
+                    if notified.await.is_err() {
+                        break Ok(None);
+                    }
+                }
+                None => reader.notified().await,
+            }
+        };
+        ret
+    }
+
+    /// Hand out the elements of the key to its blocked clients in FIFO order.
+    fn serve_ready(&self, queues: &mut Queues, key: &str) {
+        let mut ready = VecDeque::from([key.to_string()]);
+        while let Some(key) = ready.pop_front() {
+            while let Some(waiter) = queues.get(&key).and_then(|q| q.front()).cloned() {
+                if waiter.is_done() {
+                    pop_waiter(queues, &key);
+                    continue;
+                }
+                // nothing left to hand out
+                let Ok(Some(value)) = self.pop_inner(&key, op_end(&waiter.op)) else {
+                    break;
+                };
+                pop_waiter(queues, &key);
+
+                let Some(tx) = waiter.take_tx() else {
+                    // the waiter timed out meanwhile, put the element back
+                    let _ = self.push_inner(&key, op_end(&waiter.op), vec![value], false);
+                    continue;
+                };
+                let reply = match self.complete_op(&key, &waiter.op, value) {
+                    Ok(reply) => reply,
+                    Err(e) => e.into(),
+                };
+                if let BlockOp::Move { destination, .. } = &waiter.op {
+                    // the destination may have blocked clients too
+                    ready.push_back(destination.clone());
+                }
+                if let Err(reply) = tx.send(reply) {
+                    // the client is gone meanwhile, the next one is served instead
+                    self.undo_pop(&waiter.op, reply);
+                }
+            }
+        }
+    }
+
+    /// Try to serve `op` on the key right now, without blocking.
+    fn serve_op(
+        &self,
+        queues: &mut Queues,
+        key: &str,
+        op: &BlockOp,
+    ) -> Result<Option<RespFrame>, BackendError> {
+        if let BlockOp::Move { destination, .. } = op {
+            // check the type of the destination before modifying the source
+            self.read(destination, |_: &VecDeque<RespFrame>| ())?;
+        }
+        let Some(value) = self.pop_inner(key, op_end(op))? else {
+            return Ok(None);
+        };
+        let reply = self.complete_op(key, op, value)?;
+        if let BlockOp::Move { destination, .. } = op {
+            self.serve_ready(queues, destination);
+        }
+        Ok(Some(reply))
+    }
+
+    /// Finish the op with the element popped from the key, returns the reply.
+    fn complete_op(
+        &self,
+        key: &str,
+        op: &BlockOp,
+        value: RespFrame,
+    ) -> Result<RespFrame, BackendError> {
+        match op {
+            BlockOp::Pop(_) => Ok(RespArray::new([BulkString::from(key).into(), value]).into()),
+            BlockOp::Move {
+                destination, to, ..
+            } => {
+                if let Err(e) = self.push_inner(destination, *to, vec![value.clone()], false) {
+                    let _ = self.push_inner(key, op_end(op), vec![value], false);
+                    return Err(e);
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    /// Put back the element of a BLPOP / BRPOP reply which can't be delivered,
+    /// returns its key. A moved element stays in the destination, it is not lost.
+    fn undo_pop(&self, op: &BlockOp, reply: RespFrame) -> Option<String> {
+        let (BlockOp::Pop(end), RespFrame::Array(reply)) = (op, reply) else {
+            return None;
+        };
+        let [RespFrame::BulkString(key), value] = <[RespFrame; 2]>::try_from(reply.0).ok()? else {
+            return None;
+        };
+        let key = String::from_utf8(key.0).ok()?;
+        self.push_inner(&key, *end, vec![value], false).ok()?;
+        Some(key)
+    }
+
+    fn unregister(&self, keys: &[String], waiter: &Arc<Waiter>) {
+        let mut queues = self.blocked.0.lock().unwrap_or_else(|e| e.into_inner());
+        for key in keys {
+            if let Some(queue) = queues.get_mut(key) {
+                queue.retain(|w| !Arc::ptr_eq(w, waiter));
+                if queue.is_empty() {
+                    queues.remove(key);
+                }
+            }
+        }
+    }
+}
+
+/// A client blocked by `block_pop`, unregistered when dropped, also when the
+/// wait is cancelled, e.g. because the connection is closed.
+struct BlockedPop<'a> {
+    backend: &'a Backend,
+    keys: &'a [String],
+    waiter: Arc<Waiter>,
+    rx: oneshot::Receiver<RespFrame>,
+}
+
+impl Drop for BlockedPop<'_> {
+    fn drop(&mut self) {
+        // no pusher serves the waiter anymore once it is unregistered, but one
+        // may have served it before, without the reply being received
+        self.waiter.take_tx();
+        self.backend.unregister(self.keys, &self.waiter);
+        if let Ok(reply) = self.rx.try_recv() {
+            if let Some(key) = self.backend.undo_pop(&self.waiter.op, reply) {
+                let queues = self.backend.blocked.0.lock();
+                let mut queues = queues.unwrap_or_else(|e| e.into_inner());
+                self.backend.serve_ready(&mut queues, &key);
+            }
+        }
+    }
+}
+
+/// A client blocked by `block_read`, unregistered when dropped.
+struct BlockedReader<'a> {
+    backend: &'a Backend,
+    keys: &'a [String],
+    reader: Arc<Notify>,
+}
+
+impl Drop for BlockedReader<'_> {
+    fn drop(&mut self) {
+        let readers = self.backend.readers.0.lock();
+        let mut readers = readers.unwrap_or_else(|e| e.into_inner());
+        for key in self.keys {
+            if let Some(list) = readers.get_mut(key) {
+                list.retain(|r| !Arc::ptr_eq(r, &self.reader));
+                if list.is_empty() {
+                    readers.remove(key);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl Backend {
+    pub(crate) fn blocked_is_empty(&self) -> bool {
+        self.blocked.0.lock().unwrap().is_empty() && self.readers.0.lock().unwrap().is_empty()
+    }
+}
+
+impl Waiter {
+    fn take_tx(&self) -> Option<oneshot::Sender<RespFrame>> {
+        self.tx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    fn is_done(&self) -> bool {
+        self.tx.lock().unwrap_or_else(|e| e.into_inner()).is_none()
+    }
+}
+
+fn op_end(op: &BlockOp) -> ListEnd {
+    match op {
+        BlockOp::Pop(end) => *end,
+        BlockOp::Move { from, .. } => *from,
+    }
+}
+
+fn pop_waiter(queues: &mut Queues, key: &str) {
+    if let Some(queue) = queues.get_mut(key) {
+        queue.pop_front();
+        if queue.is_empty() {
+            queues.remove(key);
+        }
+    }
+}
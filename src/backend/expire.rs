@@ -0,0 +1,247 @@
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use dashmap::mapref::entry::Entry;
+use tracing::info;
+
+use super::Backend;
+
+const ACTIVE_EXPIRE_KEYS_PER_LOOP: usize = 20;
+const ACTIVE_EXPIRE_CYCLE_TIME: Duration = Duration::from_millis(25);
+
+/// Condition flags of EXPIRE-like commands, see https://redis.io/docs/latest/commands/expire/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpireCondition {
+    #[default]
+    Always,
+    Nx,   // only when the key has no expiry
+    Xx,   // only when the key has an existing expiry
+    Gt,   // only when the new expiry is greater than the current one
+    Lt,   // only when the new expiry is less than the current one
+    XxLt, // XX LT: only when the key has an expiry greater than the new one
+}
+
+/// Current unix time in milliseconds.
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+impl Backend {
+    /// Lazy expiration: remove the key if its deadline has passed.
+    /// Returns true if the key was expired and removed.
+    pub(crate) fn expire_if_needed(&self, key: &str) -> bool {
+        let now = now_ms();
+        if self.expires.get(key).is_none_or(|at| *at > now) {
+            return false;
+        }
+        // NOTE: check the deadline again while the value is held, a SET meanwhile
+        // discards the TTL and its new value must stay
+        let expired = |_: &String, at: &i64| *at <= now;
+        let removed = match self.db.entry(key.to_string()) {
+            Entry::Occupied(entry) if self.expires.remove_if(key, expired).is_some() => {
+                entry.remove();
+                true
+            }
+            Entry::Occupied(_) => false,
+            // the TTL of a key which doesn't exist anymore
+            Entry::Vacant(_) => {
+                self.expires.remove_if(key, expired);
+                false
+            }
+        };
+        if removed {
+            self.touch_watched(key);
+        }
+        removed
+    }
+
+    /// Set the deadline (unix time in milliseconds) of the key.
+    /// Returns false if the key does not exist or the condition is not met.
+    pub fn expire_at(&self, key: &str, at: i64, condition: ExpireCondition) -> bool {
+        self.expire_if_needed(key);
+        // NOTE: the deadline is written while the value is held, so a concurrent
+        // DEL doesn't leave it behind for the next value of the key
+        let Entry::Occupied(entry) = self.db.entry(key.to_string()) else {
+            return false;
+        };
+
+        let current = self.expires.get(key).map(|v| *v);
+        let ok = match (condition, current) {
+            (ExpireCondition::Always, _) => true,
+            (ExpireCondition::Nx, current) => current.is_none(),
+            (ExpireCondition::Xx, current) => current.is_some(),
+            // a key without ttl is treated as an infinite ttl
+            (ExpireCondition::Gt, Some(current)) => at > current,
+            (ExpireCondition::Gt, None) => false,
+            (ExpireCondition::Lt, Some(current)) => at < current,
+            (ExpireCondition::Lt, None) => true,
+            (ExpireCondition::XxLt, current) => current.is_some_and(|current| at < current),
+        };
+        if !ok {
+            return false;
+        }
+
+        if at <= now_ms() {
+            self.expires.remove(key);
+            entry.remove();
+        } else {
+            self.expires.insert(key.to_string(), at);
+            drop(entry);
+        }
+        self.touch_watched(key);
+        true
+    }
+
+    /// Remaining time to live in milliseconds, -1 if the key has no ttl, -2 if the key does not exist.
+    pub fn pttl(&self, key: &str) -> i64 {
+        match self.expire_time(key) {
+            at if at < 0 => at,
+            at => (at - now_ms()).max(0),
+        }
+    }
+
+    /// Absolute deadline in milliseconds, -1 if the key has no ttl, -2 if the key does not exist.
+    pub fn expire_time(&self, key: &str) -> i64 {
+        if !self.exists(key) {
+            return -2;
+        }
+        self.expires.get(key).map(|v| *v).unwrap_or(-1)
+    }
+
+    /// Remove the ttl of the key. Returns true if a ttl was removed.
+    pub fn persist(&self, key: &str) -> bool {
+        let persisted = self.exists(key) && self.expires.remove(key).is_some();
+        if persisted {
+            self.touch_watched(key);
+        }
+        persisted
+    }
+
+    /// Active expiration, like redis: sample the keys with a TTL 20 at a time,
+    /// from where the previous cycle stopped, and go on while more than 25% of
+    /// a sample is expired, for at most 25 milliseconds.
+    /// Returns the number of removed keys.
+    pub fn purge_expired(&self) -> usize {
+        let started = Instant::now();
+        let mut cursor = self.expire_cursor.load(Ordering::Relaxed);
+        let mut removed = 0;
+        loop {
+            let now = now_ms();
+            let (mut sampled, mut expired) = (0, vec![]);
+            cursor = self.scan_expires(cursor, ACTIVE_EXPIRE_KEYS_PER_LOOP, |key, at| {
+                sampled += 1;
+                if at <= now {
+                    expired.push(key.clone());
+                }
+            });
+            // the keys are removed once the shards of `expires` are unlocked
+            let n = expired
+                .iter()
+                .filter(|key| self.expire_if_needed(key))
+                .count();
+            removed += n;
+            if cursor == 0 || n * 4 <= sampled || started.elapsed() >= ACTIVE_EXPIRE_CYCLE_TIME {
+                break;
+            }
+        }
+        self.expire_cursor.store(cursor, Ordering::Relaxed);
+        removed
+    }
+
+    /// Background task which periodically purges expired keys, so that keys
+    /// which are never accessed again do not live forever.
+    pub async fn active_expire(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let n = self.purge_expired();
+            if n > 0 {
+                info!("Active expire removed {} keys", n);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire_and_ttl() {
+        let backend = Backend::new();
+        assert_eq!(backend.pttl("hello"), -2);
+
+        backend.set("hello".to_string(), "world");
+        assert_eq!(backend.pttl("hello"), -1);
+
+        assert!(backend.expire_at("hello", now_ms() + 10_000, ExpireCondition::Nx));
+        assert!(!backend.expire_at("hello", now_ms() + 20_000, ExpireCondition::Nx));
+        assert!(!backend.expire_at("hello", now_ms() + 1_000, ExpireCondition::Gt));
+        assert!(!backend.expire_at("other", now_ms() + 1_000, ExpireCondition::XxLt));
+        let ttl = backend.pttl("hello");
+        assert!(ttl > 9_000 && ttl <= 10_000);
+
+        assert!(backend.persist("hello"));
+        assert_eq!(backend.pttl("hello"), -1);
+        assert!(!backend.persist("hello"));
+    }
+
+    #[test]
+    fn test_expired_key_is_removed() {
+        let backend = Backend::new();
+        backend.sadd("set", vec!["hello".to_string()]).unwrap();
+        backend.expires.insert("set".to_string(), now_ms() - 1);
+        assert_eq!(backend.sismember("set", "hello"), Ok(false));
+        assert!(!backend.db.contains_key("set"));
+
+        backend.set("hello".to_string(), "world");
+        backend.expires.insert("hello".to_string(), now_ms() - 1);
+        assert_eq!(backend.purge_expired(), 1);
+        assert!(!backend.db.contains_key("hello"));
+    }
+
+    #[test]
+    fn test_expire_concurrent_del() {
+        let backend = Backend::new();
+        let cloned = backend.clone();
+        let handle = std::thread::spawn(move || {
+            for _ in 0..2000 {
+                cloned.expire_at("hello", now_ms() + 10_000, ExpireCondition::Always);
+            }
+        });
+        for _ in 0..2000 {
+            backend.set("hello".to_string(), "world");
+            backend.del(&["hello".to_string()]);
+        }
+        handle.join().unwrap();
+        // a removed key never keeps its TTL
+        assert!(!backend.db.contains_key("hello"));
+        assert!(!backend.expires.contains_key("hello"));
+    }
+
+    #[test]
+    fn test_purge_expired_samples_the_keys() {
+        let backend = Backend::new();
+        for i in 0..1000 {
+            backend.set(format!("live:{}", i), "value");
+            backend
+                .expires
+                .insert(format!("live:{}", i), now_ms() + 10_000);
+        }
+        for i in 0..10 {
+            backend.set(format!("dead:{}", i), "value");
+            backend.expires.insert(format!("dead:{}", i), now_ms() - 1);
+        }
+        // few keys are expired, a cycle stops after a sample
+        assert!(backend.purge_expired() <= ACTIVE_EXPIRE_KEYS_PER_LOOP);
+
+        // the next cycles go on from there, until all the keys are sampled
+        for _ in 0..100 {
+            backend.purge_expired();
+        }
+        assert_eq!(backend.db.len(), 1000);
+    }
+}
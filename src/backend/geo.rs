@@ -0,0 +1,515 @@
+use super::{Backend, BackendError, ScoreBound, Value, ZAddFlags, ZRangeBy, ZSet};
+
+// The geospatial index is a sorted set scored by the 52 bit geohash of the
+// coordinates, interleaving 26 bits of latitude and 26 bits of longitude as redis
+// does, so that a geohash cell is a range of scores. See geohash.c and
+// geohash_helper.c of redis.
+
+const GEO_STEP_MAX: u32 = 26;
+pub const GEO_LAT_MIN: f64 = -85.05112878;
+pub const GEO_LAT_MAX: f64 = 85.05112878;
+pub const GEO_LONG_MIN: f64 = -180.0;
+pub const GEO_LONG_MAX: f64 = 180.0;
+const EARTH_RADIUS_IN_METERS: f64 = 6372797.560856;
+const MERCATOR_MAX: f64 = 20037726.37;
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// The center of a GEOSEARCH.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoOrigin {
+    Member(String),
+    LonLat(f64, f64),
+}
+
+/// The shape of a GEOSEARCH, in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeoShape {
+    Radius(f64),
+    Box { width: f64, height: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoSort {
+    Asc,
+    Desc,
+}
+
+/// The query of GEOSEARCH, see https://redis.io/docs/latest/commands/geosearch/
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoQuery {
+    pub origin: GeoOrigin,
+    pub shape: GeoShape,
+    pub sort: Option<GeoSort>,
+    pub count: Option<usize>,
+    pub any: bool, // stop as soon as `count` matches are found
+}
+
+/// A member matching a GEOSEARCH.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoMatch {
+    pub member: String,
+    pub dist: f64, // in meters
+    pub hash: u64,
+    pub lon: f64,
+    pub lat: f64,
+}
+
+/// A geohash of `step` bits for each coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HashBits {
+    bits: u64,
+    step: u32,
+}
+
+/// The cell of a geohash, (min, max) for each coordinate.
+#[derive(Debug, Clone, Copy)]
+struct Area {
+    lon: (f64, f64),
+    lat: (f64, f64),
+}
+
+/// Interleave the bits, x in the even bits and y in the odd bits.
+fn interleave(x: u32, y: u32) -> u64 {
+    (0..32).fold(0, |bits, i| {
+        bits | ((x as u64 >> i) & 1) << (2 * i) | ((y as u64 >> i) & 1) << (2 * i + 1)
+    })
+}
+
+fn deinterleave(bits: u64) -> (u32, u32) {
+    (0..32).fold((0, 0), |(x, y), i| {
+        (
+            x | (((bits >> (2 * i)) & 1) as u32) << i,
+            y | (((bits >> (2 * i + 1)) & 1) as u32) << i,
+        )
+    })
+}
+
+fn encode(lon: f64, lat: f64, step: u32, lat_range: (f64, f64)) -> HashBits {
+    let cells = (1u64 << step) as f64;
+    let offset = |value: f64, (min, max): (f64, f64)| {
+        (((value - min) / (max - min) * cells) as u32).min((1 << step) - 1)
+    };
+    let lat_offset = offset(lat, lat_range);
+    let lon_offset = offset(lon, (GEO_LONG_MIN, GEO_LONG_MAX));
+    HashBits {
+        bits: interleave(lat_offset, lon_offset),
+        step,
+    }
+}
+
+fn decode(hash: HashBits) -> Area {
+    let (lat, lon) = deinterleave(hash.bits);
+    let cells = (1u64 << hash.step) as f64;
+    let area = |offset: u32, (min, max): (f64, f64)| {
+        (
+            min + offset as f64 / cells * (max - min),
+            min + (offset as f64 + 1.0) / cells * (max - min),
+        )
+    };
+    Area {
+        lon: area(lon, (GEO_LONG_MIN, GEO_LONG_MAX)),
+        lat: area(lat, (GEO_LAT_MIN, GEO_LAT_MAX)),
+    }
+}
+
+/// The score of the coordinates in the sorted set.
+fn geo_score(lon: f64, lat: f64) -> f64 {
+    encode(lon, lat, GEO_STEP_MAX, (GEO_LAT_MIN, GEO_LAT_MAX)).bits as f64
+}
+
+/// The coordinates of a score, the center of its cell.
+fn geo_coords(score: f64) -> (f64, f64) {
+    let area = decode(HashBits {
+        bits: score as u64,
+        step: GEO_STEP_MAX,
+    });
+    let lon = ((area.lon.0 + area.lon.1) / 2.0).clamp(GEO_LONG_MIN, GEO_LONG_MAX);
+    let lat = ((area.lat.0 + area.lat.1) / 2.0).clamp(GEO_LAT_MIN, GEO_LAT_MAX);
+    (lon, lat)
+}
+
+/// The standard 11 characters geohash string, which uses the [-90, 90] latitude range.
+fn geohash_string(lon: f64, lat: f64) -> String {
+    let hash = encode(lon, lat, GEO_STEP_MAX, (-90.0, 90.0));
+    (0..11)
+        .map(|i| {
+            // the 52 bits give 10 characters and 2 bits, the last one is padded as 0
+            let index = match i {
+                10 => 0,
+                _ => (hash.bits >> (52 - (i + 1) * 5)) & 0x1f,
+            };
+            GEOHASH_ALPHABET[index as usize] as char
+        })
+        .collect()
+}
+
+/// The haversine distance in meters.
+pub fn geo_distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let u = ((lat2 - lat1) / 2.0).sin();
+    let v = ((lon2.to_radians() - lon1.to_radians()) / 2.0).sin();
+    2.0 * EARTH_RADIUS_IN_METERS * (u * u + lat1.cos() * lat2.cos() * v * v).sqrt().asin()
+}
+
+/// Move the hash along the longitude (x, odd bits) or the latitude (y, even bits).
+fn move_hash(hash: HashBits, dx: i8, dy: i8) -> HashBits {
+    const ODD: u64 = 0xaaaaaaaaaaaaaaaa;
+    const EVEN: u64 = 0x5555555555555555;
+    let shift = 64 - hash.step * 2;
+    let step = |bits: u64, ones: u64, d: i8, mask: u64| {
+        let bits = match d {
+            0 => return bits,
+            1 => bits.wrapping_add(ones.wrapping_add(1)),
+            _ => (bits | ones).wrapping_sub(ones.wrapping_add(1)),
+        };
+        bits & (mask >> shift)
+    };
+    let x = step(hash.bits & ODD, EVEN >> shift, dx, ODD);
+    let y = step(hash.bits & EVEN, ODD >> shift, dy, EVEN);
+    HashBits {
+        bits: x | y,
+        step: hash.step,
+    }
+}
+
+/// The precision of the geohash cells to search, so that the 9 cells around the
+/// center cover the radius.
+fn estimate_steps(mut range: f64, lat: f64) -> u32 {
+    if range == 0.0 {
+        return GEO_STEP_MAX;
+    }
+    let mut step: i32 = 1;
+    while range < MERCATOR_MAX {
+        range *= 2.0;
+        step += 1;
+    }
+    step -= 2; // make sure the range is included in most of the base cases
+               // the cells are narrower near the poles
+    if !(-66.0..=66.0).contains(&lat) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&lat) {
+            step -= 1;
+        }
+    }
+    step.clamp(1, GEO_STEP_MAX as i32) as u32
+}
+
+impl GeoShape {
+    /// The width and the height of the bounding box, and the radius which covers it.
+    fn dimensions(&self) -> (f64, f64, f64) {
+        match *self {
+            GeoShape::Radius(radius) => (radius * 2.0, radius * 2.0, radius),
+            GeoShape::Box { width, height } => (width, height, (width / 2.0).hypot(height / 2.0)),
+        }
+    }
+
+    /// The distance from the center if the point is in the shape.
+    fn distance(&self, lon: f64, lat: f64, x: f64, y: f64) -> Option<f64> {
+        match *self {
+            GeoShape::Radius(radius) => {
+                let dist = geo_distance(lon, lat, x, y);
+                (dist <= radius).then_some(dist)
+            }
+            GeoShape::Box { width, height } => {
+                // the latitude distance is cheaper, check it first
+                let lat_dist = EARTH_RADIUS_IN_METERS * (y.to_radians() - lat.to_radians()).abs();
+                if lat_dist > height / 2.0 || geo_distance(x, y, lon, y) > width / 2.0 {
+                    return None;
+                }
+                Some(geo_distance(lon, lat, x, y))
+            }
+        }
+    }
+
+    /// The geohash cells to scan, the center cell and its useful neighbors.
+    fn cells(&self, lon: f64, lat: f64) -> Vec<HashBits> {
+        let (width, height, radius) = self.dimensions();
+        let lat_delta = (height / 2.0 / EARTH_RADIUS_IN_METERS).to_degrees();
+        let lon_delta =
+            |lat: f64| (width / 2.0 / EARTH_RADIUS_IN_METERS / lat.to_radians().cos()).to_degrees();
+        // the box is wider on the side closer to the equator
+        let lon_delta = match lat < 0.0 {
+            true => lon_delta(lat - lat_delta),
+            false => lon_delta(lat + lat_delta),
+        };
+        let (min_lon, max_lon) = (lon - lon_delta, lon + lon_delta);
+        let (min_lat, max_lat) = (lat - lat_delta, lat + lat_delta);
+
+        const AROUND: [(i8, i8); 9] = [
+            (0, 0),
+            (0, 1),
+            (0, -1),
+            (1, 0),
+            (-1, 0),
+            (-1, 1),
+            (1, 1),
+            (-1, -1),
+            (1, -1),
+        ];
+        let lat_range = (GEO_LAT_MIN, GEO_LAT_MAX);
+        let mut steps = estimate_steps(radius, lat);
+        let mut center = encode(lon, lat, steps, lat_range);
+        // the neighbors may not cover the box if the cells are too small
+        let north = decode(move_hash(center, 0, 1));
+        let south = decode(move_hash(center, 0, -1));
+        let east = decode(move_hash(center, 1, 0));
+        let west = decode(move_hash(center, -1, 0));
+        if steps > 1
+            && (north.lat.1 < max_lat
+                || south.lat.0 > min_lat
+                || east.lon.1 < max_lon
+                || west.lon.0 > min_lon)
+        {
+            steps -= 1;
+            center = encode(lon, lat, steps, lat_range);
+        }
+
+        // skip the neighbors on the sides the center cell already covers
+        let area = decode(center);
+        let useless = |dx: i8, dy: i8| {
+            steps >= 2
+                && ((dy == -1 && area.lat.0 < min_lat)
+                    || (dy == 1 && area.lat.1 > max_lat)
+                    || (dx == -1 && area.lon.0 < min_lon)
+                    || (dx == 1 && area.lon.1 > max_lon))
+        };
+        let mut cells = Vec::with_capacity(AROUND.len());
+        for (dx, dy) in AROUND {
+            let cell = move_hash(center, dx, dy);
+            if !useless(dx, dy) && !cells.contains(&cell) {
+                cells.push(cell);
+            }
+        }
+        cells
+    }
+}
+
+impl Backend {
+    /// Add the (longitude, latitude, member) points, returns what ZADD returns.
+    pub fn geoadd(
+        &self,
+        key: &str,
+        points: Vec<(f64, f64, String)>,
+        flags: ZAddFlags,
+    ) -> Result<i64, BackendError> {
+        let elements = points
+            .into_iter()
+            .map(|(lon, lat, member)| (geo_score(lon, lat), member))
+            .collect();
+        self.zadd(key, elements, flags)
+    }
+
+    /// The (longitude, latitude) of the members.
+    pub fn geopos(
+        &self,
+        key: &str,
+        members: &[String],
+    ) -> Result<Vec<Option<(f64, f64)>>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| {
+            members
+                .iter()
+                .map(|member| zset.score(member).map(geo_coords))
+                .collect()
+        })?;
+        Ok(ret.unwrap_or_else(|| vec![None; members.len()]))
+    }
+
+    /// The distance between the members in meters, None if any of them is missing.
+    pub fn geodist(&self, key: &str, from: &str, to: &str) -> Result<Option<f64>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| {
+            let (lon1, lat1) = geo_coords(zset.score(from)?);
+            let (lon2, lat2) = geo_coords(zset.score(to)?);
+            Some(geo_distance(lon1, lat1, lon2, lat2))
+        })?;
+        Ok(ret.flatten())
+    }
+
+    /// The geohash strings of the members.
+    pub fn geohash(
+        &self,
+        key: &str,
+        members: &[String],
+    ) -> Result<Vec<Option<String>>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| {
+            members
+                .iter()
+                .map(|member| {
+                    let (lon, lat) = geo_coords(zset.score(member)?);
+                    Some(geohash_string(lon, lat))
+                })
+                .collect()
+        })?;
+        Ok(ret.unwrap_or_else(|| vec![None; members.len()]))
+    }
+
+    /// The members in the shape, scanning the score ranges of the geohash cells
+    /// around the center.
+    pub fn geosearch(&self, key: &str, search: &GeoQuery) -> Result<Vec<GeoMatch>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| {
+            let (lon, lat) = match &search.origin {
+                GeoOrigin::Member(member) => zset
+                    .score(member)
+                    .map(geo_coords)
+                    .ok_or(BackendError::GeoMemberNotFound)?,
+                GeoOrigin::LonLat(lon, lat) => (*lon, *lat),
+            };
+
+            let limit = search.count.filter(|_| search.any).unwrap_or(usize::MAX);
+            let mut matches = Vec::new();
+            'cells: for cell in search.shape.cells(lon, lat) {
+                let shift = (GEO_STEP_MAX - cell.step) * 2;
+                let range = ZRangeBy::Score(
+                    ScoreBound {
+                        value: (cell.bits << shift) as f64,
+                        exclusive: false,
+                    },
+                    ScoreBound {
+                        value: ((cell.bits + 1) << shift) as f64,
+                        exclusive: true,
+                    },
+                );
+                for (member, score) in zset.range(&range, false, None) {
+                    let (x, y) = geo_coords(score);
+                    if let Some(dist) = search.shape.distance(lon, lat, x, y) {
+                        matches.push(GeoMatch {
+                            member,
+                            dist,
+                            hash: score as u64,
+                            lon: x,
+                            lat: y,
+                        });
+                        if matches.len() >= limit {
+                            break 'cells;
+                        }
+                    }
+                }
+            }
+
+            match search.sort {
+                Some(GeoSort::Asc) => matches.sort_by(|a, b| a.dist.total_cmp(&b.dist)),
+                Some(GeoSort::Desc) => matches.sort_by(|a, b| b.dist.total_cmp(&a.dist)),
+                None => {}
+            }
+            if let Some(count) = search.count {
+                matches.truncate(count);
+            }
+            Ok(matches)
+        })?;
+        ret.unwrap_or(Ok(Vec::new()))
+    }
+
+    /// Store the matches of the search in the sorted set `dst`, scored by their
+    /// geohash, or by their distance divided by `dist_unit` if it is given.
+    /// Returns the number of the stored members.
+    pub fn geosearch_store(
+        &self,
+        dst: &str,
+        key: &str,
+        search: &GeoQuery,
+        dist_unit: Option<f64>,
+    ) -> Result<i64, BackendError> {
+        let mut zset = ZSet::default();
+        for m in self.geosearch(key, search)? {
+            let score = match dist_unit {
+                Some(unit) => m.dist / unit,
+                None => m.hash as f64,
+            };
+            zset.insert(m.member, score);
+        }
+        let len = zset.len();
+        self.store(dst, Value::ZSet(zset));
+        Ok(len as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sicily(backend: &Backend) -> Result<(), BackendError> {
+        let points = vec![
+            (13.361389, 38.115556, "Palermo".to_string()),
+            (15.087269, 37.502669, "Catania".to_string()),
+            (12.758489, 38.788135, "edge1".to_string()),
+            (17.241510, 38.788135, "edge2".to_string()),
+        ];
+        assert_eq!(backend.geoadd("Sicily", points, ZAddFlags::default())?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geohash_encoding() {
+        assert_eq!(
+            deinterleave(interleave(0x3ffffff, 0x1234567)),
+            (0x3ffffff, 0x1234567)
+        );
+        assert_eq!(geo_score(13.361389, 38.115556), 3479099956230698.0);
+        let (lon, lat) = geo_coords(3479099956230698.0);
+        assert!((lon - 13.361389).abs() < 1e-5 && (lat - 38.115556).abs() < 1e-5);
+        assert_eq!(geohash_string(13.361389, 38.115556), "sqc8b49rny0");
+        assert_eq!(geohash_string(15.087269, 37.502669), "sqdtr74hyu0");
+    }
+
+    #[test]
+    fn test_geodist_geopos() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        sicily(&backend)?;
+        let dist = backend
+            .geodist("Sicily", "Palermo", "Catania")?
+            .unwrap_or_default();
+        assert!((dist - 166274.1516).abs() < 1e-3, "{}", dist);
+        assert_eq!(backend.geodist("Sicily", "Palermo", "none")?, None);
+
+        let pos = backend.geopos("Sicily", &["Palermo".to_string(), "none".to_string()])?;
+        assert!(pos[0].is_some());
+        assert_eq!(pos[1], None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_geosearch() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        sicily(&backend)?;
+        let mut search = GeoQuery {
+            origin: GeoOrigin::LonLat(15.0, 37.0),
+            shape: GeoShape::Radius(200_000.0),
+            sort: Some(GeoSort::Asc),
+            count: None,
+            any: false,
+        };
+        let members =
+            |matches: Vec<GeoMatch>| matches.into_iter().map(|m| m.member).collect::<Vec<_>>();
+        assert_eq!(
+            members(backend.geosearch("Sicily", &search)?),
+            ["Catania", "Palermo"]
+        );
+
+        search.shape = GeoShape::Box {
+            width: 400_000.0,
+            height: 400_000.0,
+        };
+        search.sort = Some(GeoSort::Desc);
+        assert_eq!(
+            members(backend.geosearch("Sicily", &search)?),
+            ["edge1", "edge2", "Palermo", "Catania"]
+        );
+
+        search.origin = GeoOrigin::Member("Palermo".to_string());
+        search.shape = GeoShape::Radius(100_000.0);
+        search.sort = Some(GeoSort::Asc);
+        search.count = Some(1);
+        assert_eq!(members(backend.geosearch("Sicily", &search)?), ["Palermo"]);
+
+        assert_eq!(
+            backend.geosearch_store("dst", "Sicily", &search, Some(1000.0))?,
+            1
+        );
+        assert_eq!(backend.zscore("dst", "Palermo")?, Some(0.0));
+        search.origin = GeoOrigin::Member("none".to_string());
+        assert_eq!(
+            backend.geosearch("Sicily", &search),
+            Err(BackendError::GeoMemberNotFound)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use super::value::HashMap;
+
+use crate::{BulkString, RespFrame};
+
+use super::{
+    random::{random_choices, random_sample},
+    Backend, BackendError,
+};
+
+type Hash = HashMap<String, RespFrame>;
+
+/// The bytes of a field value, the values are always set as bulk strings.
+fn value_bytes(value: &RespFrame) -> &[u8] {
+    match value {
+        RespFrame::BulkString(s) => s.as_ref(),
+        _ => &[],
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(value: Option<&RespFrame>) -> Option<T> {
+    match value {
+        Some(value) => std::str::from_utf8(value_bytes(value)).ok()?.parse().ok(),
+        None => "0".parse().ok(),
+    }
+}
+
+impl Backend {
+    /// Remove the fields, returns the number of the removed fields.
+    pub fn hdel(&self, key: &str, fields: &[String]) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |hmap: &mut Hash| {
+                fields
+                    .iter()
+                    .filter(|f| hmap.remove(f.as_str()).is_some())
+                    .count() as i64
+            },
+            |&n| n > 0,
+        )
+    }
+
+    pub fn hexists(&self, key: &str, field: &str) -> Result<bool, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| hmap.contains_key(field))?;
+        Ok(ret.unwrap_or(false))
+    }
+
+    pub fn hlen(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| hmap.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    pub fn hkeys(&self, key: &str) -> Result<Vec<String>, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| hmap.keys().cloned().collect())?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    pub fn hvals(&self, key: &str) -> Result<Vec<RespFrame>, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| hmap.values().cloned().collect())?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    /// Set the field only if it does not exist yet, returns whether it was set.
+    pub fn hsetnx(&self, key: &str, field: String, value: RespFrame) -> Result<bool, BackendError> {
+        self.write(
+            key,
+            |hmap: &mut Hash| match hmap.contains_key(&field) {
+                true => false,
+                false => {
+                    hmap.insert(field, value);
+                    true
+                }
+            },
+            |&set| set,
+        )
+    }
+
+    pub fn hstrlen(&self, key: &str, field: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| {
+            hmap.get(field).map(|v| value_bytes(v).len() as i64)
+        })?;
+        Ok(ret.flatten().unwrap_or(0))
+    }
+
+    /// Increment the integer value of the field, a missing field counts as 0.
+    pub fn hincrby(&self, key: &str, field: &str, increment: i64) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |hmap: &mut Hash| {
+                let value: i64 =
+                    parse_value(hmap.get(field)).ok_or(BackendError::HashValueNotInteger)?;
+                let value = value.checked_add(increment).ok_or(BackendError::Overflow)?;
+                hmap.insert(field.to_string(), BulkString::new(value.to_string()).into());
+                Ok(value)
+            },
+            Result::is_ok,
+        )?
+    }
+
+    /// Increment the float value of the field, a missing field counts as 0.
+    pub fn hincrbyfloat(
+        &self,
+        key: &str,
+        field: &str,
+        increment: f64,
+    ) -> Result<f64, BackendError> {
+        self.write(
+            key,
+            |hmap: &mut Hash| {
+                let value: f64 = parse_value(hmap.get(field))
+                    .filter(|v: &f64| !v.is_nan())
+                    .ok_or(BackendError::HashValueNotFloat)?;
+                let value = value + increment;
+                if !value.is_finite() {
+                    return Err(BackendError::NanOrInfinity);
+                }
+                hmap.insert(field.to_string(), BulkString::new(value.to_string()).into());
+                Ok(value)
+            },
+            Result::is_ok,
+        )?
+    }
+
+    /// Random fields with their values. A positive count returns distinct fields,
+    /// a negative count returns exactly `-count` fields which may repeat.
+    pub fn hrandfield(
+        &self,
+        key: &str,
+        count: i64,
+    ) -> Result<Vec<(String, RespFrame)>, BackendError> {
+        let ret = self.read(key, |hmap: &Hash| {
+            let fields = match count >= 0 {
+                true => random_sample(hmap.raw_table(), count as usize),
+                false => random_choices(hmap.raw_table(), count.unsigned_abs() as usize),
+            };
+            fields
+                .into_iter()
+                .map(|(f, v)| (f.clone(), v.clone()))
+                .collect()
+        })?;
+        Ok(ret.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hincrby() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(backend.hincrby("h", "n", 5)?, 5);
+        assert_eq!(backend.hincrby("h", "n", -7)?, -2);
+        assert_eq!(backend.hincrbyfloat("h", "n", 0.5)?, -1.5);
+        assert_eq!(
+            backend.hget("h", "n")?,
+            Some(BulkString::new("-1.5").into())
+        );
+        assert_eq!(
+            backend.hincrby("h", "n", 1),
+            Err(BackendError::HashValueNotInteger)
+        );
+
+        backend.hset(
+            "h",
+            vec![(
+                "max".to_string(),
+                BulkString::new(i64::MAX.to_string()).into(),
+            )],
+        )?;
+        assert_eq!(backend.hincrby("h", "max", 1), Err(BackendError::Overflow));
+        backend.hset("h", vec![("s".to_string(), BulkString::new("abc").into())])?;
+        assert_eq!(
+            backend.hincrbyfloat("h", "s", 1.0),
+            Err(BackendError::HashValueNotFloat)
+        );
+        assert_eq!(backend.hstrlen("h", "s")?, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,411 @@
+use super::{Backend, BackendError, Value};
+
+// The HyperLogLog is a string in the same layout as redis, see hyperloglog.c:
+//
+// +------+---+-----+----------+
+// | HYLL | E | N/U | Cardin.  |
+// +------+---+-----+----------+
+//
+// 4 bytes magic, 1 byte encoding, 3 unused bytes, and the cached cardinality as a
+// 64 bit little endian integer, its most significant bit set if the cache is stale.
+// Then come the 16384 registers, 6 bits each in the dense encoding, or run length
+// encoded with the ZERO / XZERO / VAL opcodes in the sparse encoding.
+
+const HLL_P: u32 = 14;
+const HLL_Q: u32 = 64 - HLL_P;
+const HLL_REGISTERS: usize = 1 << HLL_P;
+const HLL_BITS: usize = 6;
+const HLL_HDR_SIZE: usize = 16;
+const HLL_DENSE_SIZE: usize = HLL_HDR_SIZE + (HLL_REGISTERS * HLL_BITS).div_ceil(8);
+const HLL_DENSE: u8 = 0;
+const HLL_SPARSE: u8 = 1;
+const HLL_SPARSE_VAL_MAX: u8 = 32;
+const HLL_SPARSE_MAX_BYTES: usize = 3000; // hll-sparse-max-bytes
+const HLL_ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+/// MurmurHash64A, with the seed used by redis.
+fn murmurhash64a(key: &[u8]) -> u64 {
+    const M: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+    let mut h = 0xadc83b19u64 ^ (key.len() as u64).wrapping_mul(M);
+
+    let mut chunks = key.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or_default());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, byte) in tail.iter().enumerate() {
+            h ^= (*byte as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// The register index of the element, and the length of the 000..1 pattern.
+fn pattern_len(element: &[u8]) -> (usize, u8) {
+    let hash = murmurhash64a(element);
+    let index = (hash & (HLL_REGISTERS as u64 - 1)) as usize;
+    // make sure the loop terminates, the count is at most Q + 1
+    let hash = (hash >> HLL_P) | (1 << HLL_Q);
+    (index, hash.trailing_zeros() as u8 + 1)
+}
+
+fn dense_get(registers: &[u8], index: usize) -> u8 {
+    let (byte, bit) = (index * HLL_BITS / 8, index * HLL_BITS % 8);
+    let lo = registers[byte] >> bit;
+    let hi = registers
+        .get(byte + 1)
+        .map_or(0, |b| b.checked_shl(8 - bit as u32).unwrap_or(0));
+    (lo | hi) & 0x3f
+}
+
+fn dense_set(registers: &mut [u8], index: usize, value: u8) {
+    let (byte, bit) = (index * HLL_BITS / 8, index * HLL_BITS % 8);
+    registers[byte] &= !(0x3f << bit);
+    registers[byte] |= value << bit;
+    if bit > 8 - HLL_BITS {
+        let shift = 8 - bit;
+        registers[byte + 1] &= !(0x3f >> shift);
+        registers[byte + 1] |= value >> shift;
+    }
+}
+
+fn is_hll(value: &[u8]) -> bool {
+    value.len() >= HLL_HDR_SIZE
+        && value.starts_with(b"HYLL")
+        && match value[4] {
+            HLL_DENSE => value.len() == HLL_DENSE_SIZE,
+            HLL_SPARSE => true,
+            _ => false,
+        }
+}
+
+/// Decode the registers of the HyperLogLog, one byte per register.
+fn decode(value: &[u8]) -> Result<Vec<u8>, BackendError> {
+    if !is_hll(value) {
+        return Err(BackendError::NotHyperLogLog);
+    }
+    let data = &value[HLL_HDR_SIZE..];
+    if value[4] == HLL_DENSE {
+        return Ok((0..HLL_REGISTERS).map(|i| dense_get(data, i)).collect());
+    }
+
+    let mut registers = Vec::with_capacity(HLL_REGISTERS);
+    let mut pos = 0;
+    while pos < data.len() {
+        let op = data[pos];
+        let (value, run) = match op & 0xc0 {
+            // ZERO: 00xxxxxx
+            0x00 => (0, (op & 0x3f) as usize + 1),
+            // XZERO: 01xxxxxx yyyyyyyy
+            0x40 => {
+                pos += 1;
+                let low = *data.get(pos).ok_or(BackendError::CorruptedHll)?;
+                (0, (((op & 0x3f) as usize) << 8 | low as usize) + 1)
+            }
+            // VAL: 1vvvvvxx
+            _ => (((op >> 2) & 0x1f) + 1, (op & 0x03) as usize + 1),
+        };
+        if registers.len() + run > HLL_REGISTERS {
+            return Err(BackendError::CorruptedHll);
+        }
+        registers.resize(registers.len() + run, value);
+        pos += 1;
+    }
+    match registers.len() == HLL_REGISTERS {
+        true => Ok(registers),
+        false => Err(BackendError::CorruptedHll),
+    }
+}
+
+/// Encode the registers, sparse if possible and `sparse` is set, with a stale cache.
+fn encode(registers: &[u8], sparse: bool) -> Vec<u8> {
+    let mut value = b"HYLL".to_vec();
+    value.extend_from_slice(&[HLL_SPARSE, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80]);
+    if sparse && registers.iter().all(|r| *r <= HLL_SPARSE_VAL_MAX) {
+        let mut i = 0;
+        while i < HLL_REGISTERS && value.len() <= HLL_SPARSE_MAX_BYTES {
+            let register = registers[i];
+            let mut run = registers[i..]
+                .iter()
+                .take_while(|r| **r == register)
+                .count();
+            i += run;
+            while run > 0 {
+                let n = match register {
+                    0 if run > 64 => {
+                        let n = run.min(HLL_REGISTERS);
+                        value.push(0x40 | ((n - 1) >> 8) as u8);
+                        value.push(((n - 1) & 0xff) as u8);
+                        n
+                    }
+                    0 => {
+                        value.push((run - 1) as u8);
+                        run
+                    }
+                    _ => {
+                        let n = run.min(4);
+                        value.push(0x80 | (register - 1) << 2 | (n - 1) as u8);
+                        n
+                    }
+                };
+                run -= n;
+            }
+        }
+        if value.len() <= HLL_SPARSE_MAX_BYTES {
+            return value;
+        }
+    }
+
+    value.truncate(HLL_HDR_SIZE);
+    value[4] = HLL_DENSE;
+    value.resize(HLL_DENSE_SIZE, 0);
+    for (i, register) in registers.iter().enumerate() {
+        dense_set(&mut value[HLL_HDR_SIZE..], i, *register);
+    }
+    value
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let (mut y, mut z) = (1.0, 1.0 - x);
+    loop {
+        x = x.sqrt();
+        let prev = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if prev == z {
+            return z / 3.0;
+        }
+    }
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let (mut y, mut z) = (1.0, x);
+    loop {
+        x *= x;
+        let prev = z;
+        z += x * y;
+        y += y;
+        if prev == z {
+            return z;
+        }
+    }
+}
+
+/// The cardinality estimation of Otmar Ertl, as redis does.
+fn count(registers: &[u8]) -> u64 {
+    let m = HLL_REGISTERS as f64;
+    let mut histogram = [0usize; 64];
+    for register in registers {
+        histogram[*register as usize] += 1;
+    }
+    let q = HLL_Q as usize;
+    let mut z = m * tau((m - histogram[q + 1] as f64) / m);
+    for j in (1..=q).rev() {
+        z += histogram[j] as f64;
+        z *= 0.5;
+    }
+    z += m * sigma(histogram[0] as f64 / m);
+    (HLL_ALPHA_INF * m * m / z).round() as u64
+}
+
+impl Backend {
+    /// Add the elements, returns whether any register was updated, or the key created.
+    pub fn pfadd(&self, key: &str, elements: &[Vec<u8>]) -> Result<bool, BackendError> {
+        self.write_string(key, |value, exists| {
+            if !exists {
+                *value = encode(&[0; HLL_REGISTERS], true);
+            }
+            let mut updated = !exists;
+            if is_hll(value) && value[4] == HLL_DENSE {
+                // update the dense registers in place
+                for element in elements {
+                    let (index, len) = pattern_len(element);
+                    if dense_get(&value[HLL_HDR_SIZE..], index) < len {
+                        dense_set(&mut value[HLL_HDR_SIZE..], index, len);
+                        updated = true;
+                    }
+                }
+            } else {
+                let mut registers = decode(value)?;
+                let mut changed = false;
+                for element in elements {
+                    let (index, len) = pattern_len(element);
+                    if registers[index] < len {
+                        registers[index] = len;
+                        changed = true;
+                    }
+                }
+                if changed {
+                    *value = encode(&registers, true);
+                    updated = true;
+                }
+            }
+            if updated {
+                value[15] |= 0x80;
+            }
+            Ok(updated)
+        })
+    }
+
+    /// The approximated cardinality of the union of the HyperLogLogs.
+    /// The cardinality of a single key is cached in its header.
+    pub fn pfcount(&self, keys: &[String]) -> Result<u64, BackendError> {
+        if let [key] = keys {
+            self.expire_if_needed(key);
+            let Some(mut entry) = self.db.get_mut(key) else {
+                return Ok(0);
+            };
+            let Value::String(value) = entry.value_mut() else {
+                return Err(BackendError::WrongType);
+            };
+            if !is_hll(value) {
+                return Err(BackendError::NotHyperLogLog);
+            }
+            if value[15] & 0x80 == 0 {
+                return Ok(u64::from_le_bytes(
+                    value[8..16].try_into().unwrap_or_default(),
+                ));
+            }
+            let card = count(&decode(value)?);
+            value[8..16].copy_from_slice(&card.to_le_bytes());
+            return Ok(card);
+        }
+
+        let mut merged = vec![0; HLL_REGISTERS];
+        for key in keys {
+            self.merge_into(key, &mut merged)?;
+        }
+        Ok(count(&merged))
+    }
+
+    /// Merge the source HyperLogLogs into the destination, which is created if
+    /// it does not exist. The result is dense if any of the inputs is dense.
+    pub fn pfmerge(&self, dst: &str, sources: &[String]) -> Result<(), BackendError> {
+        let mut merged = vec![0; HLL_REGISTERS];
+        let mut sparse = true;
+        for key in sources {
+            sparse &= self.merge_into(key, &mut merged)?;
+        }
+
+        self.write_string(dst, |value, exists| {
+            if exists {
+                sparse &= is_hll(value) && value[4] == HLL_SPARSE;
+                merge(&mut merged, &decode(value)?);
+            }
+            *value = encode(&merged, sparse);
+            Ok(())
+        })
+    }
+
+    /// Merge the registers of the key, returns false if it is dense.
+    fn merge_into(&self, key: &str, merged: &mut [u8]) -> Result<bool, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| {
+            let registers = decode(value)?;
+            merge(merged, &registers);
+            Ok(value[4] == HLL_SPARSE)
+        })?;
+        ret.unwrap_or(Ok(true))
+    }
+}
+
+fn merge(merged: &mut [u8], registers: &[u8]) {
+    for (m, r) in merged.iter_mut().zip(registers) {
+        *m = (*m).max(*r);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding_roundtrip() -> Result<(), BackendError> {
+        let mut registers = vec![0; HLL_REGISTERS];
+        registers[0] = 3;
+        registers[1] = 3;
+        registers[100] = 32;
+        registers[HLL_REGISTERS - 1] = 1;
+
+        let sparse = encode(&registers, true);
+        assert_eq!(&sparse[..5], b"HYLL\x01");
+        assert_eq!(decode(&sparse)?, registers);
+
+        let dense = encode(&registers, false);
+        assert_eq!(dense.len(), HLL_DENSE_SIZE);
+        assert_eq!(decode(&dense)?, registers);
+
+        // a register over 32 can not be sparse
+        registers[7] = 33;
+        assert_eq!(encode(&registers, true)[4], HLL_DENSE);
+
+        // an empty sparse HLL is a single XZERO of all the registers
+        assert_eq!(
+            &encode(&[0; HLL_REGISTERS], true)[HLL_HDR_SIZE..],
+            &[0x7f, 0xff]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pfadd_pfcount() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let elements = [b"a", b"b", b"c", b"d", b"e", b"f", b"g"].map(|e| e.to_vec());
+        assert!(backend.pfadd("hll", &elements)?);
+        assert!(!backend.pfadd("hll", &elements[..3])?);
+        assert_eq!(backend.pfcount(&["hll".to_string()])?, 7);
+        assert!(backend.pfadd("empty", &[])?);
+        assert_eq!(backend.pfcount(&["empty".to_string()])?, 0);
+
+        backend.set("str".to_string(), "hello");
+        assert_eq!(backend.pfadd("str", &[]), Err(BackendError::NotHyperLogLog));
+        assert_eq!(
+            backend.pfcount(&["str".to_string()]),
+            Err(BackendError::NotHyperLogLog)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_bound_and_merge() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let n = 100_000;
+        let a = (0..n)
+            .map(|i| format!("a{}", i).into_bytes())
+            .collect::<Vec<_>>();
+        let b = (0..n)
+            .map(|i| format!("b{}", i).into_bytes())
+            .collect::<Vec<_>>();
+        backend.pfadd("a", &a)?;
+        backend.pfadd("b", &b)?;
+        // promoted to the dense encoding
+        assert_eq!(backend.get("a")?.map(|v| v.len()), Some(HLL_DENSE_SIZE));
+
+        let error = |count: u64, n: u64| (count as f64 - n as f64).abs() / n as f64;
+        let count = backend.pfcount(&["a".to_string()])?;
+        assert!(error(count, n) < 0.03, "{}", count);
+        let count = backend.pfcount(&["a".to_string(), "b".to_string(), "c".to_string()])?;
+        assert!(error(count, 2 * n) < 0.03, "{}", count);
+
+        backend.pfmerge("ab", &["a".to_string(), "b".to_string()])?;
+        assert_eq!(backend.pfcount(&["ab".to_string()])?, count);
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use std::sync::{
+    mpsc::{self, Sender},
+    LazyLock,
+};
+
+use dashmap::mapref::entry::Entry;
+
+use super::{Backend, BackendError, Value};
+
+// generic keyspace operations, which work for all the value types
+
+impl Backend {
+    /// Remove the keys, returns the number of keys removed.
+    pub fn del(&self, keys: &[String]) -> i64 {
+        keys.iter()
+            .filter(|key| self.remove_live(key).is_some())
+            .count() as i64
+    }
+
+    /// Like DEL, but the memory is reclaimed in another thread.
+    pub fn unlink(&self, keys: &[String]) -> i64 {
+        let values = keys
+            .iter()
+            .filter_map(|key| self.remove_live(key))
+            .collect::<Vec<_>>();
+        let cnt = values.len() as i64;
+        drop_in_background(values);
+        cnt
+    }
+
+    /// Count the existing keys, a key mentioned multiple times is counted multiple times.
+    pub fn exists_count(&self, keys: &[String]) -> i64 {
+        keys.iter().filter(|key| self.exists(key)).count() as i64
+    }
+
+    /// Rename `src` to `dst` together with its TTL, `dst` is overwritten unless `nx` is set.
+    /// Returns false if `nx` is set and `dst` already exists.
+    pub fn rename(&self, src: &str, dst: &str, nx: bool) -> Result<bool, BackendError> {
+        if !self.exists(src) {
+            return Err(BackendError::NoSuchKey);
+        }
+        if src == dst {
+            return Ok(!nx);
+        }
+        if nx && self.exists(dst) {
+            return Ok(false);
+        }
+        self.expire_if_needed(dst);
+
+        // NOTE: never hold a reference of one key while accessing another key,
+        // the two keys may live in the same shard
+        let ttl = self.expires.get(src).map(|v| *v);
+        let Some(value) = self.take_key(src) else {
+            return Err(BackendError::NoSuchKey);
+        };
+        if let Err(value) = self.insert_with_ttl(dst, value, ttl, !nx) {
+            // `dst` was created meanwhile, put `src` back unless it was too
+            if let Entry::Vacant(entry) = self.db.entry(src.to_string()) {
+                self.set_ttl(src, ttl);
+                entry.insert(value);
+            }
+            return Ok(false);
+        }
+        self.touch_watched(src);
+        self.signal_ready(dst);
+        Ok(true)
+    }
+
+    /// Copy the value of `src` with its TTL to `dst`.
+    /// Returns false if `src` does not exist, or `dst` exists and `replace` is not set.
+    pub fn copy(&self, src: &str, dst: &str, replace: bool) -> bool {
+        self.expire_if_needed(src);
+        self.expire_if_needed(dst);
+        if src == dst {
+            return false;
+        }
+
+        let Some(value) = self.db.get(src).map(|v| v.value().clone()) else {
+            return false;
+        };
+        let ttl = self.expires.get(src).map(|v| *v);
+        if self.insert_with_ttl(dst, value, ttl, replace).is_err() {
+            return false;
+        }
+        self.signal_ready(dst);
+        true
+    }
+
+    /// The number of keys in the database.
+    pub fn dbsize(&self) -> i64 {
+        self.db.len() as i64
+    }
+
+    /// Remove all the keys, with `lazy` set the memory is reclaimed in another thread.
+    pub fn flush(&self, lazy: bool) {
+        // NOTE: collect the watched keys first, don't hold the two maps at once
+        let watched = self
+            .watched
+            .iter()
+            .map(|v| v.key().clone())
+            .collect::<Vec<_>>();
+        for key in watched.iter().filter(|key| self.exists(key)) {
+            self.touch_watched(key);
+        }
+
+        self.expires.clear();
+        if !lazy {
+            self.db.clear();
+            return;
+        }
+
+        let keys = self.db.iter().map(|v| v.key().clone()).collect::<Vec<_>>();
+        let values = keys
+            .iter()
+            .filter_map(|key| self.db.remove(key).map(|(_, value)| value))
+            .collect::<Vec<_>>();
+        drop_in_background(values);
+    }
+
+    /// Insert a value with its TTL, replacing the old value and TTL of the key
+    /// unless `replace` is false. The value is given back if the key exists and
+    /// isn't replaced.
+    pub(crate) fn insert_with_ttl(
+        &self,
+        key: &str,
+        value: Value,
+        ttl: Option<i64>,
+        replace: bool,
+    ) -> Result<(), Value> {
+        // the existence check and the insert are done on the same entry
+        match self.db.entry(key.to_string()) {
+            Entry::Occupied(_) if !replace => return Err(value),
+            entry => {
+                self.set_ttl(key, ttl);
+                entry.insert(value);
+            }
+        }
+        self.touch_watched(key);
+        Ok(())
+    }
+
+    /// Overwrite the key with the value and discard its TTL, an empty value deletes the key.
+    pub(crate) fn store(&self, key: &str, value: Value) {
+        self.expire_if_needed(key);
+        match value.is_empty() {
+            true => {
+                self.remove_key(key);
+            }
+            false => {
+                let _ = self.insert_with_ttl(key, value, None, true);
+            }
+        }
+    }
+
+    fn set_ttl(&self, key: &str, ttl: Option<i64>) {
+        match ttl {
+            Some(at) => {
+                self.expires.insert(key.to_string(), at);
+            }
+            None => {
+                self.expires.remove(key);
+            }
+        }
+    }
+
+    /// Remove a key which is not expired, returning its value.
+    fn remove_live(&self, key: &str) -> Option<Value> {
+        if self.expire_if_needed(key) {
+            return None;
+        }
+        let value = self.take_key(key);
+        if value.is_some() {
+            self.touch_watched(key);
+        }
+        value
+    }
+}
+
+/// Values which take more allocations than this to free are dropped by the lazy
+/// free thread, like redis `LAZYFREE_THRESHOLD`.
+const LAZYFREE_THRESHOLD: usize = 64;
+
+/// The single thread which drops the values of UNLINK and FLUSHALL ASYNC,
+/// started on first use.
+static LAZYFREE: LazyLock<Sender<Vec<Value>>> = LazyLock::new(|| {
+    let (tx, rx) = mpsc::channel::<Vec<Value>>();
+    std::thread::spawn(move || rx.into_iter().for_each(drop));
+    tx
+});
+
+/// Drop the values in the lazy free thread, freeing large values may take a while,
+/// small values are dropped right away.
+fn drop_in_background(values: Vec<Value>) {
+    let effort = values.iter().map(Value::free_effort).sum::<usize>();
+    if effort > LAZYFREE_THRESHOLD {
+        // the thread lives as long as the process, the send doesn't fail
+        let _ = LAZYFREE.send(values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{now_ms, ExpireCondition};
+
+    #[test]
+    fn test_rename_and_copy() {
+        let backend = Backend::new();
+        assert_eq!(
+            backend.rename("hello", "world", false),
+            Err(BackendError::NoSuchKey)
+        );
+
+        backend.set("hello".to_string(), "world");
+        backend.expire_at("hello", now_ms() + 10_000, ExpireCondition::Always);
+        assert_eq!(backend.rename("hello", "foo", false), Ok(true));
+        assert!(!backend.exists("hello"));
+        assert!(backend.pttl("foo") > 0);
+
+        backend.set("bar".to_string(), "bar");
+        assert_eq!(backend.rename("foo", "bar", true), Ok(false));
+
+        assert!(!backend.copy("foo", "bar", false));
+        assert!(backend.copy("foo", "bar", true));
+        assert_eq!(backend.get("bar"), Ok(Some(b"world".to_vec())));
+        assert!(backend.pttl("bar") > 0);
+    }
+
+    #[test]
+    fn test_del_and_flush() {
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        backend.sadd("set", vec!["hello".to_string()]).unwrap();
+        let keys = ["hello".to_string(), "set".to_string(), "none".to_string()];
+        assert_eq!(backend.exists_count(&keys), 2);
+        assert_eq!(backend.del(&keys), 2);
+        assert_eq!(backend.dbsize(), 0);
+
+        backend.set("hello".to_string(), "world");
+        backend.flush(true);
+        assert_eq!(backend.dbsize(), 0);
+    }
+
+    #[test]
+    fn test_renamenx_and_copy_concurrent() {
+        let backend = Backend::new();
+        for i in 0..8 {
+            backend.set(format!("src:{}", i), i.to_string());
+        }
+        let handles = (0..8)
+            .map(|i| {
+                let backend = backend.clone();
+                std::thread::spawn(move || match i % 2 {
+                    0 => backend.rename(&format!("src:{}", i), "dst", true).unwrap(),
+                    _ => backend.copy(&format!("src:{}", i), "dst", false),
+                })
+            })
+            .collect::<Vec<_>>();
+        let done = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|&done| done)
+            .count();
+        // a destination created meanwhile is never overwritten
+        assert_eq!(done, 1);
+        let sources = (0..8)
+            .filter(|i| backend.exists(&format!("src:{}", i)))
+            .count();
+        assert!(sources == 7 || sources == 8);
+        assert!(backend.exists("dst"));
+
+        let members = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>();
+        backend.sadd("set", members).unwrap();
+        assert_eq!(backend.unlink(&["set".to_string()]), 1);
+        assert!(!backend.exists("set"));
+    }
+}
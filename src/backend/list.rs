@@ -0,0 +1,338 @@
+use std::collections::VecDeque;
+
+use crate::RespFrame;
+
+use super::{Backend, BackendError};
+
+type List = VecDeque<RespFrame>;
+
+/// The end of a list, LEFT is the head and RIGHT is the tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEnd {
+    Left,
+    Right,
+}
+
+/// Normalize the redis style inclusive range, negative indexes count from the tail.
+/// Returns None if the range is empty.
+pub(crate) fn normalize_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let stop = if stop < 0 {
+        len + stop
+    } else {
+        stop.min(len - 1)
+    };
+    if start > stop || start >= len {
+        return None;
+    }
+    Some((start as usize, stop as usize))
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+impl Backend {
+    /// Push the values one by one to the end of the list, returns the length of the list.
+    /// With `only_existing` the values are pushed only if the list already exists.
+    /// Clients blocked on the key are served afterwards.
+    pub fn push(
+        &self,
+        key: &str,
+        end: ListEnd,
+        values: Vec<RespFrame>,
+        only_existing: bool,
+    ) -> Result<i64, BackendError> {
+        let len = self.push_inner(key, end, values, only_existing)?;
+        if len > 0 {
+            self.signal_ready(key);
+        }
+        Ok(len)
+    }
+
+    /// Push without serving the blocked clients.
+    pub(crate) fn push_inner(
+        &self,
+        key: &str,
+        end: ListEnd,
+        values: Vec<RespFrame>,
+        only_existing: bool,
+    ) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |list: &mut List| {
+                if only_existing && list.is_empty() {
+                    return 0;
+                }
+                for value in values {
+                    match end {
+                        ListEnd::Left => list.push_front(value),
+                        ListEnd::Right => list.push_back(value),
+                    }
+                }
+                list.len() as i64
+            },
+            |&len| len > 0,
+        )
+    }
+
+    pub(crate) fn pop_inner(
+        &self,
+        key: &str,
+        end: ListEnd,
+    ) -> Result<Option<RespFrame>, BackendError> {
+        Ok(self.pop(key, end, 1)?.pop())
+    }
+
+    /// Pop at most `count` values from the end of the list.
+    pub fn pop(
+        &self,
+        key: &str,
+        end: ListEnd,
+        count: usize,
+    ) -> Result<Vec<RespFrame>, BackendError> {
+        self.write(
+            key,
+            |list: &mut List| {
+                let count = count.min(list.len());
+                match end {
+                    ListEnd::Left => list.drain(..count).collect(),
+                    ListEnd::Right => list.drain(list.len() - count..).rev().collect(),
+                }
+            },
+            |values: &Vec<_>| !values.is_empty(),
+        )
+    }
+
+    pub fn lrange(&self, key: &str, start: i64, stop: i64) -> Result<Vec<RespFrame>, BackendError> {
+        let ret = self.read(key, |list: &List| {
+            match normalize_range(start, stop, list.len()) {
+                Some((start, stop)) => list.range(start..=stop).cloned().collect(),
+                None => vec![],
+            }
+        })?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    pub fn lindex(&self, key: &str, index: i64) -> Result<Option<RespFrame>, BackendError> {
+        let ret = self.read(key, |list: &List| {
+            normalize_index(index, list.len()).map(|i| list[i].clone())
+        })?;
+        Ok(ret.flatten())
+    }
+
+    pub fn lset(&self, key: &str, index: i64, value: RespFrame) -> Result<(), BackendError> {
+        self.write(
+            key,
+            |list: &mut List| {
+                if list.is_empty() {
+                    return Err(BackendError::NoSuchKey);
+                }
+                let i = normalize_index(index, list.len()).ok_or(BackendError::IndexOutOfRange)?;
+                list[i] = value;
+                Ok(())
+            },
+            Result::is_ok,
+        )?
+    }
+
+    /// Remove the first `count` occurrences of the value, from the tail if `count` is
+    /// negative, or all of them if `count` is 0. Returns the number of removed values.
+    pub fn lrem(&self, key: &str, count: i64, value: &RespFrame) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |list: &mut List| {
+                let limit = if count == 0 {
+                    usize::MAX
+                } else {
+                    count.unsigned_abs() as usize
+                };
+                // one pass over the list, from the tail if count is negative
+                if count < 0 {
+                    list.make_contiguous().reverse();
+                }
+                let mut removed = 0;
+                list.retain(|v| {
+                    let remove = removed < limit && v == value;
+                    removed += remove as usize;
+                    !remove
+                });
+                if count < 0 {
+                    list.make_contiguous().reverse();
+                }
+                removed as i64
+            },
+            |&n| n > 0,
+        )
+    }
+
+    /// Trim the list to the inclusive range.
+    pub fn ltrim(&self, key: &str, start: i64, stop: i64) -> Result<(), BackendError> {
+        self.write(
+            key,
+            |list: &mut List| match normalize_range(start, stop, list.len()) {
+                Some((start, stop)) => {
+                    list.truncate(stop + 1);
+                    list.drain(..start);
+                }
+                None => list.clear(),
+            },
+            |_| true,
+        )
+    }
+
+    /// Insert the value before or after the pivot, returns the length of the list,
+    /// -1 if the pivot is not found, or 0 if the key does not exist.
+    pub fn linsert(
+        &self,
+        key: &str,
+        before: bool,
+        pivot: &RespFrame,
+        value: RespFrame,
+    ) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |list: &mut List| {
+                if list.is_empty() {
+                    return 0;
+                }
+                match list.iter().position(|v| v == pivot) {
+                    Some(i) => {
+                        list.insert(if before { i } else { i + 1 }, value);
+                        list.len() as i64
+                    }
+                    None => -1,
+                }
+            },
+            |&len| len > 0,
+        )
+    }
+
+    pub fn llen(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |list: &List| list.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// Pop a value from one end of `src`, and push it to one end of `dst`.
+    pub fn lmove(
+        &self,
+        src: &str,
+        dst: &str,
+        from: ListEnd,
+        to: ListEnd,
+    ) -> Result<Option<RespFrame>, BackendError> {
+        // check the type of the destination before modifying the source
+        self.read(dst, |_: &List| ())?;
+
+        let Some(value) = self.pop(src, from, 1)?.pop() else {
+            return Ok(None);
+        };
+        self.push(dst, to, vec![value.clone()], false)?;
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BulkString;
+
+    fn values(s: &[&str]) -> Vec<RespFrame> {
+        s.iter().map(|v| BulkString::new(*v).into()).collect()
+    }
+
+    #[test]
+    fn test_normalize_range() {
+        assert_eq!(normalize_range(0, -1, 3), Some((0, 2)));
+        assert_eq!(normalize_range(-2, 10, 3), Some((1, 2)));
+        assert_eq!(normalize_range(-10, 0, 3), Some((0, 0)));
+        assert_eq!(normalize_range(2, 1, 3), None);
+        assert_eq!(normalize_range(3, 5, 3), None);
+        assert_eq!(normalize_range(0, -4, 3), None);
+        assert_eq!(normalize_range(0, -1, 0), None);
+    }
+
+    #[test]
+    fn test_list_operations() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(
+            backend.push("list", ListEnd::Left, values(&["a"]), true)?,
+            0
+        );
+        assert!(!backend.exists("list"));
+
+        assert_eq!(
+            backend.push("list", ListEnd::Right, values(&["a", "b", "c"]), false)?,
+            3
+        );
+        assert_eq!(
+            backend.push("list", ListEnd::Left, values(&["x", "y"]), false)?,
+            5
+        );
+        assert_eq!(
+            backend.lrange("list", 0, -1)?,
+            values(&["y", "x", "a", "b", "c"])
+        );
+        assert_eq!(
+            backend.lindex("list", -1)?,
+            Some(BulkString::new("c").into())
+        );
+
+        assert_eq!(backend.pop("list", ListEnd::Right, 2)?, values(&["c", "b"]));
+        assert_eq!(
+            backend.linsert(
+                "list",
+                true,
+                &BulkString::new("a").into(),
+                BulkString::new("x").into()
+            )?,
+            4
+        );
+        assert_eq!(backend.lrem("list", -1, &BulkString::new("x").into())?, 1);
+        assert_eq!(backend.lrange("list", 0, -1)?, values(&["y", "x", "a"]));
+
+        assert_eq!(
+            backend.lset("list", 5, BulkString::new("z").into()),
+            Err(BackendError::IndexOutOfRange)
+        );
+        backend.ltrim("list", 1, 1)?;
+        assert_eq!(backend.lrange("list", 0, -1)?, values(&["x"]));
+
+        assert_eq!(
+            backend.lmove("list", "other", ListEnd::Left, ListEnd::Right)?,
+            Some(BulkString::new("x").into())
+        );
+        assert!(!backend.exists("list"));
+        assert_eq!(backend.llen("other")?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lrem() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let a = BulkString::new("a").into();
+        backend.push(
+            "list",
+            ListEnd::Right,
+            values(&["a", "b", "a", "c", "a", "a"]),
+            false,
+        )?;
+        assert_eq!(backend.lrem("list", 1, &a)?, 1);
+        assert_eq!(
+            backend.lrange("list", 0, -1)?,
+            values(&["b", "a", "c", "a", "a"])
+        );
+        assert_eq!(backend.lrem("list", -2, &a)?, 2);
+        assert_eq!(backend.lrange("list", 0, -1)?, values(&["b", "a", "c"]));
+        assert_eq!(backend.lrem("list", 0, &BulkString::new("x").into())?, 0);
+        assert_eq!(backend.lrem("list", 0, &a)?, 1);
+        assert_eq!(backend.lrange("list", 0, -1)?, values(&["b", "c"]));
+        Ok(())
+    }
+}
@@ -1,16 +1,88 @@
+mod bitmap;
+mod blocking;
+mod expire;
+mod geo;
+mod glob;
+mod hash;
+mod hyperloglog;
+mod keyspace;
+mod list;
+mod pubsub;
+mod random;
+mod scan;
+mod session;
+mod set;
+mod skiplist;
+mod slot;
+mod stats;
+mod stream;
+mod stream_group;
+mod string;
+mod transaction;
+mod value;
+mod zset;
+
 use crate::RespFrame;
-use dashmap::{DashMap, DashSet};
+use dashmap::{mapref::entry::Entry, DashMap};
 use std::ops::Deref;
-use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, RwLock};
+
+pub use bitmap::{BitOverflow, BitUnit, BitfieldOp, BitfieldType, BitwiseOp};
+pub use blocking::BlockOp;
+use blocking::{BlockedClients, BlockedReaders};
+pub use expire::{now_ms, ExpireCondition};
+pub use geo::{
+    GeoMatch, GeoOrigin, GeoQuery, GeoShape, GeoSort, GEO_LAT_MAX, GEO_LAT_MIN, GEO_LONG_MAX,
+    GEO_LONG_MIN,
+};
+pub use glob::glob_match;
+pub use list::ListEnd;
+use pubsub::PubSub;
+pub use pubsub::{PubSubMessage, Subscriber};
+use session::SessionInner;
+pub use session::{ClientInfo, ClientKillFilter, Session, SessionState, DEFAULT_USER};
+pub use set::SetOp;
+pub use slot::{crc16, key_slot, SLOT_COUNT};
+use stats::Counters;
+pub use stats::ServerStats;
+pub use stream::{
+    Stream, StreamEntry, StreamFields, StreamId, StreamTrim, TrimStrategy, XAddId, XReadId,
+};
+pub use stream_group::{
+    ClaimOptions, ConsumerInfo, GroupEntry, GroupInfo, PendingInfo, PendingRange, PendingSummary,
+    StreamInfo,
+};
+use transaction::WatchVersion;
+pub use transaction::WatchedKey;
+pub use value::{BackendError, Value};
+pub(crate) use value::{HashMap, HashSet, TypedValue};
+pub use zset::{LexBound, ScoreBound, ScoreCompare, ZAddFlags, ZRangeBy, ZSet};
+
+/// Condition of the SET command, see https://redis.io/docs/latest/commands/set/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SetCondition {
+    #[default]
+    Always,
+    Nx, // only set the key if it does not already exist
+    Xx, // only set the key if it already exists
+}
 
 #[derive(Debug, Clone)]
 pub struct Backend(Arc<BackendInner>);
 
 #[derive(Debug)]
 pub struct BackendInner {
-    pub(crate) map: DashMap<String, RespFrame>,
-    pub(crate) hmap: DashMap<String, DashMap<String, RespFrame>>,
-    pub(crate) set: DashMap<String, DashSet<String>>, //  DashSet 中的元素要求实现 Eq, RespFrame 不能实现 Eq, 因此这里使用 String
+    pub(crate) db: DashMap<String, Value>, // one keyspace for all the value types
+    pub(crate) expires: DashMap<String, i64>, // key -> deadline, unix time in milliseconds
+    pub(crate) blocked: BlockedClients,    // clients blocked by BLPOP / BRPOP / BLMOVE
+    pub(crate) readers: BlockedReaders,    // clients blocked by XREAD
+    pub(crate) pubsub: PubSub,
+    pub(crate) txn_lock: RwLock<()>, // held exclusively by EXEC
+    pub(crate) sessions: DashMap<u64, Arc<SessionInner>>, // the connections by ID
+    pub(crate) counters: Counters,
+    pub(crate) expire_cursor: AtomicU64, // where the active expiration goes on
+    pub(crate) watched: DashMap<String, WatchVersion>, // the keys watched by WATCH
 }
 
 impl Backend {
@@ -18,61 +90,240 @@ impl Backend {
         Self::default()
     }
 
-    pub fn get(&self, key: &str) -> Option<RespFrame> {
-        self.map.get(key).map(|v| v.value().clone())
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, BackendError> {
+        self.expire_if_needed(key);
+        match self.db.get(key).as_deref() {
+            Some(Value::String(v)) => Ok(Some(v.clone())),
+            Some(_) => Err(BackendError::WrongType),
+            None => Ok(None),
+        }
     }
 
-    pub fn set(&self, key: String, value: RespFrame) {
-        self.map.insert(key, value);
+    /// SET overwrites a value of any type and discards any TTL previously associated with the key.
+    pub fn set(&self, key: String, value: impl Into<Vec<u8>>) {
+        self.expire_if_needed(&key);
+        self.expires.remove(&key);
+        self.db.insert(key.clone(), Value::String(value.into()));
+        self.touch_watched(&key);
     }
 
-    pub fn hget(&self, key: &str, field: &str) -> Option<RespFrame> {
-        self.hmap
-            .get(key)
-            .and_then(|v| v.get(field).map(|v| v.value().clone()))
+    /// SET with options, atomic for the key.
+    /// `expire_at` is the new deadline in milliseconds; without it the TTL is
+    /// discarded unless `keep_ttl` is set.
+    /// Returns whether the value was set, and the old value of the key if `get` is set.
+    pub fn set_with(
+        &self,
+        key: String,
+        value: Vec<u8>,
+        condition: SetCondition,
+        expire_at: Option<i64>,
+        keep_ttl: bool,
+        get: bool,
+    ) -> Result<(bool, Option<Vec<u8>>), BackendError> {
+        self.expire_if_needed(&key);
+
+        let old = match self.db.entry(key.clone()) {
+            Entry::Occupied(mut entry) => {
+                let old = match entry.get() {
+                    Value::String(v) => Some(v.clone()),
+                    // SET .. GET is not allowed against a key of another type
+                    _ if get => return Err(BackendError::WrongType),
+                    _ => None,
+                };
+                if condition == SetCondition::Nx {
+                    return Ok((false, old));
+                }
+                entry.insert(Value::String(value));
+                old
+            }
+            Entry::Vacant(entry) => {
+                if condition == SetCondition::Xx {
+                    return Ok((false, None));
+                }
+                entry.insert(Value::String(value));
+                None
+            }
+        };
+
+        match expire_at {
+            Some(at) => {
+                self.expires.insert(key.clone(), at);
+            }
+            None if !keep_ttl => {
+                self.expires.remove(&key);
+            }
+            None => {}
+        }
+        self.touch_watched(&key);
+        Ok((true, old))
     }
 
-    pub fn hset(&self, key: String, field: String, value: RespFrame) {
-        let hmap = self.hmap.entry(key).or_default();
-        hmap.insert(field, value);
+    pub fn hget(&self, key: &str, field: &str) -> Result<Option<RespFrame>, BackendError> {
+        let ret = self.read(key, |hmap: &HashMap<String, RespFrame>| {
+            hmap.get(field).cloned()
+        })?;
+        Ok(ret.flatten())
     }
 
-    pub fn hgetall(&self, key: &str) -> Option<DashMap<String, RespFrame>> {
-        self.hmap.get(key).map(|v| v.clone())
+    /// Set the fields, returns the number of the new fields.
+    pub fn hset(&self, key: &str, fields: Vec<(String, RespFrame)>) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |hmap: &mut HashMap<String, RespFrame>| {
+                let mut added = 0;
+                for (field, value) in fields {
+                    if hmap.insert(field, value).is_none() {
+                        added += 1;
+                    }
+                }
+                added
+            },
+            |_| true,
+        )
     }
 
-    pub fn sadd(&self, key: String, members: impl Into<Vec<String>>) -> i64 {
-        let set = self.set.entry(key).or_default();
-        let mut cnt = 0;
-        for member in members.into() {
-            if set.insert(member) {
-                cnt += 1;
-            }
+    pub fn hgetall(&self, key: &str) -> Result<Option<HashMap<String, RespFrame>>, BackendError> {
+        self.read(key, |hmap: &HashMap<String, RespFrame>| hmap.clone())
+    }
+
+    pub fn sadd(&self, key: &str, members: impl Into<Vec<String>>) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |set: &mut HashSet<String>| {
+                let mut cnt = 0;
+                for member in members.into() {
+                    if set.insert(member) {
+                        cnt += 1;
+                    }
+                }
+                cnt
+            },
+            |&cnt| cnt > 0,
+        )
+    }
+
+    pub fn sismember(&self, key: &str, value: &str) -> Result<bool, BackendError> {
+        let ret = self.read(key, |set: &HashSet<String>| set.contains(value))?;
+        Ok(ret.unwrap_or(false))
+    }
+
+    pub fn insert_set(&self, key: &str, values: Vec<String>) -> Result<(), BackendError> {
+        self.sadd(key, values).map(|_| ())
+    }
+
+    /// Whether the key exists, whatever the type of its value.
+    pub fn exists(&self, key: &str) -> bool {
+        self.expire_if_needed(key);
+        self.db.contains_key(key)
+    }
+
+    /// The type name of the value stored at key, "none" if the key does not exist.
+    pub fn key_type(&self, key: &str) -> &'static str {
+        self.expire_if_needed(key);
+        self.db.get(key).map(|v| v.type_name()).unwrap_or("none")
+    }
+
+    /// Read access to a value of type `T`.
+    /// Returns None if the key does not exist, WrongType if it holds another type.
+    pub(crate) fn read<T: TypedValue, R>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<Option<R>, BackendError> {
+        self.expire_if_needed(key);
+        match self.db.get(key) {
+            Some(v) => T::from_ref(v.value())
+                .map(|v| Some(f(v)))
+                .ok_or(BackendError::WrongType),
+            None => Ok(None),
         }
-        cnt
     }
 
-    pub fn sismember(&self, key: &str, value: &str) -> bool {
-        self.set
-            .get(key)
-            .and_then(|v| v.get(value).map(|_| true))
-            .unwrap_or(false)
+    /// Write access to a value of type `T`, atomic for the key.
+    /// An empty value is created if the key does not exist, and the key is
+    /// removed if the value is empty afterwards.
+    /// `modified` tells from the result whether `f` changed the value, for WATCH.
+    pub(crate) fn write<T: TypedValue, R>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&mut T) -> R,
+        modified: impl FnOnce(&R) -> bool,
+    ) -> Result<R, BackendError> {
+        self.expire_if_needed(key);
+        let (ret, empty, created) = {
+            let mut created = false;
+            let mut entry = self.db.entry(key.to_string()).or_insert_with(|| {
+                created = true;
+                T::default().into()
+            });
+            let v = T::from_mut(entry.value_mut()).ok_or(BackendError::WrongType)?;
+            let ret = f(v);
+            (ret, v.is_empty(), created)
+        };
+        // a missing key left empty hasn't been modified
+        if modified(&ret) && !(created && empty) {
+            self.touch_watched(key);
+        }
+        if empty {
+            self.remove_if_empty(key);
+        }
+        Ok(ret)
     }
-    pub fn insert_set(&self, key: String, values: Vec<String>) {
-        let set = self.set.get_mut(&key);
-        match set {
-            Some(set) => {
-                for value in values {
-                    (*set).insert(value);
-                }
+
+    /// Write access to an existing value of type `T`, None if the key does not exist.
+    /// The key is removed if the value is empty afterwards.
+    pub(crate) fn update<T: TypedValue, R>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&mut T) -> R,
+        modified: impl FnOnce(&R) -> bool,
+    ) -> Result<Option<R>, BackendError> {
+        self.expire_if_needed(key);
+        let (ret, empty) = {
+            let Some(mut entry) = self.db.get_mut(key) else {
+                return Ok(None);
+            };
+            let v = T::from_mut(entry.value_mut()).ok_or(BackendError::WrongType)?;
+            let ret = f(v);
+            (ret, v.is_empty())
+        };
+        if modified(&ret) {
+            self.touch_watched(key);
+        }
+        if empty {
+            self.remove_if_empty(key);
+        }
+        Ok(Some(ret))
+    }
+
+    fn remove_if_empty(&self, key: &str) {
+        if let Entry::Occupied(entry) = self.db.entry(key.to_string()) {
+            if entry.get().is_empty() {
+                self.expires.remove(key);
+                entry.remove();
             }
-            None => {
-                let new_set = DashSet::new();
-                for value in values {
-                    new_set.insert(value);
-                }
-                self.set.insert(key.to_string(), new_set);
+        }
+    }
+
+    /// Remove the key together with its TTL.
+    fn remove_key(&self, key: &str) -> bool {
+        let removed = self.take_key(key).is_some();
+        if removed {
+            self.touch_watched(key);
+        }
+        removed
+    }
+
+    /// Remove the value of the key and its TTL, returning the value.
+    /// NOTE: a TTL is only written or removed while the entry of the key is held,
+    /// so a concurrent EXPIRE can't leave a TTL behind for a removed key.
+    pub(crate) fn take_key(&self, key: &str) -> Option<Value> {
+        match self.db.entry(key.to_string()) {
+            Entry::Occupied(entry) => {
+                self.expires.remove(key);
+                Some(entry.remove())
             }
+            Entry::Vacant(_) => None,
         }
     }
 }
@@ -88,9 +339,16 @@ impl Deref for Backend {
 impl Default for BackendInner {
     fn default() -> Self {
         Self {
-            map: DashMap::new(),
-            hmap: DashMap::new(),
-            set: DashMap::new(),
+            db: DashMap::new(),
+            expires: DashMap::new(),
+            blocked: BlockedClients::default(),
+            readers: BlockedReaders::default(),
+            pubsub: PubSub::default(),
+            txn_lock: RwLock::new(()),
+            sessions: DashMap::new(),
+            counters: Counters::default(),
+            expire_cursor: AtomicU64::new(0),
+            watched: DashMap::new(),
         }
     }
 }
@@ -100,3 +358,35 @@ impl Default for Backend {
         Self(Arc::new(BackendInner::default()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BulkString;
+
+    #[test]
+    fn test_wrong_type() {
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(backend.hget("hello", "field"), Err(BackendError::WrongType));
+        assert_eq!(
+            backend.sadd("hello", vec!["world".to_string()]),
+            Err(BackendError::WrongType)
+        );
+        assert_eq!(backend.key_type("hello"), "string");
+
+        backend
+            .hset(
+                "map",
+                vec![("field".to_string(), BulkString::new("value").into())],
+            )
+            .unwrap();
+        assert_eq!(backend.get("map"), Err(BackendError::WrongType));
+        assert_eq!(backend.key_type("map"), "hash");
+        assert_eq!(backend.key_type("none"), "none");
+
+        // SET overwrites a value of any type
+        backend.set("map".to_string(), "world");
+        assert_eq!(backend.key_type("map"), "string");
+    }
+}
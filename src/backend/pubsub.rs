@@ -0,0 +1,436 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use dashmap::DashMap;
+use tokio::sync::mpsc::{channel, error::TrySendError, Receiver, Sender};
+
+use super::{glob_match, key_slot, Backend};
+
+// The pub/sub hub maps each channel and pattern to the connections subscribed to
+// it. A published message is sent to the queue of every matching connection,
+// which writes it out between the replies to its own commands.
+// Shard channels live in their own namespace, partitioned into the slots of the
+// keyspace, so SPUBLISH never reaches the subscribers of a global channel.
+// Like the pub/sub output buffer limit of redis, a connection which doesn't read
+// its messages fast enough is disconnected once its queue is full.
+
+/// The number of messages queued for a connection before it is disconnected.
+const PUBSUB_QUEUE_LIMIT: usize = 4096;
+
+/// A message delivered to a subscribed connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PubSubMessage {
+    Message {
+        channel: String,
+        payload: Vec<u8>,
+    },
+    PMessage {
+        pattern: String,
+        channel: String,
+        payload: Vec<u8>,
+    },
+    SMessage {
+        channel: String,
+        payload: Vec<u8>,
+    },
+}
+
+/// The sending side of the queue of a subscribed connection.
+#[derive(Debug, Clone)]
+struct SubscriberTx {
+    tx: Sender<PubSubMessage>,
+    overflowed: Arc<AtomicBool>,
+}
+
+type Subscribers = HashMap<u64, SubscriberTx>;
+
+#[derive(Debug, Default)]
+pub(crate) struct PubSub {
+    next_id: AtomicU64,
+    channels: DashMap<String, Subscribers>,
+    patterns: DashMap<String, Subscribers>,
+    shards: DashMap<u16, DashMap<String, Subscribers>>, // slot -> shard channels
+}
+
+/// The subscriptions of a connection, unsubscribed from everything when dropped.
+#[derive(Debug)]
+pub struct Subscriber {
+    id: u64,
+    backend: Backend,
+    tx: SubscriberTx,
+    rx: Receiver<PubSubMessage>,
+    channels: BTreeSet<String>,
+    patterns: BTreeSet<String>,
+    shard_channels: BTreeSet<String>,
+}
+
+fn add(map: &DashMap<String, Subscribers>, name: &str, id: u64, tx: &SubscriberTx) {
+    map.entry(name.to_string())
+        .or_default()
+        .insert(id, tx.clone());
+}
+
+fn remove(map: &DashMap<String, Subscribers>, name: &str, id: u64) {
+    map.remove_if_mut(name, |_, subscribers| {
+        subscribers.remove(&id);
+        subscribers.is_empty()
+    });
+}
+
+fn add_shard(pubsub: &PubSub, channel: &str, id: u64, tx: &SubscriberTx) {
+    let slot = key_slot(channel.as_bytes());
+    add(&pubsub.shards.entry(slot).or_default(), channel, id, tx);
+}
+
+fn remove_shard(pubsub: &PubSub, channel: &str, id: u64) {
+    let slot = key_slot(channel.as_bytes());
+    pubsub.shards.remove_if(&slot, |_, channels| {
+        remove(channels, channel, id);
+        channels.is_empty()
+    });
+}
+
+impl SubscriberTx {
+    /// Queue the message, returns false if the connection is closed.
+    /// A full queue marks the connection to be disconnected, the message is lost.
+    fn send(&self, message: PubSubMessage) -> bool {
+        match self.tx.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.overflowed.store(true, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+impl Subscriber {
+    /// Subscribe to the channel, returns the number of subscriptions of the connection.
+    pub fn subscribe(&mut self, channel: &str) -> usize {
+        if self.channels.insert(channel.to_string()) {
+            add(&self.backend.pubsub.channels, channel, self.id, &self.tx);
+        }
+        self.count()
+    }
+
+    pub fn unsubscribe(&mut self, channel: &str) -> usize {
+        if self.channels.remove(channel) {
+            remove(&self.backend.pubsub.channels, channel, self.id);
+        }
+        self.count()
+    }
+
+    pub fn psubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.insert(pattern.to_string()) {
+            add(&self.backend.pubsub.patterns, pattern, self.id, &self.tx);
+        }
+        self.count()
+    }
+
+    pub fn punsubscribe(&mut self, pattern: &str) -> usize {
+        if self.patterns.remove(pattern) {
+            remove(&self.backend.pubsub.patterns, pattern, self.id);
+        }
+        self.count()
+    }
+
+    /// Subscribe to the shard channel, returns the number of shard channels of the connection.
+    pub fn ssubscribe(&mut self, channel: &str) -> usize {
+        if self.shard_channels.insert(channel.to_string()) {
+            add_shard(&self.backend.pubsub, channel, self.id, &self.tx);
+        }
+        self.shard_channels.len()
+    }
+
+    pub fn sunsubscribe(&mut self, channel: &str) -> usize {
+        if self.shard_channels.remove(channel) {
+            remove_shard(&self.backend.pubsub, channel, self.id);
+        }
+        self.shard_channels.len()
+    }
+
+    /// The subscribed channels, in order.
+    pub fn channels(&self) -> Vec<String> {
+        self.channels.iter().cloned().collect()
+    }
+
+    pub fn patterns(&self) -> Vec<String> {
+        self.patterns.iter().cloned().collect()
+    }
+
+    pub fn shard_channels(&self) -> Vec<String> {
+        self.shard_channels.iter().cloned().collect()
+    }
+
+    /// The number of channels and patterns subscribed.
+    pub fn count(&self) -> usize {
+        self.channels.len() + self.patterns.len()
+    }
+
+    /// Whether there is no subscription at all, including the shard channels.
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0 && self.shard_channels.is_empty()
+    }
+
+    /// Wait for the next message published to the subscriptions.
+    /// Returns None once the queue overflowed, the connection has to be closed.
+    pub async fn recv(&mut self) -> Option<PubSubMessage> {
+        let message = self.rx.recv().await;
+        match self.tx.overflowed.load(Ordering::Relaxed) {
+            true => None,
+            false => message,
+        }
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        for channel in &self.channels {
+            remove(&self.backend.pubsub.channels, channel, self.id);
+        }
+        for pattern in &self.patterns {
+            remove(&self.backend.pubsub.patterns, pattern, self.id);
+        }
+        for channel in &self.shard_channels {
+            remove_shard(&self.backend.pubsub, channel, self.id);
+        }
+    }
+}
+
+impl Backend {
+    /// A new connection-side handle to subscribe to channels and patterns.
+    pub fn subscriber(&self) -> Subscriber {
+        let (tx, rx) = channel(PUBSUB_QUEUE_LIMIT);
+        Subscriber {
+            id: self.pubsub.next_id.fetch_add(1, Ordering::Relaxed),
+            backend: self.clone(),
+            tx: SubscriberTx {
+                tx,
+                overflowed: Arc::new(AtomicBool::new(false)),
+            },
+            rx,
+            channels: BTreeSet::new(),
+            patterns: BTreeSet::new(),
+            shard_channels: BTreeSet::new(),
+        }
+    }
+
+    /// Send the message to the subscribers of the channel and the matching
+    /// patterns, returns the number of the receivers.
+    pub fn publish(&self, channel: &str, payload: &[u8]) -> i64 {
+        let mut receivers = 0;
+        if let Some(subscribers) = self.pubsub.channels.get(channel) {
+            for tx in subscribers.values() {
+                let message = PubSubMessage::Message {
+                    channel: channel.to_string(),
+                    payload: payload.to_vec(),
+                };
+                // a closed connection unsubscribes when its subscriber is dropped
+                if tx.send(message) {
+                    receivers += 1;
+                }
+            }
+        }
+        for entry in self.pubsub.patterns.iter() {
+            if !glob_match(entry.key().as_bytes(), channel.as_bytes()) {
+                continue;
+            }
+            for tx in entry.value().values() {
+                let message = PubSubMessage::PMessage {
+                    pattern: entry.key().clone(),
+                    channel: channel.to_string(),
+                    payload: payload.to_vec(),
+                };
+                if tx.send(message) {
+                    receivers += 1;
+                }
+            }
+        }
+        receivers
+    }
+
+    /// Send the message to the subscribers of the shard channel, returns the
+    /// number of the receivers.
+    pub fn spublish(&self, channel: &str, payload: &[u8]) -> i64 {
+        let slot = key_slot(channel.as_bytes());
+        let Some(channels) = self.pubsub.shards.get(&slot) else {
+            return 0;
+        };
+        let Some(subscribers) = channels.get(channel) else {
+            return 0;
+        };
+        let message = PubSubMessage::SMessage {
+            channel: channel.to_string(),
+            payload: payload.to_vec(),
+        };
+        subscribers
+            .values()
+            .filter(|tx| tx.send(message.clone()))
+            .count() as i64
+    }
+
+    /// The channels with at least one subscriber, optionally matching the pattern.
+    pub fn pubsub_channels(&self, pattern: Option<&str>) -> Vec<String> {
+        let mut channels = self
+            .pubsub
+            .channels
+            .iter()
+            .map(|entry| entry.key().clone())
+            .filter(|channel| pattern.is_none_or(|p| glob_match(p.as_bytes(), channel.as_bytes())))
+            .collect::<Vec<_>>();
+        channels.sort();
+        channels
+    }
+
+    /// The number of subscribers of each channel, patterns are not counted.
+    pub fn pubsub_numsub(&self, channels: &[String]) -> Vec<(String, i64)> {
+        channels
+            .iter()
+            .map(|channel| {
+                let count = self.pubsub.channels.get(channel).map_or(0, |s| s.len());
+                (channel.clone(), count as i64)
+            })
+            .collect()
+    }
+
+    /// The shard channels with at least one subscriber, optionally matching the pattern.
+    pub fn pubsub_shardchannels(&self, pattern: Option<&str>) -> Vec<String> {
+        let mut channels = self
+            .pubsub
+            .shards
+            .iter()
+            .flat_map(|slot| {
+                slot.iter()
+                    .map(|entry| entry.key().clone())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|channel| pattern.is_none_or(|p| glob_match(p.as_bytes(), channel.as_bytes())))
+            .collect::<Vec<_>>();
+        channels.sort();
+        channels
+    }
+
+    /// The number of subscribers of each shard channel.
+    pub fn pubsub_shardnumsub(&self, channels: &[String]) -> Vec<(String, i64)> {
+        channels
+            .iter()
+            .map(|channel| {
+                let slot = key_slot(channel.as_bytes());
+                let count = self
+                    .pubsub
+                    .shards
+                    .get(&slot)
+                    .map_or(0, |channels| channels.get(channel).map_or(0, |s| s.len()));
+                (channel.clone(), count as i64)
+            })
+            .collect()
+    }
+
+    /// The number of unique patterns subscribed by all the connections.
+    pub fn pubsub_numpat(&self) -> i64 {
+        self.pubsub.patterns.len() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_subscribe() {
+        let backend = Backend::new();
+        let mut news = backend.subscriber();
+        let mut all = backend.subscriber();
+        assert_eq!(news.subscribe("news"), 1);
+        assert_eq!(news.subscribe("news"), 1);
+        assert_eq!(all.psubscribe("n*"), 1);
+        assert_eq!(all.subscribe("weather"), 2);
+
+        assert_eq!(backend.publish("news", b"hello"), 2);
+        assert_eq!(backend.publish("sports", b"goal"), 0);
+        assert_eq!(
+            news.recv().await,
+            Some(PubSubMessage::Message {
+                channel: "news".to_string(),
+                payload: b"hello".to_vec()
+            })
+        );
+        assert_eq!(
+            all.recv().await,
+            Some(PubSubMessage::PMessage {
+                pattern: "n*".to_string(),
+                channel: "news".to_string(),
+                payload: b"hello".to_vec()
+            })
+        );
+
+        assert_eq!(backend.pubsub_channels(None), ["news", "weather"]);
+        assert_eq!(backend.pubsub_channels(Some("w*")), ["weather"]);
+        assert_eq!(backend.pubsub_numpat(), 1);
+        let numsub = backend.pubsub_numsub(&["news".to_string(), "none".to_string()]);
+        assert_eq!(numsub, [("news".to_string(), 1), ("none".to_string(), 0)]);
+
+        assert_eq!(news.unsubscribe("news"), 0);
+        drop(all);
+        assert!(backend.pubsub_channels(None).is_empty());
+        assert_eq!(backend.pubsub_numpat(), 0);
+        assert_eq!(backend.publish("news", b"hello"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_overflow() {
+        let backend = Backend::new();
+        let mut slow = backend.subscriber();
+        slow.subscribe("news");
+        for _ in 0..PUBSUB_QUEUE_LIMIT {
+            backend.publish("news", b"hello");
+        }
+        assert!(slow.recv().await.is_some());
+
+        // the queue is full again, the next message overflows it
+        backend.publish("news", b"hello");
+        backend.publish("news", b"hello");
+        assert_eq!(slow.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_sharded_publish_subscribe() {
+        let backend = Backend::new();
+        let mut global = backend.subscriber();
+        let mut sharded = backend.subscriber();
+        global.subscribe("news");
+        assert_eq!(sharded.ssubscribe("news"), 1);
+        assert_eq!(sharded.ssubscribe("{news}.sports"), 2);
+        assert_eq!(sharded.count(), 0);
+        assert!(!sharded.is_empty());
+
+        // the shard channels are separate from the global channels
+        assert_eq!(backend.spublish("news", b"hello"), 1);
+        assert_eq!(
+            sharded.recv().await,
+            Some(PubSubMessage::SMessage {
+                channel: "news".to_string(),
+                payload: b"hello".to_vec()
+            })
+        );
+        assert_eq!(backend.pubsub_channels(None), ["news"]);
+        assert_eq!(
+            backend.pubsub_shardchannels(None),
+            ["news", "{news}.sports"]
+        );
+        let numsub = backend.pubsub_shardnumsub(&["{news}.sports".to_string()]);
+        assert_eq!(numsub, [("{news}.sports".to_string(), 1)]);
+
+        assert_eq!(sharded.sunsubscribe("news"), 1);
+        drop(sharded);
+        assert!(backend.pubsub_shardchannels(None).is_empty());
+        assert!(backend.pubsub.shards.is_empty());
+        assert_eq!(backend.publish("news", b"hello"), 1);
+    }
+}
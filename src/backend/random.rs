@@ -0,0 +1,99 @@
+use std::cell::Cell;
+use std::collections::{hash_map::RandomState, HashSet};
+use std::hash::{BuildHasher, Hasher};
+
+use hashbrown::raw::RawTable;
+
+// A small xorshift64* generator, good enough for the skiplist levels and
+// the random commands, it is not meant to be cryptographically secure.
+
+thread_local! {
+    static STATE: Cell<u64> = Cell::new(seed());
+}
+
+fn seed() -> u64 {
+    // RandomState is randomly keyed per thread, use it as the entropy source
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0x9e3779b97f4a7c15);
+    hasher.finish() | 1
+}
+
+/// A pseudo random u64.
+pub(crate) fn random_u64() -> u64 {
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+        x.wrapping_mul(0x2545f4914f6cdd1d)
+    })
+}
+
+/// A pseudo random number in `0..n`, `n` must be positive.
+pub(crate) fn random_below(n: usize) -> usize {
+    (random_u64() % n as u64) as usize
+}
+
+/// A random item of the table, None if it's empty. Like redis, random buckets are
+/// tried until a full one is found, a table left sparse by removals is walked instead.
+pub(crate) fn random_item<T>(table: &RawTable<T>) -> Option<&T> {
+    if table.is_empty() {
+        return None;
+    }
+    let buckets = table.buckets();
+    if table.len() * 8 < buckets {
+        // SAFETY: the table outlives the returned reference
+        return unsafe { table.iter().nth(random_below(table.len())) }
+            .map(|b| unsafe { b.as_ref() });
+    }
+    loop {
+        let index = random_below(buckets);
+        // SAFETY: the index is below the number of buckets, and a full bucket holds an item
+        unsafe {
+            if table.is_bucket_full(index) {
+                return Some(table.bucket(index).as_ref());
+            }
+        }
+    }
+}
+
+/// Pick `count` distinct items at random, or all of them if there are not enough.
+pub(crate) fn random_sample<T>(table: &RawTable<T>, count: usize) -> Vec<&T> {
+    if count.saturating_mul(3) < table.len() {
+        // a few items of a big table, pick them one by one
+        let mut seen = HashSet::new();
+        let mut items = vec![];
+        while items.len() < count {
+            let item = random_item(table).expect("the table is not empty");
+            if seen.insert(item as *const T) {
+                items.push(item);
+            }
+        }
+        return items;
+    }
+
+    // SAFETY: the table outlives the returned references
+    let mut items = unsafe { table.iter().map(|b| b.as_ref()).collect::<Vec<_>>() };
+    let count = count.min(items.len());
+    // partial Fisher-Yates shuffle
+    for i in 0..count {
+        let j = i + random_below(items.len() - i);
+        items.swap(i, j);
+    }
+    items.truncate(count);
+    items
+}
+
+/// Pick `count` items at random, the same item may be picked multiple times.
+pub(crate) fn random_choices<T>(table: &RawTable<T>, count: usize) -> Vec<&T> {
+    // NOTE: the count comes from the client, don't reserve it upfront
+    let mut items = vec![];
+    while items.len() < count {
+        match random_item(table) {
+            Some(item) => items.push(item),
+            None => break,
+        }
+    }
+    items
+}
@@ -0,0 +1,318 @@
+use std::hash::BuildHasher;
+
+use dashmap::DashMap;
+use hashbrown::raw::RawTable;
+
+use crate::RespFrame;
+
+use super::{glob_match, Backend, BackendError, HashMap, HashSet};
+
+// The cursor of SCAN / HSCAN / SSCAN is a block of bucket positions of the
+// hash table, incremented in the reverse binary order of the redis dictScan:
+// the high bits of the block are incremented first, so the blocks already
+// visited are still visited when the table grows or shrinks meanwhile, and an
+// element present for the whole iteration is always returned, maybe more than
+// once.
+// The hashbrown tables are open addressing, an element is not always stored in
+// the bucket of its hash, so a block visits the elements whose probe sequence
+// starts in it, wherever they are stored. The positions of a block share most
+// of their probe sequences, so an element is only hashed once per block.
+// SCAN walks the shards of the DashMap one after another, the shard is in the
+// low bits of the cursor.
+// Cursor 0 starts an iteration, and is returned when the iteration is done.
+
+const BLOCK_BITS: u32 = 4; // a block of 16 positions, the whole table if it is smaller
+
+/// The work of one call: about `count` elements, and at most 10 times `count`
+/// blocks for a sparse table, like redis does for the buckets.
+struct ScanLimit {
+    elements: usize,
+    blocks: usize,
+}
+
+impl ScanLimit {
+    fn new(count: usize) -> Self {
+        let count = count.max(1);
+        Self {
+            elements: count,
+            blocks: count.saturating_mul(10),
+        }
+    }
+
+    fn reached(&self) -> bool {
+        self.elements == 0 || self.blocks == 0
+    }
+}
+
+/// Visit the elements of the block, returns the next block, 0 after the last one.
+fn scan_block<T>(
+    table: &RawTable<T>,
+    cursor: u64,
+    hash: impl Fn(&T) -> u64,
+    mut f: impl FnMut(&T),
+) -> u64 {
+    let buckets = table.buckets() as u64;
+    let mask = (buckets >> BLOCK_BITS).max(1) - 1;
+    let block = cursor & mask;
+    let start = block << BLOCK_BITS;
+    let mut found = vec![];
+    for position in start..(start + (1 << BLOCK_BITS)).min(buckets) {
+        // a probe sequence only yields the elements of the same 7 bits tag, the
+        // top bits of the hash, so try them all
+        for tag in 0..128u64 {
+            // SAFETY: the table is borrowed, it can't be modified while its buckets are read
+            for bucket in unsafe { table.iter_hash(tag << 57 | position) } {
+                found.push(unsafe { table.bucket_index(&bucket) });
+            }
+        }
+    }
+    found.sort_unstable();
+    found.dedup();
+    for index in found {
+        // SAFETY: the index is of a full bucket found above
+        let item = unsafe { table.bucket(index).as_ref() };
+        if (hash(item) & (buckets - 1)) >> BLOCK_BITS == block {
+            f(item);
+        }
+    }
+    (cursor | !mask)
+        .reverse_bits()
+        .wrapping_add(1)
+        .reverse_bits()
+}
+
+/// Visit the blocks from the cursor until the limit is reached, returns the
+/// next block, 0 once the table is done.
+fn scan_table<T>(
+    table: &RawTable<T>,
+    mut cursor: u64,
+    limit: &mut ScanLimit,
+    hash: impl Fn(&T) -> u64,
+    mut f: impl FnMut(&T),
+) -> u64 {
+    loop {
+        cursor = scan_block(table, cursor, &hash, |item| {
+            limit.elements = limit.elements.saturating_sub(1);
+            f(item);
+        });
+        limit.blocks -= 1;
+        if cursor == 0 || limit.reached() {
+            return cursor;
+        }
+    }
+}
+
+/// Visit about `count` entries of a DashMap from the cursor, returns the next
+/// cursor, 0 once the map is done.
+fn scan_shards<V>(
+    map: &DashMap<String, V>,
+    cursor: u64,
+    count: usize,
+    mut f: impl FnMut(&String, &V),
+) -> u64 {
+    let shards = map.shards();
+    let bits = shards.len().trailing_zeros();
+    let mut shard = (cursor & (shards.len() as u64 - 1)) as usize;
+    let mut block = cursor >> bits;
+    let mut limit = ScanLimit::new(count);
+    loop {
+        let table = shards[shard].read();
+        block = scan_table(
+            &table,
+            block,
+            &mut limit,
+            |(k, _)| map.hash_usize(k) as u64,
+            |(k, v)| f(k, v.get()),
+        );
+        drop(table);
+        if block == 0 {
+            shard += 1;
+        }
+        if shard == shards.len() {
+            return 0;
+        }
+        if limit.reached() {
+            return block << bits | shard as u64;
+        }
+    }
+}
+
+fn matches(pattern: Option<&str>, s: &str) -> bool {
+    pattern.is_none_or(|p| glob_match(p.as_bytes(), s.as_bytes()))
+}
+
+impl Backend {
+    /// SCAN the keyspace, optionally filtered by a glob pattern and the value type.
+    pub fn scan(
+        &self,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+        key_type: Option<&str>,
+    ) -> (u64, Vec<String>) {
+        let mut keys = vec![];
+        let next = scan_shards(&self.db, cursor, count, |k, v| {
+            if matches(pattern, k) && key_type.is_none_or(|t| t == v.type_name()) {
+                keys.push(k.clone());
+            }
+        });
+        // the shards are only locked for the read, the expired keys are removed afterwards
+        keys.retain(|k| !self.expire_if_needed(k));
+        (next, keys)
+    }
+
+    /// Visit about `count` keys with a TTL, and their deadline, for the active expiration.
+    pub(crate) fn scan_expires(
+        &self,
+        cursor: u64,
+        count: usize,
+        mut f: impl FnMut(&String, i64),
+    ) -> u64 {
+        scan_shards(&self.expires, cursor, count, |k, at| f(k, *at))
+    }
+
+    /// HSCAN the fields of a hash.
+    pub fn hscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> Result<(u64, Vec<(String, RespFrame)>), BackendError> {
+        let ret = self.read(key, |hmap: &HashMap<String, RespFrame>| {
+            let mut fields = vec![];
+            let next = scan_table(
+                hmap.raw_table(),
+                cursor,
+                &mut ScanLimit::new(count),
+                |(k, _)| hmap.hasher().hash_one(k),
+                |(k, v)| {
+                    if matches(pattern, k) {
+                        fields.push((k.clone(), v.clone()));
+                    }
+                },
+            );
+            (next, fields)
+        })?;
+        Ok(ret.unwrap_or((0, vec![])))
+    }
+
+    /// SSCAN the members of a set.
+    pub fn sscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        pattern: Option<&str>,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), BackendError> {
+        let ret = self.read(key, |set: &HashSet<String>| {
+            let mut members = vec![];
+            let next = scan_table(
+                set.raw_table(),
+                cursor,
+                &mut ScanLimit::new(count),
+                |(m, _)| set.hasher().hash_one(m),
+                |(m, _)| {
+                    if matches(pattern, m) {
+                        members.push(m.clone());
+                    }
+                },
+            );
+            (next, members)
+        })?;
+        Ok(ret.unwrap_or((0, vec![])))
+    }
+
+    /// All the keys matching the glob pattern.
+    pub fn keys(&self, pattern: &str) -> Vec<String> {
+        let keys = self
+            .db
+            .iter()
+            .map(|v| v.key().clone())
+            .filter(|k| glob_match(pattern.as_bytes(), k.as_bytes()))
+            .collect::<Vec<_>>();
+        // NOTE: the iterator of DashMap holds the read lock, can't remove the expired keys in it
+        keys.into_iter()
+            .filter(|k| !self.expire_if_needed(k))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_scan_returns_all_keys() {
+        let backend = Backend::new();
+        for i in 0..100 {
+            backend.set(format!("key:{}", i), "value");
+        }
+
+        let mut cursor = 0;
+        let mut seen = HashSet::new();
+        let mut rounds = 0;
+        loop {
+            let (next, keys) = backend.scan(cursor, None, 10, None);
+            seen.extend(keys);
+            // the keyspace changes during the iteration
+            backend.set(format!("new:{}", rounds), "value");
+            backend.del(&[format!("key:{}", 99 - rounds)]);
+            rounds += 1;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        for i in 0..(100 - rounds) {
+            assert!(seen.contains(&format!("key:{}", i)));
+        }
+    }
+
+    #[test]
+    fn test_scan_with_pattern_and_type() {
+        let backend = Backend::new();
+        backend.set("user:1".to_string(), "value");
+        backend.set("post:1".to_string(), "value");
+        backend.sadd("user:2", vec!["hello".to_string()]).unwrap();
+
+        let (next, mut keys) = backend.scan(0, Some("user:*"), 100, None);
+        keys.sort();
+        assert_eq!(next, 0);
+        assert_eq!(keys, vec!["user:1", "user:2"]);
+
+        let (_, keys) = backend.scan(0, None, 100, Some("set"));
+        assert_eq!(keys, vec!["user:2"]);
+
+        let mut keys = backend.keys("*:1");
+        keys.sort();
+        assert_eq!(keys, vec!["post:1", "user:1"]);
+    }
+
+    #[test]
+    fn test_sscan_while_the_set_grows() {
+        let backend = Backend::new();
+        let members = (0..100).map(|i| format!("m:{}", i)).collect::<Vec<_>>();
+        backend.sadd("set", members.clone()).unwrap();
+
+        let mut cursor = 0;
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        loop {
+            let (next, batch) = backend.sscan("set", cursor, None, 10).unwrap();
+            seen.extend(batch);
+            // the members are moved into the buckets of a bigger table
+            if round < 3 {
+                let added = (0..200).map(|i| format!("new:{}:{}", round, i));
+                backend.sadd("set", added.collect::<Vec<_>>()).unwrap();
+            }
+            round += 1;
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        assert!(members.iter().all(|m| seen.contains(m)));
+    }
+}
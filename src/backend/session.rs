@@ -0,0 +1,247 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::Notify;
+
+use super::{now_ms, Backend};
+
+// Every connection registers a session in the backend, so CLIENT LIST can show
+// all the connections and CLIENT KILL can close any of them. The connection
+// keeps the session up to date, e.g. the last command and its subscriptions.
+
+/// There are no ACL users, every connection is authenticated as the default user.
+pub const DEFAULT_USER: &str = "default";
+
+/// The state of a connection which changes over time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionState {
+    pub name: Option<String>,
+    pub db: usize,
+    pub protocol: u8,          // 2 or 3
+    pub last_interaction: i64, // unix time in milliseconds
+    pub last_command: String,
+    pub no_evict: bool,
+    pub sub: usize,           // subscribed channels
+    pub psub: usize,          // subscribed patterns
+    pub ssub: usize,          // subscribed shard channels
+    pub multi: Option<usize>, // the number of queued commands inside MULTI
+    pub watch: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct SessionInner {
+    id: u64,
+    addr: String,  // the address of the client
+    laddr: String, // the local address of the connection
+    created: i64,
+    state: Mutex<SessionState>,
+    killed: AtomicBool,
+    kill: Notify,
+}
+
+/// The session of a connection, unregistered when dropped.
+#[derive(Debug)]
+pub struct Session {
+    inner: Arc<SessionInner>,
+    backend: Backend,
+}
+
+/// A snapshot of a session, as listed by CLIENT LIST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub id: u64,
+    pub addr: String,
+    pub laddr: String,
+    pub age: i64,  // in seconds
+    pub idle: i64, // in seconds
+    pub user: &'static str,
+    pub state: SessionState,
+}
+
+/// The filters of CLIENT KILL, a connection is killed if it matches all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientKillFilter {
+    pub id: Option<u64>,
+    pub addr: Option<String>,
+    pub laddr: Option<String>,
+    pub user: Option<String>,
+    pub skip: Option<u64>, // the ID of the connection not to kill, e.g. the caller
+}
+
+impl SessionInner {
+    fn info(&self) -> ClientInfo {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let now = now_ms();
+        ClientInfo {
+            id: self.id,
+            addr: self.addr.clone(),
+            laddr: self.laddr.clone(),
+            age: (now - self.created) / 1000,
+            idle: (now - state.last_interaction) / 1000,
+            user: DEFAULT_USER,
+            state,
+        }
+    }
+
+    fn kill(&self) {
+        self.killed.store(true, Ordering::Relaxed);
+        self.kill.notify_one();
+    }
+}
+
+impl ClientKillFilter {
+    fn matches(&self, session: &SessionInner) -> bool {
+        self.id.is_none_or(|id| id == session.id)
+            && self.addr.as_ref().is_none_or(|addr| *addr == session.addr)
+            && self
+                .laddr
+                .as_ref()
+                .is_none_or(|laddr| *laddr == session.laddr)
+            && self.user.as_ref().is_none_or(|user| user == DEFAULT_USER)
+            && self.skip.is_none_or(|id| id != session.id)
+    }
+}
+
+impl Session {
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn update<R>(&self, f: impl FnOnce(&mut SessionState) -> R) -> R {
+        f(&mut self.inner.state.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Record the command the connection is about to execute.
+    pub fn touch(&self, command: &str) {
+        self.update(|state| {
+            state.last_interaction = now_ms();
+            state.last_command = command.to_string();
+        });
+    }
+
+    pub fn info(&self) -> ClientInfo {
+        self.inner.info()
+    }
+
+    pub fn is_killed(&self) -> bool {
+        self.inner.killed.load(Ordering::Relaxed)
+    }
+
+    /// Wait until the connection is killed by CLIENT KILL.
+    /// The future doesn't borrow the session, it also cancels a running command.
+    pub fn killed(&self) -> impl Future<Output = ()> + Send + 'static {
+        let inner = self.inner.clone();
+        async move {
+            while !inner.killed.load(Ordering::Relaxed) {
+                inner.kill.notified().await;
+            }
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.backend.sessions.remove(&self.inner.id);
+    }
+}
+
+impl Backend {
+    /// Register the session of a new connection.
+    pub fn new_session(&self, addr: impl Into<String>, laddr: impl Into<String>) -> Session {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let now = now_ms();
+        let inner = Arc::new(SessionInner {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            addr: addr.into(),
+            laddr: laddr.into(),
+            created: now,
+            state: Mutex::new(SessionState {
+                name: None,
+                db: 0,
+                protocol: 2,
+                last_interaction: now,
+                last_command: "NULL".to_string(),
+                no_evict: false,
+                sub: 0,
+                psub: 0,
+                ssub: 0,
+                multi: None,
+                watch: 0,
+            }),
+            killed: AtomicBool::new(false),
+            kill: Notify::new(),
+        });
+        self.sessions.insert(inner.id, inner.clone());
+        Session {
+            inner,
+            backend: self.clone(),
+        }
+    }
+
+    /// The connections ordered by ID.
+    pub fn client_list(&self) -> Vec<ClientInfo> {
+        let mut clients = self
+            .sessions
+            .iter()
+            .map(|session| session.info())
+            .collect::<Vec<_>>();
+        clients.sort_by_key(|client| client.id);
+        clients
+    }
+
+    /// Close the matching connections, returns the number of the connections killed.
+    pub fn client_kill(&self, filter: &ClientKillFilter) -> i64 {
+        let killed = self
+            .sessions
+            .iter()
+            .filter(|session| filter.matches(session))
+            .map(|session| session.kill())
+            .count();
+        killed as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_session() {
+        let backend = Backend::new();
+        let first = backend.new_session("127.0.0.1:50001", "127.0.0.1:6379");
+        let second = backend.new_session("127.0.0.1:50002", "127.0.0.1:6379");
+        assert!(second.id() > first.id());
+
+        first.update(|state| state.name = Some("worker".to_string()));
+        first.touch("get");
+        let clients = backend.client_list();
+        assert_eq!(clients.len(), 2);
+        assert_eq!(clients[0].state.name.as_deref(), Some("worker"));
+        assert_eq!(clients[0].state.last_command, "get");
+
+        let filter = ClientKillFilter {
+            user: Some(DEFAULT_USER.to_string()),
+            skip: Some(first.id()),
+            ..Default::default()
+        };
+        assert_eq!(backend.client_kill(&filter), 1);
+        second.killed().await;
+        assert!(!first.is_killed());
+
+        drop(second);
+        assert_eq!(backend.client_list().len(), 1);
+    }
+}
@@ -0,0 +1,217 @@
+use super::value::HashSet;
+
+use super::{
+    random::{random_choices, random_sample},
+    Backend, BackendError, Value,
+};
+
+type Set = HashSet<String>;
+
+/// The set algebra of SINTER / SUNION / SDIFF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+    Inter,
+    Union,
+    Diff,
+}
+
+/// Random members, see `Backend::srandmember` for the meaning of `count`.
+fn random_members(set: &Set, count: i64) -> Vec<String> {
+    let members = match count >= 0 {
+        true => random_sample(set.raw_table(), count as usize),
+        false => random_choices(set.raw_table(), count.unsigned_abs() as usize),
+    };
+    members.into_iter().map(|(m, _)| m.clone()).collect()
+}
+
+impl Backend {
+    /// Remove the members, returns the number of the removed members.
+    pub fn srem(&self, key: &str, members: &[String]) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |set: &mut Set| members.iter().filter(|m| set.remove(m.as_str())).count() as i64,
+            |&n| n > 0,
+        )
+    }
+
+    pub fn smembers(&self, key: &str) -> Result<Vec<String>, BackendError> {
+        let ret = self.read(key, |set: &Set| set.iter().cloned().collect())?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    pub fn scard(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |set: &Set| set.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    pub fn smismember(&self, key: &str, members: &[String]) -> Result<Vec<bool>, BackendError> {
+        let ret = self.read(key, |set: &Set| {
+            members.iter().map(|m| set.contains(m)).collect()
+        })?;
+        Ok(ret.unwrap_or_else(|| vec![false; members.len()]))
+    }
+
+    /// Remove and return up to `count` random members.
+    pub fn spop(&self, key: &str, count: usize) -> Result<Vec<String>, BackendError> {
+        self.write(
+            key,
+            |set: &mut Set| {
+                let members = random_members(set, count as i64);
+                for member in &members {
+                    set.remove(member);
+                }
+                members
+            },
+            |members| !members.is_empty(),
+        )
+    }
+
+    /// Random members without removing them. A positive count returns distinct members,
+    /// a negative count returns exactly `-count` members which may repeat.
+    pub fn srandmember(&self, key: &str, count: i64) -> Result<Vec<String>, BackendError> {
+        let ret = self.read(key, |set: &Set| random_members(set, count))?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    /// Move the member from the source set to the destination set,
+    /// returns false if the member is not in the source set.
+    pub fn smove(&self, src: &str, dst: &str, member: &str) -> Result<bool, BackendError> {
+        // check the destination first, so a wrong type doesn't lose the member
+        self.read(dst, |_: &Set| ())?;
+        if src == dst {
+            return self.sismember(src, member);
+        }
+        if !self.write(src, |set: &mut Set| set.remove(member), |&removed| removed)? {
+            return Ok(false);
+        }
+        self.sadd(dst, vec![member.to_string()])?;
+        Ok(true)
+    }
+
+    /// The intersection / union / difference of the sets, a missing key is an empty set.
+    pub fn set_op(&self, keys: &[String], op: SetOp) -> Result<Set, BackendError> {
+        let mut sets = self.read_sets(keys)?.into_iter();
+        let Some(mut ret) = sets.next() else {
+            return Ok(Set::default());
+        };
+        for set in sets {
+            match op {
+                SetOp::Inter => ret.retain(|m| set.contains(m)),
+                SetOp::Union => ret.extend(set),
+                SetOp::Diff => ret.retain(|m| !set.contains(m)),
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Store the result of `set_op` in `dst`, returns the size of the result.
+    pub fn set_op_store(&self, dst: &str, keys: &[String], op: SetOp) -> Result<i64, BackendError> {
+        let set = self.set_op(keys, op)?;
+        let len = set.len() as i64;
+        self.store(dst, Value::Set(set));
+        Ok(len)
+    }
+
+    /// The size of the intersection, counting stops at `limit` if it is not 0.
+    pub fn sintercard(&self, keys: &[String], limit: usize) -> Result<i64, BackendError> {
+        let sets = self.read_sets(keys)?;
+        let Some((first, others)) = sets.split_first() else {
+            return Ok(0);
+        };
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let count = first
+            .iter()
+            .filter(|m| others.iter().all(|set| set.contains(*m)))
+            .take(limit)
+            .count();
+        Ok(count as i64)
+    }
+
+    /// Read all the keys first, so a wrong type is always reported.
+    fn read_sets(&self, keys: &[String]) -> Result<Vec<Set>, BackendError> {
+        let mut sets = Vec::with_capacity(keys.len());
+        for key in keys {
+            sets.push(self.read(key, |set: &Set| set.clone())?.unwrap_or_default());
+        }
+        Ok(sets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_op() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        backend.sadd(
+            "s1",
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        )?;
+        backend.sadd(
+            "s2",
+            vec!["b".to_string(), "c".to_string(), "d".to_string()],
+        )?;
+        let keys = ["s1".to_string(), "s2".to_string()];
+
+        let sorted = |set: Set| {
+            let mut v = set.into_iter().collect::<Vec<_>>();
+            v.sort();
+            v
+        };
+        assert_eq!(sorted(backend.set_op(&keys, SetOp::Inter)?), ["b", "c"]);
+        assert_eq!(
+            sorted(backend.set_op(&keys, SetOp::Union)?),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(sorted(backend.set_op(&keys, SetOp::Diff)?), ["a"]);
+        assert_eq!(backend.sintercard(&keys, 1)?, 1);
+
+        let missing = ["s1".to_string(), "none".to_string()];
+        assert_eq!(backend.set_op_store("dst", &missing, SetOp::Inter)?, 0);
+        assert!(!backend.exists("dst"));
+        assert_eq!(backend.set_op_store("dst", &keys, SetOp::Diff)?, 1);
+        assert_eq!(backend.smembers("dst")?, ["a"]);
+
+        backend.set("str".to_string(), "v");
+        let wrong = ["s1".to_string(), "str".to_string()];
+        assert_eq!(
+            backend.set_op(&wrong, SetOp::Union),
+            Err(BackendError::WrongType)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_members() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        backend.sadd("s", vec!["a".to_string(), "b".to_string(), "c".to_string()])?;
+
+        let mut members = backend.srandmember("s", 5)?;
+        members.sort();
+        assert_eq!(members, ["a", "b", "c"]);
+        assert_eq!(backend.srandmember("s", -5)?.len(), 5);
+        assert_eq!(backend.srandmember("none", -5)?.len(), 0);
+
+        assert_eq!(backend.spop("s", 2)?.len(), 2);
+        assert_eq!(backend.scard("s")?, 1);
+        assert_eq!(backend.spop("s", 2)?.len(), 1);
+        assert!(!backend.exists("s"));
+
+        // a few distinct members of a big set, then of the same set left sparse
+        let members = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>();
+        backend.sadd("big", members.clone())?;
+        let mut picked = backend.srandmember("big", 10)?;
+        picked.sort();
+        picked.dedup();
+        assert_eq!(picked.len(), 10);
+        backend.srem("big", &members[..995])?;
+        let mut picked = backend.srandmember("big", 3)?;
+        picked.sort();
+        picked.dedup();
+        assert_eq!(picked.len(), 3);
+        assert!(picked.iter().all(|m| m.parse::<i32>().unwrap() >= 995));
+        assert_eq!(backend.srandmember("big", -20)?.len(), 20);
+        Ok(())
+    }
+}
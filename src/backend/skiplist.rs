@@ -0,0 +1,381 @@
+use super::random::random_u64;
+
+// A skiplist ordered by (score, member), with the span of every forward link,
+// as in the redis implementation, so the rank of a node can be computed while
+// searching it. The nodes live in an arena and link to each other by index.
+
+const MAX_LEVEL: usize = 32;
+const HEAD: usize = 0;
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct Level {
+    forward: usize,
+    span: usize, // the number of nodes skipped by the forward link
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    member: String,
+    score: f64,
+    backward: usize,
+    levels: Vec<Level>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SkipList {
+    nodes: Vec<Node>, // nodes[HEAD] is the header, it holds no element
+    free: Vec<usize>,
+    level: usize,
+    len: usize,
+    tail: usize,
+}
+
+/// Iterates the elements from a node, forward or backward.
+pub(crate) struct Iter<'a> {
+    list: &'a SkipList,
+    next: usize,
+    rev: bool,
+}
+
+impl Node {
+    fn new(member: String, score: f64, level: usize) -> Self {
+        Node {
+            member,
+            score,
+            backward: NIL,
+            levels: vec![
+                Level {
+                    forward: NIL,
+                    span: 0
+                };
+                level
+            ],
+        }
+    }
+
+    /// Whether the node is ordered before (score, member).
+    fn before(&self, score: f64, member: &str) -> bool {
+        self.score < score || (self.score == score && self.member.as_str() < member)
+    }
+}
+
+fn random_level() -> usize {
+    // every level has 1/4 of the nodes of the level below
+    let bits = random_u64() | 1 << (2 * (MAX_LEVEL - 1));
+    bits.trailing_zeros() as usize / 2 + 1
+}
+
+impl SkipList {
+    pub(crate) fn new() -> Self {
+        SkipList {
+            nodes: vec![Node::new(String::new(), 0.0, MAX_LEVEL)],
+            free: Vec::new(),
+            level: 1,
+            len: 0,
+            tail: NIL,
+        }
+    }
+
+    fn forward(&self, node: usize, level: usize) -> usize {
+        self.nodes[node].levels[level].forward
+    }
+
+    fn span(&self, node: usize, level: usize) -> usize {
+        self.nodes[node].levels[level].span
+    }
+
+    fn alloc(&mut self, node: Node) -> usize {
+        match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Insert a new element, the member must not be in the list.
+    pub(crate) fn insert(&mut self, score: f64, member: String) {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut rank = [0; MAX_LEVEL];
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            rank[i] = if i == self.level - 1 { 0 } else { rank[i + 1] };
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || !self.nodes[next].before(score, &member) {
+                    break;
+                }
+                rank[i] += self.span(x, i);
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let level = random_level();
+        if level > self.level {
+            for i in self.level..level {
+                rank[i] = 0;
+                update[i] = HEAD;
+                self.nodes[HEAD].levels[i].span = self.len;
+            }
+            self.level = level;
+        }
+
+        let node = self.alloc(Node::new(member, score, level));
+        for i in 0..level {
+            let prev = update[i];
+            let skipped = rank[0] - rank[i];
+            self.nodes[node].levels[i] = Level {
+                forward: self.forward(prev, i),
+                span: self.span(prev, i) - skipped,
+            };
+            self.nodes[prev].levels[i] = Level {
+                forward: node,
+                span: skipped + 1,
+            };
+        }
+        for (i, prev) in update.iter().enumerate().take(self.level).skip(level) {
+            self.nodes[*prev].levels[i].span += 1;
+        }
+
+        self.nodes[node].backward = if update[0] == HEAD { NIL } else { update[0] };
+        match self.forward(node, 0) {
+            NIL => self.tail = node,
+            next => self.nodes[next].backward = node,
+        }
+        self.len += 1;
+    }
+
+    /// Remove the element, returns false if it is not in the list.
+    pub(crate) fn remove(&mut self, score: f64, member: &str) -> bool {
+        let mut update = [HEAD; MAX_LEVEL];
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || !self.nodes[next].before(score, member) {
+                    break;
+                }
+                x = next;
+            }
+            update[i] = x;
+        }
+
+        let node = self.forward(x, 0);
+        if node == NIL || self.nodes[node].score != score || self.nodes[node].member != member {
+            return false;
+        }
+
+        for (i, prev) in update.iter().enumerate().take(self.level) {
+            if self.forward(*prev, i) == node {
+                let span = self.span(*prev, i) + self.span(node, i) - 1;
+                self.nodes[*prev].levels[i] = Level {
+                    forward: self.forward(node, i),
+                    span,
+                };
+            } else {
+                self.nodes[*prev].levels[i].span -= 1;
+            }
+        }
+        let backward = self.nodes[node].backward;
+        match self.forward(node, 0) {
+            NIL => self.tail = backward,
+            next => self.nodes[next].backward = backward,
+        }
+        while self.level > 1 && self.forward(HEAD, self.level - 1) == NIL {
+            self.level -= 1;
+        }
+
+        // release the memory of the member, the slot is reused by the next insert
+        self.nodes[node] = Node::new(String::new(), 0.0, 0);
+        self.free.push(node);
+        self.len -= 1;
+        true
+    }
+
+    /// The 0 based rank of the element, None if it is not in the list.
+    pub(crate) fn rank(&self, score: f64, member: &str) -> Option<usize> {
+        let mut rank = 0;
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || !self.nodes[next].before(score, member) {
+                    break;
+                }
+                rank += self.span(x, i);
+                x = next;
+            }
+        }
+        let node = self.forward(x, 0);
+        (node != NIL && self.nodes[node].score == score && self.nodes[node].member == member)
+            .then_some(rank)
+    }
+
+    /// The node at the 0 based rank.
+    fn node_by_rank(&self, rank: usize) -> usize {
+        if rank >= self.len {
+            return NIL;
+        }
+        let target = rank + 1;
+        let mut traversed = 0;
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || traversed + self.span(x, i) > target {
+                    break;
+                }
+                traversed += self.span(x, i);
+                x = next;
+            }
+            if traversed == target {
+                return x;
+            }
+        }
+        NIL
+    }
+
+    /// The elements from the 0 based rank, in ascending order or in descending
+    /// order if `rev`. The rank is always counted in ascending order.
+    pub(crate) fn iter_from_rank(&self, rank: usize, rev: bool) -> Iter<'_> {
+        Iter {
+            list: self,
+            next: self.node_by_rank(rank),
+            rev,
+        }
+    }
+
+    /// The elements in ascending order, from the first one where `past_min` is true.
+    /// `past_min` must be monotonic along the order of the list.
+    pub(crate) fn seek_first(&self, past_min: impl Fn(f64, &str) -> bool) -> Iter<'_> {
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || past_min(self.nodes[next].score, &self.nodes[next].member) {
+                    break;
+                }
+                x = next;
+            }
+        }
+        Iter {
+            list: self,
+            next: self.forward(x, 0),
+            rev: false,
+        }
+    }
+
+    /// The elements in descending order, from the last one where `before_max` is true.
+    /// `before_max` must be monotonic along the order of the list.
+    pub(crate) fn seek_last(&self, before_max: impl Fn(f64, &str) -> bool) -> Iter<'_> {
+        let mut x = HEAD;
+        for i in (0..self.level).rev() {
+            loop {
+                let next = self.forward(x, i);
+                if next == NIL || !before_max(self.nodes[next].score, &self.nodes[next].member) {
+                    break;
+                }
+                x = next;
+            }
+        }
+        Iter {
+            list: self,
+            next: if x == HEAD { NIL } else { x },
+            rev: true,
+        }
+    }
+
+    /// All the elements, in ascending order or in descending order if `rev`.
+    pub(crate) fn iter(&self, rev: bool) -> Iter<'_> {
+        Iter {
+            list: self,
+            next: if rev {
+                self.tail
+            } else {
+                self.forward(HEAD, 0)
+            },
+            rev,
+        }
+    }
+}
+
+impl Default for SkipList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == NIL {
+            return None;
+        }
+        let node = &self.list.nodes[self.next];
+        self.next = match self.rev {
+            true => node.backward,
+            false => node.levels[0].forward,
+        };
+        Some((node.member.as_str(), node.score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skiplist_rank_and_order() {
+        let mut list = SkipList::new();
+        for i in (0..1000).rev() {
+            list.insert((i / 2) as f64, format!("m{:04}", i));
+        }
+        assert_eq!(list.len, 1000);
+        assert_eq!(list.rank(0.0, "m0000"), Some(0));
+        assert_eq!(list.rank(250.0, "m0501"), Some(501));
+        assert_eq!(list.rank(250.0, "nope"), None);
+
+        for i in (0..1000).step_by(2) {
+            assert!(list.remove((i / 2) as f64, &format!("m{:04}", i)));
+        }
+        assert!(!list.remove(0.0, "m0000"));
+        assert_eq!(list.len, 500);
+        assert_eq!(list.rank(250.0, "m0501"), Some(250));
+        assert_eq!(
+            list.iter_from_rank(250, false).next(),
+            Some(("m0501", 250.0))
+        );
+
+        let all = list
+            .iter(false)
+            .map(|(m, _)| m.to_string())
+            .collect::<Vec<_>>();
+        let expected = (1..1000)
+            .step_by(2)
+            .map(|i| format!("m{:04}", i))
+            .collect::<Vec<_>>();
+        assert_eq!(all, expected);
+        assert_eq!(list.iter(true).count(), 500);
+    }
+
+    #[test]
+    fn test_skiplist_seek() {
+        let mut list = SkipList::new();
+        for (score, member) in [(1.0, "a"), (2.0, "b"), (2.0, "c"), (3.0, "d")] {
+            list.insert(score, member.to_string());
+        }
+        let first = list.seek_first(|score, _| score >= 2.0).next();
+        assert_eq!(first, Some(("b", 2.0)));
+        let last = list.seek_last(|score, _| score <= 2.0).next();
+        assert_eq!(last, Some(("c", 2.0)));
+        assert_eq!(list.seek_first(|score, _| score > 3.0).next(), None);
+        assert_eq!(list.seek_last(|score, _| score < 1.0).next(), None);
+    }
+}
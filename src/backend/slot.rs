@@ -0,0 +1,45 @@
+// Keys and shard channels are partitioned into 16384 slots like redis cluster:
+// the slot is CRC16 of the key modulo 16384, and if the key contains a hash tag,
+// e.g. `{user1000}.following`, only the tag is hashed, so related keys can be
+// kept in the same slot.
+
+pub const SLOT_COUNT: u16 = 16384;
+
+/// CRC16-CCITT (XMODEM), polynomial 0x1021 with initial value 0.
+pub fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, &b| {
+        let mut crc = crc ^ ((b as u16) << 8);
+        for _ in 0..8 {
+            crc = match crc & 0x8000 {
+                0 => crc << 1,
+                _ => (crc << 1) ^ 0x1021,
+            };
+        }
+        crc
+    })
+}
+
+/// The slot of the key, only the hash tag is hashed if there is a non-empty one.
+pub fn key_slot(key: &[u8]) -> u16 {
+    let tag = key.iter().position(|&b| b == b'{').and_then(|start| {
+        let end = key[start + 1..].iter().position(|&b| b == b'}')?;
+        (end > 0).then(|| &key[start + 1..start + 1 + end])
+    });
+    crc16(tag.unwrap_or(key)) % SLOT_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_slot() {
+        // the check value of CRC16/XMODEM
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"user1000"));
+        // an empty tag hashes the whole key
+        assert_eq!(key_slot(b"foo{}{bar}"), crc16(b"foo{}{bar}") % SLOT_COUNT);
+        assert_eq!(key_slot(b"foo{{bar}}zap"), key_slot(b"{bar"));
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::Backend;
+
+/// The counters of the server, updated as the commands are executed.
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    unknown_commands: AtomicU64,
+}
+
+/// A snapshot of the server, as reported by INFO.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerStats {
+    pub connected_clients: usize,
+    pub unknown_commands: u64,
+    pub keys: usize,
+    pub expires: usize, // the keys with a TTL
+}
+
+impl Backend {
+    /// Count a command which is not implemented.
+    pub fn incr_unknown_commands(&self) {
+        self.counters
+            .unknown_commands
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> ServerStats {
+        ServerStats {
+            connected_clients: self.sessions.len(),
+            unknown_commands: self.counters.unknown_commands.load(Ordering::Relaxed),
+            keys: self.db.len(),
+            expires: self.expires.len(),
+        }
+    }
+}
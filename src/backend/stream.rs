@@ -0,0 +1,540 @@
+use std::{collections::BTreeMap, fmt, ops::Bound, time::Duration};
+
+use super::{now_ms, stream_group::ConsumerGroup, Backend, BackendError};
+
+// A stream is an append only log of entries ordered by their IDs, `ms-seq`
+// where ms is the unix time in milliseconds of the entry and seq tells apart
+// the entries of the same millisecond.
+
+/// The entries are trimmed by whole nodes with the `~` option, like the radix
+/// tree nodes of redis (stream-node-max-entries).
+const STREAM_NODE_MAX_ENTRIES: usize = 100;
+
+/// The ID of a stream entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StreamId {
+    pub ms: u64,
+    pub seq: u64,
+}
+
+/// The field-value pairs of an entry, in the order they were added.
+pub type StreamFields = Vec<(Vec<u8>, Vec<u8>)>;
+
+pub type StreamEntry = (StreamId, StreamFields);
+
+/// The ID argument of XADD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XAddId {
+    Auto,         // *
+    AutoSeq(u64), // ms-*
+    Explicit(StreamId),
+}
+
+/// The ID argument of XREAD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XReadId {
+    After(StreamId),
+    New,       // $, the entries added after the call
+    LastEntry, // +, the last entry
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimStrategy {
+    MaxLen(usize),
+    MinId(StreamId),
+}
+
+/// The trimming options of XADD and XTRIM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamTrim {
+    pub strategy: TrimStrategy,
+    pub approx: bool,         // `~`, trim whole nodes only
+    pub limit: Option<usize>, // the maximum number of entries to trim with `~`, 0 for no limit
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stream {
+    pub(super) entries: BTreeMap<StreamId, StreamFields>,
+    pub(super) last_id: StreamId, // kept when the last entry is deleted, the IDs never go back
+    pub(super) max_deleted_id: StreamId, // the greatest ID deleted by XDEL
+    pub(super) entries_added: u64, // all the entries ever added
+    pub(super) groups: BTreeMap<String, ConsumerGroup>,
+}
+
+impl StreamId {
+    pub const MIN: StreamId = StreamId { ms: 0, seq: 0 };
+    pub const MAX: StreamId = StreamId {
+        ms: u64::MAX,
+        seq: u64::MAX,
+    };
+
+    pub fn new(ms: u64, seq: u64) -> Self {
+        StreamId { ms, seq }
+    }
+
+    /// The smallest ID greater than this one.
+    pub fn next(self) -> Option<StreamId> {
+        match (self.ms, self.seq) {
+            (_, seq) if seq < u64::MAX => Some(StreamId::new(self.ms, seq + 1)),
+            (ms, _) if ms < u64::MAX => Some(StreamId::new(ms + 1, 0)),
+            _ => None,
+        }
+    }
+
+    /// The greatest ID smaller than this one.
+    pub fn prev(self) -> Option<StreamId> {
+        match (self.ms, self.seq) {
+            (_, seq) if seq > 0 => Some(StreamId::new(self.ms, seq - 1)),
+            (ms, _) if ms > 0 => Some(StreamId::new(ms - 1, u64::MAX)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.ms, self.seq)
+    }
+}
+
+impl Stream {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn last_id(&self) -> StreamId {
+        self.last_id
+    }
+
+    pub fn first_entry(&self) -> Option<StreamEntry> {
+        self.entries
+            .first_key_value()
+            .map(|(id, f)| (*id, f.clone()))
+    }
+
+    pub fn last_entry(&self) -> Option<StreamEntry> {
+        self.entries
+            .last_key_value()
+            .map(|(id, f)| (*id, f.clone()))
+    }
+
+    pub fn get(&self, id: &StreamId) -> Option<&StreamFields> {
+        self.entries.get(id)
+    }
+
+    /// The ID of the new entry, greater than any ID of the stream.
+    fn next_id(&self, id: XAddId) -> Result<StreamId, BackendError> {
+        let last = self.last_id;
+        match id {
+            XAddId::Auto => match now_ms() as u64 {
+                ms if ms > last.ms => Ok(StreamId::new(ms, 0)),
+                _ => last.next().ok_or(BackendError::StreamExhausted),
+            },
+            XAddId::AutoSeq(ms) if ms > last.ms => Ok(StreamId::new(ms, 0)),
+            XAddId::AutoSeq(ms) if ms == last.ms && last.seq < u64::MAX => {
+                Ok(StreamId::new(ms, last.seq + 1))
+            }
+            XAddId::Explicit(id) if id > last => Ok(id),
+            _ => Err(BackendError::StreamIdTooSmall),
+        }
+    }
+
+    /// Append an entry, returns its ID.
+    pub fn add(&mut self, id: XAddId, fields: StreamFields) -> Result<StreamId, BackendError> {
+        if id == XAddId::Explicit(StreamId::MIN) {
+            return Err(BackendError::StreamIdZero);
+        }
+        let id = self.next_id(id)?;
+        self.entries.insert(id, fields);
+        self.last_id = id;
+        self.entries_added += 1;
+        Ok(id)
+    }
+
+    /// The entries between the bounds, in reverse order if `rev`.
+    pub fn range(
+        &self,
+        start: Bound<StreamId>,
+        end: Bound<StreamId>,
+        count: Option<usize>,
+        rev: bool,
+    ) -> Vec<StreamEntry> {
+        if is_empty_range(start, end) {
+            return Vec::new();
+        }
+
+        let range = self.entries.range((start, end));
+        let count = count.unwrap_or(usize::MAX);
+        let clone = |(id, fields): (&StreamId, &StreamFields)| (*id, fields.clone());
+        match rev {
+            true => range.rev().take(count).map(clone).collect(),
+            false => range.take(count).map(clone).collect(),
+        }
+    }
+
+    /// Delete the entries, returns the number of the deleted ones.
+    /// The pending entries of the groups are kept, see XCLAIM.
+    pub fn delete(&mut self, ids: &[StreamId]) -> usize {
+        let mut deleted = 0;
+        for id in ids {
+            if self.entries.remove(id).is_some() {
+                self.max_deleted_id = self.max_deleted_id.max(*id);
+                deleted += 1;
+            }
+        }
+        deleted
+    }
+
+    /// Evict the oldest entries, returns the number of the evicted ones.
+    pub fn trim(&mut self, trim: &StreamTrim) -> usize {
+        let mut evict = match trim.strategy {
+            TrimStrategy::MaxLen(len) => self.entries.len().saturating_sub(len),
+            TrimStrategy::MinId(id) => self.entries.range(..id).count(),
+        };
+        if trim.approx {
+            let limit = match trim.limit {
+                Some(0) => usize::MAX,
+                Some(limit) => limit,
+                None => STREAM_NODE_MAX_ENTRIES * 100,
+            };
+            evict = evict.min(limit) / STREAM_NODE_MAX_ENTRIES * STREAM_NODE_MAX_ENTRIES;
+        }
+        for _ in 0..evict {
+            self.entries.pop_first();
+        }
+        evict
+    }
+}
+
+/// Whether the range contains no IDs, BTreeMap::range panics on some of them.
+pub(super) fn is_empty_range(start: Bound<StreamId>, end: Bound<StreamId>) -> bool {
+    match (start, end) {
+        (Bound::Included(s), Bound::Included(e)) => s > e,
+        (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => {
+            s >= e
+        }
+        _ => false,
+    }
+}
+
+impl Backend {
+    /// Append an entry to the stream and trim it, returns the ID of the entry.
+    /// With `nomkstream` a missing stream is not created and None is returned.
+    pub fn xadd(
+        &self,
+        key: &str,
+        id: XAddId,
+        fields: StreamFields,
+        nomkstream: bool,
+        trim: Option<&StreamTrim>,
+    ) -> Result<Option<StreamId>, BackendError> {
+        // don't create the stream for an invalid ID
+        if id == XAddId::Explicit(StreamId::MIN) {
+            return Err(BackendError::StreamIdZero);
+        }
+        let add = |stream: &mut Stream| {
+            let id = stream.add(id, fields)?;
+            if let Some(trim) = trim {
+                stream.trim(trim);
+            }
+            Ok(id)
+        };
+        let id = match nomkstream {
+            true => self.update(key, add, Result::is_ok)?.transpose()?,
+            false => Some(self.write(key, add, Result::is_ok)??),
+        };
+        if id.is_some() {
+            self.signal_ready(key);
+        }
+        Ok(id)
+    }
+
+    /// The entries between the bounds, see `Stream::range`.
+    pub fn xrange(
+        &self,
+        key: &str,
+        start: Bound<StreamId>,
+        end: Bound<StreamId>,
+        count: Option<usize>,
+        rev: bool,
+    ) -> Result<Vec<StreamEntry>, BackendError> {
+        let ret = self.read(key, |stream: &Stream| stream.range(start, end, count, rev))?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    pub fn xlen(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |stream: &Stream| stream.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    pub fn xdel(&self, key: &str, ids: &[StreamId]) -> Result<i64, BackendError> {
+        let ret = self.update(
+            key,
+            |stream: &mut Stream| stream.delete(ids) as i64,
+            |&n| n > 0,
+        )?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    pub fn xtrim(&self, key: &str, trim: &StreamTrim) -> Result<i64, BackendError> {
+        let ret = self.update(
+            key,
+            |stream: &mut Stream| stream.trim(trim) as i64,
+            |&n| n > 0,
+        )?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// The entries after the IDs of each stream, only the streams with entries are returned.
+    pub fn xread(
+        &self,
+        keys: &[String],
+        ids: &[XReadId],
+        count: Option<usize>,
+    ) -> Result<Vec<(String, Vec<StreamEntry>)>, BackendError> {
+        let ids = self.resolve_read_ids(keys, ids)?;
+        self.read_after(keys, &ids, count)
+    }
+
+    /// XREAD, or wait until an entry is added to one of the streams.
+    /// `timeout` None waits forever, returns None on timeout.
+    pub async fn xread_block(
+        &self,
+        keys: &[String],
+        ids: &[XReadId],
+        count: Option<usize>,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Vec<(String, Vec<StreamEntry>)>>, BackendError> {
+        // `$` means the entries added from now on, even if the wait is woken up later
+        let ids = self.resolve_read_ids(keys, ids)?;
+        self.block_read(keys, timeout, || {
+            let streams = self.read_after(keys, &ids, count)?;
+            Ok((!streams.is_empty()).then_some(streams))
+        })
+        .await
+    }
+
+    /// The IDs to read after.
+    fn resolve_read_ids(
+        &self,
+        keys: &[String],
+        ids: &[XReadId],
+    ) -> Result<Vec<StreamId>, BackendError> {
+        keys.iter()
+            .zip(ids)
+            .map(|(key, id)| {
+                let after = match id {
+                    XReadId::After(id) => Some(*id),
+                    XReadId::New => self.read(key, |s: &Stream| s.last_id)?,
+                    XReadId::LastEntry => {
+                        self.read(key, |s: &Stream| match s.entries.last_key_value() {
+                            Some((id, _)) => id.prev().unwrap_or_default(),
+                            None => s.last_id,
+                        })?
+                    }
+                };
+                Ok(after.unwrap_or_default())
+            })
+            .collect()
+    }
+
+    fn read_after(
+        &self,
+        keys: &[String],
+        ids: &[StreamId],
+        count: Option<usize>,
+    ) -> Result<Vec<(String, Vec<StreamEntry>)>, BackendError> {
+        let mut streams = Vec::new();
+        for (key, id) in keys.iter().zip(ids) {
+            let entries = self.xrange(
+                key,
+                Bound::Excluded(*id),
+                Bound::Included(StreamId::MAX),
+                count,
+                false,
+            )?;
+            if !entries.is_empty() {
+                streams.push((key.clone(), entries));
+            }
+        }
+        Ok(streams)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> StreamFields {
+        pairs
+            .iter()
+            .map(|(f, v)| (f.as_bytes().to_vec(), v.as_bytes().to_vec()))
+            .collect()
+    }
+
+    fn ids(entries: &[StreamEntry]) -> Vec<String> {
+        entries.iter().map(|(id, _)| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_xadd_ids() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let add = |id| backend.xadd("s", id, fields(&[("a", "1")]), false, None);
+        assert_eq!(
+            add(XAddId::Explicit(StreamId::MIN)),
+            Err(BackendError::StreamIdZero)
+        );
+        assert!(!backend.exists("s"));
+
+        assert_eq!(add(XAddId::AutoSeq(0))?, Some(StreamId::new(0, 1)));
+        assert_eq!(
+            add(XAddId::Explicit(StreamId::new(5, 5)))?,
+            Some(StreamId::new(5, 5))
+        );
+        assert_eq!(add(XAddId::AutoSeq(5))?, Some(StreamId::new(5, 6)));
+        assert_eq!(
+            add(XAddId::Explicit(StreamId::new(5, 6))),
+            Err(BackendError::StreamIdTooSmall)
+        );
+        assert_eq!(add(XAddId::AutoSeq(4)), Err(BackendError::StreamIdTooSmall));
+        let id = add(XAddId::Auto)?.unwrap_or_default();
+        assert!(id.ms > 5 && id.seq == 0);
+        assert_eq!(backend.xlen("s")?, 4);
+
+        assert_eq!(
+            backend.xadd("none", XAddId::Auto, fields(&[("a", "1")]), true, None),
+            Ok(None)
+        );
+        assert!(!backend.exists("none"));
+        assert_eq!(backend.key_type("s"), "stream");
+        Ok(())
+    }
+
+    #[test]
+    fn test_xrange_xdel_xtrim() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        for i in 1..=5 {
+            let id = XAddId::Explicit(StreamId::new(i, 0));
+            backend.xadd("s", id, fields(&[("i", &i.to_string())]), false, None)?;
+        }
+        let (min, max) = (
+            Bound::Included(StreamId::MIN),
+            Bound::Included(StreamId::MAX),
+        );
+        assert_eq!(
+            ids(&backend.xrange("s", min, max, Some(2), false)?),
+            ["1-0", "2-0"]
+        );
+        assert_eq!(
+            ids(&backend.xrange("s", min, max, Some(2), true)?),
+            ["5-0", "4-0"]
+        );
+        let start = Bound::Excluded(StreamId::new(2, 0));
+        let end = Bound::Included(StreamId::new(4, u64::MAX));
+        assert_eq!(
+            ids(&backend.xrange("s", start, end, None, false)?),
+            ["3-0", "4-0"]
+        );
+        assert!(backend.xrange("s", end, start, None, false)?.is_empty());
+
+        assert_eq!(
+            backend.xdel("s", &[StreamId::new(3, 0), StreamId::new(9, 0)])?,
+            1
+        );
+        let trim = StreamTrim {
+            strategy: TrimStrategy::MaxLen(2),
+            approx: false,
+            limit: None,
+        };
+        assert_eq!(backend.xtrim("s", &trim)?, 2);
+        assert_eq!(
+            ids(&backend.xrange("s", min, max, None, false)?),
+            ["4-0", "5-0"]
+        );
+
+        // all the entries are deleted, but the stream and its last ID remain
+        let trim = StreamTrim {
+            strategy: TrimStrategy::MinId(StreamId::new(6, 0)),
+            approx: false,
+            limit: None,
+        };
+        assert_eq!(backend.xtrim("s", &trim)?, 2);
+        assert_eq!(backend.xlen("s")?, 0);
+        assert_eq!(
+            backend.xadd(
+                "s",
+                XAddId::Explicit(StreamId::new(5, 0)),
+                fields(&[]),
+                false,
+                None
+            ),
+            Err(BackendError::StreamIdTooSmall)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_trim_approx() {
+        let mut stream = Stream::default();
+        for i in 1..=250 {
+            let _ = stream.add(XAddId::Explicit(StreamId::new(i, 0)), fields(&[]));
+        }
+        let mut trim = StreamTrim {
+            strategy: TrimStrategy::MaxLen(10),
+            approx: true,
+            limit: None,
+        };
+        assert_eq!(stream.trim(&trim), 200);
+        assert_eq!(stream.len(), 50);
+        trim.limit = Some(99);
+        assert_eq!(stream.trim(&trim), 0);
+    }
+
+    #[tokio::test]
+    async fn test_xread_block() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        backend.xadd(
+            "s",
+            XAddId::Explicit(StreamId::new(1, 0)),
+            fields(&[]),
+            false,
+            None,
+        )?;
+        let keys = ["s".to_string()];
+        let read = backend.xread(&keys, &[XReadId::After(StreamId::MIN)], None)?;
+        assert_eq!(ids(&read[0].1), ["1-0"]);
+        assert!(backend.xread(&keys, &[XReadId::New], None)?.is_empty());
+        let read = backend.xread(&keys, &[XReadId::LastEntry], None)?;
+        assert_eq!(ids(&read[0].1), ["1-0"]);
+
+        let timeout = Some(Duration::from_millis(10));
+        assert_eq!(
+            backend
+                .xread_block(&keys, &[XReadId::New], None, timeout)
+                .await?,
+            None
+        );
+
+        let cloned = backend.clone();
+        let handle = tokio::spawn(async move {
+            let keys = ["s".to_string()];
+            cloned.xread_block(&keys, &[XReadId::New], None, None).await
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        backend.xadd(
+            "s",
+            XAddId::Explicit(StreamId::new(2, 0)),
+            fields(&[]),
+            false,
+            None,
+        )?;
+        let read = handle.await.unwrap_or(Ok(None))?.unwrap_or_default();
+        assert_eq!(ids(&read[0].1), ["2-0"]);
+        assert!(backend.blocked_is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,797 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+    time::Duration,
+};
+
+use super::{
+    now_ms, stream::is_empty_range, Backend, BackendError, Stream, StreamEntry, StreamFields,
+    StreamId,
+};
+
+// A consumer group delivers every entry of the stream to one of its consumers.
+// The delivered entries stay in the pending entries list (PEL) of the group
+// until they are acknowledged, so the entries of a dead consumer can be claimed
+// by another one.
+
+/// A consumer group of a stream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsumerGroup {
+    last_id: StreamId, // the last delivered ID
+    pel: BTreeMap<StreamId, PendingEntry>,
+    consumers: BTreeMap<String, Consumer>,
+}
+
+/// An entry delivered but not acknowledged yet.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingEntry {
+    consumer: String,
+    delivery_time: i64, // unix time in milliseconds
+    delivery_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Consumer {
+    seen_time: i64,           // the last attempted interaction
+    active_time: Option<i64>, // the last successful interaction
+    pending: BTreeSet<StreamId>,
+}
+
+/// An entry read by XREADGROUP, without fields if it was deleted while pending.
+pub type GroupEntry = (StreamId, Option<StreamFields>);
+
+/// The summary form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSummary {
+    pub count: usize,
+    pub range: Option<(StreamId, StreamId)>, // the smallest and the greatest pending IDs
+    pub consumers: Vec<(String, usize)>,
+}
+
+/// The range of the extended form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRange {
+    pub start: Bound<StreamId>,
+    pub end: Bound<StreamId>,
+    pub count: usize,
+    pub consumer: Option<String>,
+    pub min_idle: Option<i64>, // in milliseconds
+}
+
+/// A pending entry, in the extended form of XPENDING.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingInfo {
+    pub id: StreamId,
+    pub consumer: String,
+    pub idle: i64,
+    pub delivery_count: u64,
+}
+
+/// The options of XCLAIM.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClaimOptions {
+    pub idle: Option<i64>,        // set the idle time of the claimed entries
+    pub time: Option<i64>,        // set the delivery time, unix time in milliseconds
+    pub retry_count: Option<u64>, // set the delivery count
+    pub force: bool,              // claim the entries even if they are not pending
+    pub just_id: bool,            // don't increment the delivery count
+    pub last_id: Option<StreamId>,
+}
+
+/// The reply of XINFO STREAM.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamInfo {
+    pub length: usize,
+    pub last_generated_id: StreamId,
+    pub max_deleted_id: StreamId,
+    pub entries_added: u64,
+    pub first_id: StreamId,
+    pub groups: usize,
+    pub first_entry: Option<StreamEntry>,
+    pub last_entry: Option<StreamEntry>,
+}
+
+/// A group in the reply of XINFO GROUPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupInfo {
+    pub name: String,
+    pub consumers: usize,
+    pub pending: usize,
+    pub last_delivered_id: StreamId,
+    pub entries_read: u64,
+    pub lag: u64,
+}
+
+/// A consumer in the reply of XINFO CONSUMERS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerInfo {
+    pub name: String,
+    pub pending: usize,
+    pub idle: i64,             // since the last attempted interaction
+    pub inactive: Option<i64>, // since the last successful interaction
+}
+
+impl Consumer {
+    fn new(now: i64) -> Self {
+        Consumer {
+            seen_time: now,
+            active_time: None,
+            pending: BTreeSet::new(),
+        }
+    }
+}
+
+impl ConsumerGroup {
+    fn new(last_id: StreamId) -> Self {
+        ConsumerGroup {
+            last_id,
+            ..Default::default()
+        }
+    }
+
+    /// The consumer, created if it does not exist.
+    fn consumer(&mut self, name: &str, now: i64) -> &mut Consumer {
+        let consumer = self
+            .consumers
+            .entry(name.to_string())
+            .or_insert_with(|| Consumer::new(now));
+        consumer.seen_time = now;
+        consumer
+    }
+
+    /// Make the entry pending for the consumer, removing it from its previous owner.
+    fn assign(&mut self, id: StreamId, consumer: &str, delivery_time: i64) -> &mut PendingEntry {
+        let pending = self.pel.entry(id).or_insert_with(|| PendingEntry {
+            consumer: consumer.to_string(),
+            delivery_time,
+            delivery_count: 0,
+        });
+        if pending.consumer != consumer {
+            if let Some(owner) = self.consumers.get_mut(&pending.consumer) {
+                owner.pending.remove(&id);
+            }
+            pending.consumer = consumer.to_string();
+        }
+        pending.delivery_time = delivery_time;
+        if let Some(owner) = self.consumers.get_mut(consumer) {
+            owner.pending.insert(id);
+        }
+        pending
+    }
+
+    /// Remove the entry from the PEL, returns whether it was pending.
+    fn ack(&mut self, id: &StreamId) -> bool {
+        let Some(pending) = self.pel.remove(id) else {
+            return false;
+        };
+        if let Some(owner) = self.consumers.get_mut(&pending.consumer) {
+            owner.pending.remove(id);
+        }
+        true
+    }
+
+    /// Deliver the entries after the last delivered ID, or with `after`, the
+    /// pending entries of the consumer after it again.
+    fn read(
+        &mut self,
+        entries: &BTreeMap<StreamId, StreamFields>,
+        consumer: &str,
+        after: Option<StreamId>,
+        count: Option<usize>,
+        noack: bool,
+    ) -> Vec<GroupEntry> {
+        let now = now_ms();
+        let count = count.unwrap_or(usize::MAX);
+        let pending = &self.consumer(consumer, now).pending;
+        let read = match after {
+            None => entries
+                .range((Bound::Excluded(self.last_id), Bound::Unbounded))
+                .take(count)
+                .map(|(id, fields)| (*id, Some(fields.clone())))
+                .collect::<Vec<_>>(),
+            Some(after) => pending
+                .range((Bound::Excluded(after), Bound::Unbounded))
+                .take(count)
+                .map(|id| (*id, entries.get(id).cloned()))
+                .collect(),
+        };
+
+        for (id, _) in &read {
+            if after.is_none() {
+                self.last_id = *id;
+                if noack {
+                    continue;
+                }
+                self.assign(*id, consumer, now).delivery_count = 1;
+            } else if let Some(pending) = self.pel.get_mut(id) {
+                pending.delivery_time = now;
+                pending.delivery_count += 1;
+            }
+        }
+        if !read.is_empty() {
+            self.consumer(consumer, now).active_time = Some(now);
+        }
+        read
+    }
+
+    /// Claim the pending entry for the consumer if it has been idle long enough.
+    /// A deleted entry is removed from the PEL. Returns the entry if claimed.
+    fn claim(
+        &mut self,
+        entries: &BTreeMap<StreamId, StreamFields>,
+        id: StreamId,
+        consumer: &str,
+        min_idle: i64,
+        opts: &ClaimOptions,
+    ) -> Option<StreamEntry> {
+        let now = now_ms();
+        let Some(fields) = entries.get(&id) else {
+            self.ack(&id);
+            return None;
+        };
+        match self.pel.get(&id) {
+            None if !opts.force => return None,
+            Some(pending) if now - pending.delivery_time < min_idle => return None,
+            _ => {}
+        }
+
+        let delivery_time = match (opts.time, opts.idle) {
+            (Some(time), _) => time,
+            (_, Some(idle)) => now - idle,
+            _ => now,
+        };
+        self.consumer(consumer, now).active_time = Some(now);
+        let pending = self.assign(id, consumer, delivery_time);
+        match opts.retry_count {
+            Some(count) => pending.delivery_count = count,
+            None if !opts.just_id => pending.delivery_count += 1,
+            None => {}
+        }
+        Some((id, fields.clone()))
+    }
+}
+
+impl Backend {
+    /// Run `f` on the group of the stream, NOGROUP if there is no such stream or group.
+    /// `modified` tells from the result whether `f` changed the group, see `write`.
+    fn with_group<R>(
+        &self,
+        key: &str,
+        group: &str,
+        f: impl FnOnce(&BTreeMap<StreamId, StreamFields>, &mut ConsumerGroup) -> R,
+        modified: impl FnOnce(&R) -> bool,
+    ) -> Result<R, BackendError> {
+        let ret = self.update(
+            key,
+            |stream: &mut Stream| {
+                let consumer_group = stream.groups.get_mut(group)?;
+                Some(f(&stream.entries, consumer_group))
+            },
+            |ret: &Option<R>| ret.as_ref().is_some_and(modified),
+        )?;
+        ret.flatten()
+            .ok_or_else(|| BackendError::NoGroup(key.to_string(), group.to_string()))
+    }
+
+    /// Create a group delivering the entries after `id`, None for the last ID of the stream.
+    pub fn xgroup_create(
+        &self,
+        key: &str,
+        group: &str,
+        id: Option<StreamId>,
+        mkstream: bool,
+    ) -> Result<(), BackendError> {
+        let create = |stream: &mut Stream| {
+            if stream.groups.contains_key(group) {
+                return Err(BackendError::BusyGroup);
+            }
+            let last_id = id.unwrap_or(stream.last_id);
+            stream
+                .groups
+                .insert(group.to_string(), ConsumerGroup::new(last_id));
+            Ok(())
+        };
+        match mkstream {
+            true => self.write(key, create, Result::is_ok)?,
+            false => self
+                .update(key, create, Result::is_ok)?
+                .ok_or(BackendError::NoStreamForGroup)?,
+        }
+    }
+
+    /// Set the last delivered ID of the group, None for the last ID of the stream.
+    pub fn xgroup_setid(
+        &self,
+        key: &str,
+        group: &str,
+        id: Option<StreamId>,
+    ) -> Result<(), BackendError> {
+        let ret = self.update(
+            key,
+            |stream: &mut Stream| {
+                let last_id = id.unwrap_or(stream.last_id);
+                stream.groups.get_mut(group).map(|g| g.last_id = last_id)
+            },
+            Option::is_some,
+        )?;
+        ret.flatten()
+            .ok_or_else(|| BackendError::NoGroup(key.to_string(), group.to_string()))
+    }
+
+    pub fn xgroup_destroy(&self, key: &str, group: &str) -> Result<bool, BackendError> {
+        let ret = self.update(
+            key,
+            |stream: &mut Stream| stream.groups.remove(group).is_some(),
+            |&destroyed| destroyed,
+        )?;
+        let destroyed = ret.ok_or(BackendError::NoStreamForGroup)?;
+        if destroyed {
+            // the blocked readers of the group get an error
+            self.signal_ready(key);
+        }
+        Ok(destroyed)
+    }
+
+    pub fn xgroup_createconsumer(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+    ) -> Result<bool, BackendError> {
+        self.with_group(
+            key,
+            group,
+            |_, group| {
+                if group.consumers.contains_key(consumer) {
+                    return false;
+                }
+                group.consumer(consumer, now_ms());
+                true
+            },
+            |&created| created,
+        )
+    }
+
+    /// Delete the consumer, returns the number of its pending entries which are dropped.
+    pub fn xgroup_delconsumer(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+    ) -> Result<i64, BackendError> {
+        let ret = self.with_group(
+            key,
+            group,
+            |_, group| {
+                let removed = group.consumers.remove(consumer)?;
+                for id in &removed.pending {
+                    group.pel.remove(id);
+                }
+                Some(removed.pending.len() as i64)
+            },
+            Option::is_some,
+        )?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// Read the streams as the consumer of the group. None in `ids` is `>`, the
+    /// entries never delivered to the group, otherwise the pending entries of the
+    /// consumer after the ID are read again.
+    pub fn xreadgroup(
+        &self,
+        group: &str,
+        consumer: &str,
+        keys: &[String],
+        ids: &[Option<StreamId>],
+        count: Option<usize>,
+        noack: bool,
+    ) -> Result<Vec<(String, Vec<GroupEntry>)>, BackendError> {
+        let mut streams = Vec::new();
+        for (key, after) in keys.iter().zip(ids) {
+            let entries = self.with_group(
+                key,
+                group,
+                |entries, group| group.read(entries, consumer, *after, count, noack),
+                |entries| !entries.is_empty(),
+            )?;
+            // the history is always replied, even if it is empty
+            if !entries.is_empty() || after.is_some() {
+                streams.push((key.clone(), entries));
+            }
+        }
+        Ok(streams)
+    }
+
+    /// XREADGROUP, or wait until an entry is added to one of the streams.
+    /// `timeout` None waits forever, returns None on timeout.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn xreadgroup_block(
+        &self,
+        group: &str,
+        consumer: &str,
+        keys: &[String],
+        ids: &[Option<StreamId>],
+        count: Option<usize>,
+        noack: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Vec<(String, Vec<GroupEntry>)>>, BackendError> {
+        self.block_read(keys, timeout, || {
+            let streams = self.xreadgroup(group, consumer, keys, ids, count, noack)?;
+            Ok((!streams.is_empty()).then_some(streams))
+        })
+        .await
+    }
+
+    /// Acknowledge the entries, returns the number of the entries which were pending.
+    pub fn xack(&self, key: &str, group: &str, ids: &[StreamId]) -> Result<i64, BackendError> {
+        let ret = self.with_group(
+            key,
+            group,
+            |_, group| ids.iter().filter(|id| group.ack(id)).count() as i64,
+            |&n| n > 0,
+        );
+        match ret {
+            Err(BackendError::NoGroup(..)) => Ok(0),
+            ret => ret,
+        }
+    }
+
+    pub fn xpending_summary(&self, key: &str, group: &str) -> Result<PendingSummary, BackendError> {
+        self.with_group(
+            key,
+            group,
+            |_, group| {
+                let first = group.pel.first_key_value().map(|(id, _)| *id);
+                let last = group.pel.last_key_value().map(|(id, _)| *id);
+                PendingSummary {
+                    count: group.pel.len(),
+                    range: first.zip(last),
+                    consumers: group
+                        .consumers
+                        .iter()
+                        .filter(|(_, c)| !c.pending.is_empty())
+                        .map(|(name, c)| (name.clone(), c.pending.len()))
+                        .collect(),
+                }
+            },
+            |_| false,
+        )
+    }
+
+    pub fn xpending(
+        &self,
+        key: &str,
+        group: &str,
+        range: &PendingRange,
+    ) -> Result<Vec<PendingInfo>, BackendError> {
+        self.with_group(
+            key,
+            group,
+            |_, group| {
+                if is_empty_range(range.start, range.end) {
+                    return Vec::new();
+                }
+                let now = now_ms();
+                group
+                    .pel
+                    .range((range.start, range.end))
+                    .filter(|(_, p)| range.consumer.as_ref().is_none_or(|c| *c == p.consumer))
+                    .filter(|(_, p)| {
+                        range
+                            .min_idle
+                            .is_none_or(|idle| now - p.delivery_time >= idle)
+                    })
+                    .take(range.count)
+                    .map(|(id, p)| PendingInfo {
+                        id: *id,
+                        consumer: p.consumer.clone(),
+                        idle: now - p.delivery_time,
+                        delivery_count: p.delivery_count,
+                    })
+                    .collect()
+            },
+            |_| false,
+        )
+    }
+
+    /// Claim the pending entries idle for at least `min_idle` milliseconds,
+    /// returns the claimed entries.
+    pub fn xclaim(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: i64,
+        ids: &[StreamId],
+        opts: &ClaimOptions,
+    ) -> Result<Vec<StreamEntry>, BackendError> {
+        self.with_group(
+            key,
+            group,
+            |entries, group| {
+                group.consumer(consumer, now_ms());
+                if let Some(last_id) = opts.last_id {
+                    group.last_id = group.last_id.max(last_id);
+                }
+                ids.iter()
+                    .filter_map(|id| group.claim(entries, *id, consumer, min_idle, opts))
+                    .collect()
+            },
+            |claimed: &Vec<_>| !claimed.is_empty(),
+        )
+    }
+
+    /// Scan the PEL from `start` and claim up to `count` entries idle for at least
+    /// `min_idle` milliseconds. Returns the ID to continue the scan from, 0-0 if
+    /// it is complete, the claimed entries, and the IDs of the deleted entries
+    /// removed from the PEL.
+    #[allow(clippy::too_many_arguments)]
+    pub fn xautoclaim(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle: i64,
+        start: StreamId,
+        count: usize,
+        just_id: bool,
+    ) -> Result<(StreamId, Vec<StreamEntry>, Vec<StreamId>), BackendError> {
+        // don't scan the whole PEL when few entries are idle
+        let attempts = count.saturating_mul(10);
+        self.with_group(
+            key,
+            group,
+            |entries, group| {
+                group.consumer(consumer, now_ms());
+                let candidates = group
+                    .pel
+                    .range(start..)
+                    .map(|(id, _)| *id)
+                    .take(attempts.saturating_add(1))
+                    .collect::<Vec<_>>();
+                let opts = ClaimOptions {
+                    just_id,
+                    ..Default::default()
+                };
+
+                let (mut claimed, mut deleted) = (Vec::new(), Vec::new());
+                for (i, id) in candidates.iter().enumerate() {
+                    if claimed.len() >= count || i >= attempts {
+                        return (*id, claimed, deleted);
+                    }
+                    if !entries.contains_key(id) {
+                        group.ack(id);
+                        deleted.push(*id);
+                        continue;
+                    }
+                    claimed.extend(group.claim(entries, *id, consumer, min_idle, &opts));
+                }
+                (StreamId::MIN, claimed, deleted)
+            },
+            |(_, claimed, deleted)| !claimed.is_empty() || !deleted.is_empty(),
+        )
+    }
+
+    pub fn xinfo_stream(&self, key: &str) -> Result<StreamInfo, BackendError> {
+        let ret = self.read(key, |stream: &Stream| StreamInfo {
+            length: stream.len(),
+            last_generated_id: stream.last_id,
+            max_deleted_id: stream.max_deleted_id,
+            entries_added: stream.entries_added,
+            first_id: stream.first_entry().map(|(id, _)| id).unwrap_or_default(),
+            groups: stream.groups.len(),
+            first_entry: stream.first_entry(),
+            last_entry: stream.last_entry(),
+        })?;
+        ret.ok_or(BackendError::NoSuchKey)
+    }
+
+    pub fn xinfo_groups(&self, key: &str) -> Result<Vec<GroupInfo>, BackendError> {
+        let ret = self.read(key, |stream: &Stream| {
+            stream
+                .groups
+                .iter()
+                .map(|(name, group)| {
+                    // the entries not delivered yet, they are all in the stream
+                    let lag = stream
+                        .entries
+                        .range((Bound::Excluded(group.last_id), Bound::Unbounded))
+                        .count() as u64;
+                    GroupInfo {
+                        name: name.clone(),
+                        consumers: group.consumers.len(),
+                        pending: group.pel.len(),
+                        last_delivered_id: group.last_id,
+                        entries_read: stream.entries_added.saturating_sub(lag),
+                        lag,
+                    }
+                })
+                .collect()
+        })?;
+        ret.ok_or(BackendError::NoSuchKey)
+    }
+
+    pub fn xinfo_consumers(
+        &self,
+        key: &str,
+        group: &str,
+    ) -> Result<Vec<ConsumerInfo>, BackendError> {
+        let ret = self.read(key, |stream: &Stream| {
+            let now = now_ms();
+            let group = stream.groups.get(group)?;
+            let consumers = group
+                .consumers
+                .iter()
+                .map(|(name, c)| ConsumerInfo {
+                    name: name.clone(),
+                    pending: c.pending.len(),
+                    idle: now - c.seen_time,
+                    inactive: c.active_time.map(|t| now - t),
+                })
+                .collect();
+            Some(consumers)
+        })?;
+        match ret {
+            None => Err(BackendError::NoSuchKey),
+            Some(None) => Err(BackendError::NoGroup(key.to_string(), group.to_string())),
+            Some(Some(consumers)) => Ok(consumers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XAddId;
+
+    fn stream_with(backend: &Backend, n: u64) -> Result<(), BackendError> {
+        for i in 1..=n {
+            let fields = vec![(b"i".to_vec(), i.to_string().into_bytes())];
+            backend.xadd(
+                "s",
+                XAddId::Explicit(StreamId::new(i, 0)),
+                fields,
+                false,
+                None,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn ids<T>(entries: &[(StreamId, T)]) -> Vec<u64> {
+        entries.iter().map(|(id, _)| id.ms).collect()
+    }
+
+    #[test]
+    fn test_xgroup() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(
+            backend.xgroup_create("s", "g", None, false),
+            Err(BackendError::NoStreamForGroup)
+        );
+        backend.xgroup_create("s", "g", None, true)?;
+        assert_eq!(backend.key_type("s"), "stream");
+        assert_eq!(
+            backend.xgroup_create("s", "g", None, true),
+            Err(BackendError::BusyGroup)
+        );
+
+        assert!(backend.xgroup_createconsumer("s", "g", "alice")?);
+        assert!(!backend.xgroup_createconsumer("s", "g", "alice")?);
+        assert_eq!(
+            backend.xgroup_createconsumer("s", "none", "alice"),
+            Err(BackendError::NoGroup("s".to_string(), "none".to_string()))
+        );
+        assert!(backend.xgroup_destroy("s", "g")?);
+        assert!(!backend.xgroup_destroy("s", "g")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_xreadgroup_xack() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        stream_with(&backend, 3)?;
+        backend.xgroup_create("s", "g", Some(StreamId::MIN), false)?;
+        let keys = ["s".to_string()];
+
+        let read = backend.xreadgroup("g", "alice", &keys, &[None], Some(2), false)?;
+        assert_eq!(ids(&read[0].1), [1, 2]);
+        let read = backend.xreadgroup("g", "bob", &keys, &[None], None, false)?;
+        assert_eq!(ids(&read[0].1), [3]);
+        assert!(backend
+            .xreadgroup("g", "bob", &keys, &[None], None, false)?
+            .is_empty());
+
+        // the history of alice, with the deleted entries
+        backend.xdel("s", &[StreamId::new(2, 0)])?;
+        let read = backend.xreadgroup("g", "alice", &keys, &[Some(StreamId::MIN)], None, false)?;
+        assert_eq!(read[0].1[1], (StreamId::new(2, 0), None));
+
+        let summary = backend.xpending_summary("s", "g")?;
+        assert_eq!(summary.count, 3);
+        assert_eq!(
+            summary.range,
+            Some((StreamId::new(1, 0), StreamId::new(3, 0)))
+        );
+        assert_eq!(
+            summary.consumers,
+            [("alice".to_string(), 2), ("bob".to_string(), 1)]
+        );
+
+        let range = PendingRange {
+            start: Bound::Included(StreamId::MIN),
+            end: Bound::Included(StreamId::MAX),
+            count: 10,
+            consumer: Some("alice".to_string()),
+            min_idle: None,
+        };
+        let pending = backend.xpending("s", "g", &range)?;
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].delivery_count, 2);
+
+        assert_eq!(
+            backend.xack("s", "g", &[StreamId::new(1, 0), StreamId::new(9, 0)])?,
+            1
+        );
+        assert_eq!(backend.xack("s", "none", &[StreamId::new(1, 0)])?, 0);
+        assert_eq!(backend.xpending_summary("s", "g")?.count, 2);
+        assert_eq!(backend.xgroup_delconsumer("s", "g", "alice")?, 1);
+        assert_eq!(backend.xpending_summary("s", "g")?.count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_xclaim_xautoclaim() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        stream_with(&backend, 4)?;
+        backend.xgroup_create("s", "g", Some(StreamId::MIN), false)?;
+        let keys = ["s".to_string()];
+        backend.xreadgroup("g", "alice", &keys, &[None], None, false)?;
+
+        let one = [StreamId::new(1, 0)];
+        let opts = ClaimOptions::default();
+        assert!(backend
+            .xclaim("s", "g", "bob", 60_000, &one, &opts)?
+            .is_empty());
+        let claimed = backend.xclaim("s", "g", "bob", 0, &one, &opts)?;
+        assert_eq!(ids(&claimed), [1]);
+
+        backend.xdel("s", &[StreamId::new(3, 0)])?;
+        let (next, claimed, deleted) =
+            backend.xautoclaim("s", "g", "carol", 0, StreamId::MIN, 2, false)?;
+        assert_eq!((next, ids(&claimed)), (StreamId::new(3, 0), vec![1, 2]));
+        assert!(deleted.is_empty());
+        let (next, claimed, deleted) = backend.xautoclaim("s", "g", "carol", 0, next, 2, true)?;
+        assert_eq!((next, ids(&claimed)), (StreamId::MIN, vec![4]));
+        assert_eq!(deleted, [StreamId::new(3, 0)]);
+
+        let info = backend.xinfo_consumers("s", "g")?;
+        let pending = info
+            .iter()
+            .map(|c| (c.name.as_str(), c.pending))
+            .collect::<Vec<_>>();
+        assert_eq!(pending, [("alice", 0), ("bob", 0), ("carol", 3)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_xinfo() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(backend.xinfo_stream("s"), Err(BackendError::NoSuchKey));
+        stream_with(&backend, 3)?;
+        backend.xdel("s", &[StreamId::new(2, 0)])?;
+        backend.xgroup_create("s", "g", Some(StreamId::new(1, 0)), false)?;
+
+        let info = backend.xinfo_stream("s")?;
+        assert_eq!(info.length, 2);
+        assert_eq!(info.entries_added, 3);
+        assert_eq!(info.max_deleted_id, StreamId::new(2, 0));
+        assert_eq!(info.groups, 1);
+        assert_eq!(info.last_entry.map(|(id, _)| id), Some(StreamId::new(3, 0)));
+
+        let groups = backend.xinfo_groups("s")?;
+        assert_eq!(groups[0].lag, 1);
+        assert_eq!(groups[0].last_delivered_id, StreamId::new(1, 0));
+        Ok(())
+    }
+}
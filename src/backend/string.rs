@@ -0,0 +1,314 @@
+use dashmap::mapref::entry::Entry;
+
+use super::{Backend, BackendError, ExpireCondition, Value};
+
+/// The max size of a string value, 512MB like redis.
+const MAX_STRING_SIZE: usize = 512 * 1024 * 1024;
+
+fn parse_number<T: std::str::FromStr>(value: &[u8]) -> Option<T> {
+    std::str::from_utf8(value).ok()?.parse().ok()
+}
+
+/// Normalize the inclusive byte range of GETRANGE, negative offsets count from the end.
+fn byte_range(start: i64, end: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let end = if end < 0 { len + end } else { end.min(len - 1) };
+    (start <= end && start < len && end >= 0).then_some((start as usize, end as usize))
+}
+
+impl Backend {
+    /// Update the string value of the key atomically.
+    /// `f` gets the current value and whether the key exists, a missing key is created
+    /// with the value left by `f` unless it returns an error.
+    pub(crate) fn write_string<R>(
+        &self,
+        key: &str,
+        f: impl FnOnce(&mut Vec<u8>, bool) -> Result<R, BackendError>,
+    ) -> Result<R, BackendError> {
+        self.expire_if_needed(key);
+        let ret = match self.db.entry(key.to_string()) {
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                Value::String(value) => f(value, true),
+                _ => Err(BackendError::WrongType),
+            },
+            Entry::Vacant(entry) => {
+                let mut value = Vec::new();
+                let ret = f(&mut value, false)?;
+                entry.insert(Value::String(value));
+                Ok(ret)
+            }
+        };
+        if ret.is_ok() {
+            self.touch_watched(key);
+        }
+        ret
+    }
+
+    /// Increment the integer value of the key, a missing key counts as 0.
+    pub fn incr_by(&self, key: &str, increment: i64) -> Result<i64, BackendError> {
+        self.write_string(key, |value, exists| {
+            let n: i64 = match exists {
+                true => parse_number(value).ok_or(BackendError::ValueNotInteger)?,
+                false => 0,
+            };
+            let n = n.checked_add(increment).ok_or(BackendError::Overflow)?;
+            *value = n.to_string().into_bytes();
+            Ok(n)
+        })
+    }
+
+    /// Increment the float value of the key, a missing key counts as 0.
+    pub fn incr_by_float(&self, key: &str, increment: f64) -> Result<f64, BackendError> {
+        self.write_string(key, |value, exists| {
+            let n: f64 = match exists {
+                true => parse_number(value)
+                    .filter(|n: &f64| !n.is_nan())
+                    .ok_or(BackendError::ValueNotFloat)?,
+                false => 0.0,
+            };
+            let n = n + increment;
+            if !n.is_finite() {
+                return Err(BackendError::NanOrInfinity);
+            }
+            *value = n.to_string().into_bytes();
+            Ok(n)
+        })
+    }
+
+    /// Append to the string value, returns the new length.
+    pub fn append(&self, key: &str, data: &[u8]) -> Result<i64, BackendError> {
+        self.write_string(key, |value, _| {
+            if value.len() + data.len() > MAX_STRING_SIZE {
+                return Err(BackendError::StringTooLong);
+            }
+            value.extend_from_slice(data);
+            Ok(value.len() as i64)
+        })
+    }
+
+    pub fn strlen(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| value.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    /// The bytes in the inclusive range, negative offsets count from the end.
+    pub fn getrange(&self, key: &str, start: i64, end: i64) -> Result<Vec<u8>, BackendError> {
+        let ret = self.read(key, |value: &Vec<u8>| {
+            match byte_range(start, end, value.len()) {
+                Some((start, end)) => value[start..=end].to_vec(),
+                None => vec![],
+            }
+        })?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    /// Overwrite the string from `offset`, padding with zero bytes if needed.
+    /// Returns the new length.
+    pub fn setrange(&self, key: &str, offset: usize, data: &[u8]) -> Result<i64, BackendError> {
+        if data.is_empty() {
+            // nothing to write, don't create the key
+            return self.strlen(key);
+        }
+        if offset + data.len() > MAX_STRING_SIZE {
+            return Err(BackendError::StringTooLong);
+        }
+        self.write_string(key, |value, _| {
+            let end = offset + data.len();
+            if value.len() < end {
+                value.resize(end, 0);
+            }
+            value[offset..end].copy_from_slice(data);
+            Ok(value.len() as i64)
+        })
+    }
+
+    /// Get the value and delete the key.
+    pub fn getdel(&self, key: &str) -> Result<Option<Vec<u8>>, BackendError> {
+        self.expire_if_needed(key);
+        // the TTL is removed while the value is held, see `take_key`
+        let removed = self.db.remove_if(key, |_, v| {
+            let string = matches!(v, Value::String(_));
+            if string {
+                self.expires.remove(key);
+            }
+            string
+        });
+        match removed {
+            Some((_, Value::String(value))) => {
+                self.touch_watched(key);
+                Ok(Some(value))
+            }
+            _ if self.db.contains_key(key) => Err(BackendError::WrongType),
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the value and update its TTL, `expire_at` is the new deadline in milliseconds,
+    /// or the TTL is removed if `persist` is set.
+    pub fn getex(
+        &self,
+        key: &str,
+        expire_at: Option<i64>,
+        persist: bool,
+    ) -> Result<Option<Vec<u8>>, BackendError> {
+        let value = self.get(key)?;
+        if value.is_some() {
+            match expire_at {
+                Some(at) => {
+                    self.expire_at(key, at, ExpireCondition::Always);
+                }
+                None if persist => {
+                    self.persist(key);
+                }
+                None => {}
+            }
+        }
+        Ok(value)
+    }
+
+    /// The values of the keys, None for a missing key or a key of another type.
+    pub fn mget(&self, keys: &[String]) -> Vec<Option<Vec<u8>>> {
+        keys.iter()
+            .map(|key| self.get(key).ok().flatten())
+            .collect()
+    }
+
+    pub fn mset(&self, pairs: Vec<(String, Vec<u8>)>) {
+        for (key, value) in pairs {
+            self.set(key, value);
+        }
+    }
+
+    /// Set the keys only if none of them exists, returns whether they were set.
+    pub fn msetnx(&self, pairs: Vec<(String, Vec<u8>)>) -> bool {
+        // NOTE: all or nothing, each key is inserted through its vacant entry, so a
+        // key created meanwhile isn't overwritten, and the keys inserted so far are
+        // removed again once an existing key is found
+        let mut inserted = std::collections::HashSet::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            self.expire_if_needed(&key);
+            match self.db.entry(key.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(Value::String(value));
+                }
+                // a key given twice is set to its last value
+                Entry::Occupied(mut entry) if inserted.contains(&key) => {
+                    entry.insert(Value::String(value));
+                }
+                Entry::Occupied(entry) => {
+                    drop(entry);
+                    for key in &inserted {
+                        self.db.remove(key);
+                    }
+                    return false;
+                }
+            }
+            inserted.insert(key);
+        }
+        for key in &inserted {
+            self.touch_watched(key);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incr() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(backend.incr_by("n", 1)?, 1);
+        assert_eq!(backend.incr_by("n", -11)?, -10);
+        assert_eq!(backend.incr_by_float("n", 0.5)?, -9.5);
+        assert_eq!(backend.get("n")?, Some(b"-9.5".to_vec()));
+        assert_eq!(backend.incr_by("n", 1), Err(BackendError::ValueNotInteger));
+
+        backend.set("n".to_string(), i64::MAX.to_string());
+        assert_eq!(backend.incr_by("n", 1), Err(BackendError::Overflow));
+        backend.set("s".to_string(), "");
+        assert_eq!(backend.incr_by("s", 1), Err(BackendError::ValueNotInteger));
+        assert_eq!(
+            backend.incr_by_float("s", 1.0),
+            Err(BackendError::ValueNotFloat)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_incr_concurrent() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let handles = (0..8)
+            .map(|_| {
+                let backend = backend.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        backend.incr_by("counter", 1).unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(backend.get("counter")?, Some(b"8000".to_vec()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ranges() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        assert_eq!(backend.append("s", b"Hello")?, 5);
+        assert_eq!(backend.append("s", b" World")?, 11);
+        assert_eq!(backend.getrange("s", 0, 4)?, b"Hello");
+        assert_eq!(backend.getrange("s", -3, -1)?, b"rld");
+        assert_eq!(backend.getrange("s", 5, 2)?, b"");
+        assert_eq!(backend.getrange("s", 0, 100)?, b"Hello World");
+
+        assert_eq!(backend.setrange("s", 6, b"Redis")?, 11);
+        assert_eq!(backend.get("s")?, Some(b"Hello Redis".to_vec()));
+        assert_eq!(backend.setrange("pad", 2, b"x")?, 3);
+        assert_eq!(backend.get("pad")?, Some(b"\0\0x".to_vec()));
+        assert_eq!(backend.setrange("none", 2, b"")?, 0);
+        assert!(!backend.exists("none"));
+
+        assert_eq!(backend.getdel("s")?, Some(b"Hello Redis".to_vec()));
+        assert!(!backend.exists("s"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_msetnx() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let pairs = |keys: &[&str]| {
+            keys.iter()
+                .map(|k| (k.to_string(), b"v".to_vec()))
+                .collect::<Vec<_>>()
+        };
+        assert!(backend.msetnx(pairs(&["a", "b", "a"])));
+        // b exists, a new key set before it is removed again
+        assert!(!backend.msetnx(pairs(&["c", "b"])));
+        assert!(!backend.exists("c"));
+
+        // only one of the concurrent calls sets the key
+        let handles = (0..8)
+            .map(|_| {
+                let backend = backend.clone();
+                std::thread::spawn(move || backend.msetnx(pairs(&["x", "y"])))
+            })
+            .collect::<Vec<_>>();
+        let set = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|&set| set)
+            .count();
+        assert_eq!(set, 1);
+        Ok(())
+    }
+}
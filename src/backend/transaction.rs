@@ -0,0 +1,123 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    RwLockReadGuard, RwLockWriteGuard,
+};
+
+use super::Backend;
+
+// EXEC runs the queued commands with the keyspace locked exclusively, while
+// every other command holds the lock shared, so no command of another
+// connection is interleaved with a transaction.
+// Like redis `signalModifiedKey`, a write which changes a watched key bumps its
+// version, and EXEC compares it with the version seen by WATCH. A write which
+// leaves the key as it was doesn't count, e.g. SADD of an existing member.
+
+/// The version of a key watched by at least one connection.
+#[derive(Debug, Default)]
+pub(crate) struct WatchVersion {
+    watchers: usize,
+    version: AtomicU64,
+}
+
+/// A key watched by WATCH, unwatched when dropped.
+#[derive(Debug)]
+pub struct WatchedKey {
+    key: String,
+    version: u64,
+    backend: Backend,
+}
+
+impl Backend {
+    /// Hold the keyspace shared, for a single command.
+    pub fn lock_shared(&self) -> RwLockReadGuard<'_, ()> {
+        self.txn_lock.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Hold the keyspace exclusively, for a transaction.
+    pub fn lock_exclusive(&self) -> RwLockWriteGuard<'_, ()> {
+        self.txn_lock.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn watch(&self, key: &str) -> WatchedKey {
+        self.expire_if_needed(key);
+        let mut watched = self.watched.entry(key.to_string()).or_default();
+        watched.watchers += 1;
+        WatchedKey {
+            key: key.to_string(),
+            version: watched.version.load(Ordering::Relaxed),
+            backend: self.clone(),
+        }
+    }
+
+    /// Whether the watched key has been modified, expired or deleted since it was watched.
+    pub fn is_modified(&self, watched: &WatchedKey) -> bool {
+        // a key which expired meanwhile is removed now, which touches it
+        self.expire_if_needed(&watched.key);
+        self.watched
+            .get(&watched.key)
+            .is_none_or(|v| v.version.load(Ordering::Relaxed) != watched.version)
+    }
+
+    /// Mark the key as modified for the connections watching it.
+    /// NOTE: call it once the entry of the key is released.
+    pub(crate) fn touch_watched(&self, key: &str) {
+        if let Some(watched) = self.watched.get(key) {
+            watched.version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for WatchedKey {
+    fn drop(&mut self) {
+        self.backend.watched.remove_if_mut(&self.key, |_, watched| {
+            watched.watchers -= 1;
+            watched.watchers == 0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{now_ms, ExpireCondition};
+
+    #[test]
+    fn test_watch() {
+        let backend = Backend::new();
+        let missing = backend.watch("hello");
+        backend.set("hello".to_string(), "world");
+        assert!(backend.is_modified(&missing));
+
+        let watched = backend.watch("hello");
+        assert!(!backend.is_modified(&watched));
+        backend.expire_at("hello", now_ms() + 10_000, ExpireCondition::Always);
+        assert!(backend.is_modified(&watched));
+
+        let watched = backend.watch("hello");
+        backend.append("hello", b"!").unwrap();
+        assert!(backend.is_modified(&watched));
+
+        // the value is the same again, but it has been modified
+        backend.set("counter".to_string(), "1");
+        let watched = backend.watch("counter");
+        backend.incr_by("counter", 1).unwrap();
+        backend.incr_by("counter", -1).unwrap();
+        assert!(backend.is_modified(&watched));
+
+        // a write which changes nothing doesn't modify the key
+        backend.sadd("set", &["a".to_string()]).unwrap();
+        let (set, missing) = (backend.watch("set"), backend.watch("missing"));
+        backend.sadd("set", &["a".to_string()]).unwrap();
+        backend.srem("missing", &["a".to_string()]).unwrap();
+        assert!(!backend.is_modified(&set));
+        assert!(!backend.is_modified(&missing));
+
+        // the version is forgotten once nobody watches the key
+        let backend = Backend::new();
+        let (first, second) = (backend.watch("hello"), backend.watch("hello"));
+        drop(first);
+        assert!(!backend.is_modified(&second));
+        drop(second);
+        assert!(backend.watched.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::{hash_map::RandomState, VecDeque};
+
+use thiserror::Error;
+
+use crate::{RespFrame, SimpleError};
+
+use super::{Stream, ZSet};
+
+// hashbrown instead of std for the hashes and the sets, HSCAN / SSCAN walk the
+// buckets of their raw tables, see scan.rs
+pub(crate) type HashMap<K, V> = hashbrown::HashMap<K, V, RandomState>;
+pub(crate) type HashSet<T> = hashbrown::HashSet<T, RandomState>;
+
+/// A value stored in the keyspace, every key holds exactly one type of value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(Vec<u8>), // strings are binary safe
+    Hash(HashMap<String, RespFrame>),
+    Set(HashSet<String>), // RespFrame 不能实现 Eq + Hash, 因此这里使用 String
+    List(VecDeque<RespFrame>),
+    ZSet(ZSet),
+    Stream(Stream),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BackendError {
+    #[error("WRONGTYPE Operation against a key holding the wrong kind of value")]
+    WrongType,
+    #[error("ERR no such key")]
+    NoSuchKey,
+    #[error("ERR index out of range")]
+    IndexOutOfRange,
+    #[error("ERR resulting score is not a number (NaN)")]
+    NotANumber,
+    #[error("ERR hash value is not an integer")]
+    HashValueNotInteger,
+    #[error("ERR hash value is not a float")]
+    HashValueNotFloat,
+    #[error("ERR value is not an integer or out of range")]
+    ValueNotInteger,
+    #[error("ERR value is not a valid float")]
+    ValueNotFloat,
+    #[error("ERR string exceeds maximum allowed size (proto-max-bulk-len)")]
+    StringTooLong,
+    #[error("ERR increment or decrement would overflow")]
+    Overflow,
+    #[error("ERR increment would produce NaN or Infinity")]
+    NanOrInfinity,
+    #[error("WRONGTYPE Key is not a valid HyperLogLog string value.")]
+    NotHyperLogLog,
+    #[error("INVALIDOBJ Corrupted HLL object detected")]
+    CorruptedHll,
+    #[error("ERR could not decode requested zset member")]
+    GeoMemberNotFound,
+    #[error("ERR The ID specified in XADD is equal or smaller than the target stream top item")]
+    StreamIdTooSmall,
+    #[error("ERR The ID specified in XADD must be greater than 0-0")]
+    StreamIdZero,
+    #[error("ERR The stream has exhausted the last possible ID, unable to add more items")]
+    StreamExhausted,
+    #[error("BUSYGROUP Consumer Group name already exists")]
+    BusyGroup,
+    #[error("NOGROUP No such key '{0}' or consumer group '{1}'")]
+    NoGroup(String, String),
+    #[error("ERR The XGROUP subcommand requires the key to exist. Note that for CREATE you may want to use the MKSTREAM option to create an empty stream automatically.")]
+    NoStreamForGroup,
+}
+
+/// The types which can be stored in a `Value`.
+/// A key holding an empty aggregate is removed from the keyspace.
+pub(crate) trait TypedValue: Default + Into<Value> {
+    fn from_ref(value: &Value) -> Option<&Self>;
+    fn from_mut(value: &mut Value) -> Option<&mut Self>;
+    fn is_empty(&self) -> bool;
+}
+
+impl Value {
+    /// The type name reported by the TYPE command.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Hash(_) => "hash",
+            Value::Set(_) => "set",
+            Value::List(_) => "list",
+            Value::ZSet(_) => "zset",
+            Value::Stream(_) => "stream",
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::String(_) => false,
+            Value::Hash(v) => v.is_empty(),
+            Value::Set(v) => v.is_empty(),
+            Value::List(v) => v.is_empty(),
+            Value::ZSet(v) => v.is_empty(),
+            // a stream is kept when all its entries are deleted
+            Value::Stream(_) => false,
+        }
+    }
+
+    /// Roughly the number of allocations to free, like redis `lazyfreeGetFreeEffort`.
+    pub fn free_effort(&self) -> usize {
+        match self {
+            Value::String(_) => 1,
+            Value::Hash(v) => v.len(),
+            Value::Set(v) => v.len(),
+            Value::List(v) => v.len(),
+            Value::ZSet(v) => v.len(),
+            Value::Stream(v) => v.len(),
+        }
+    }
+}
+
+impl From<BackendError> for RespFrame {
+    fn from(e: BackendError) -> Self {
+        SimpleError::new(e.to_string()).into()
+    }
+}
+
+impl From<HashMap<String, RespFrame>> for Value {
+    fn from(v: HashMap<String, RespFrame>) -> Self {
+        Value::Hash(v)
+    }
+}
+
+impl From<HashSet<String>> for Value {
+    fn from(v: HashSet<String>) -> Self {
+        Value::Set(v)
+    }
+}
+
+impl From<VecDeque<RespFrame>> for Value {
+    fn from(v: VecDeque<RespFrame>) -> Self {
+        Value::List(v)
+    }
+}
+
+impl From<ZSet> for Value {
+    fn from(v: ZSet) -> Self {
+        Value::ZSet(v)
+    }
+}
+
+impl From<Stream> for Value {
+    fn from(v: Stream) -> Self {
+        Value::Stream(v)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Self {
+        Value::String(v)
+    }
+}
+
+impl TypedValue for Vec<u8> {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // an empty string is still a value, unlike an empty aggregate
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl TypedValue for HashMap<String, RespFrame> {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Hash(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Hash(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+}
+
+impl TypedValue for HashSet<String> {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Set(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Set(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+}
+
+impl TypedValue for VecDeque<RespFrame> {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+}
+
+impl TypedValue for ZSet {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::ZSet(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::ZSet(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        ZSet::is_empty(self)
+    }
+}
+
+impl TypedValue for Stream {
+    fn from_ref(value: &Value) -> Option<&Self> {
+        match value {
+            Value::Stream(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn from_mut(value: &mut Value) -> Option<&mut Self> {
+        match value {
+            Value::Stream(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
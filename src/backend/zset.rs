@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+
+use super::{list::normalize_range, skiplist::SkipList, Backend, BackendError, SetCondition};
+
+/// A sorted set, the skiplist keeps the members ordered by (score, member)
+/// for the rank and range queries, the map gives the score of a member in O(1).
+#[derive(Debug, Clone, Default)]
+pub struct ZSet {
+    dict: HashMap<String, f64>,
+    list: SkipList,
+}
+
+/// The GT / LT options of ZADD, only update the existing elements if the new
+/// score is greater / less than the current score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreCompare {
+    Gt,
+    Lt,
+}
+
+/// The options of ZADD, see https://redis.io/docs/latest/commands/zadd/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ZAddFlags {
+    pub condition: SetCondition,
+    pub compare: Option<ScoreCompare>,
+    pub ch: bool, // count the changed elements as well as the new ones
+}
+
+/// A score bound of ZRANGE BYSCORE, `(1.5` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBound {
+    pub value: f64,
+    pub exclusive: bool,
+}
+
+/// A member bound of ZRANGE BYLEX, `-` and `+` are the minimum and the maximum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LexBound {
+    Min,
+    Max,
+    Inclusive(String),
+    Exclusive(String),
+}
+
+/// The range of ZRANGE, always given as (min, max) whatever the direction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZRangeBy {
+    Rank(i64, i64),
+    Score(ScoreBound, ScoreBound),
+    Lex(LexBound, LexBound),
+}
+
+impl ScoreBound {
+    fn above_min(&self, score: f64) -> bool {
+        match self.exclusive {
+            true => score > self.value,
+            false => score >= self.value,
+        }
+    }
+
+    fn below_max(&self, score: f64) -> bool {
+        match self.exclusive {
+            true => score < self.value,
+            false => score <= self.value,
+        }
+    }
+}
+
+impl LexBound {
+    fn above_min(&self, member: &str) -> bool {
+        match self {
+            LexBound::Min => true,
+            LexBound::Max => false,
+            LexBound::Inclusive(s) => member >= s.as_str(),
+            LexBound::Exclusive(s) => member > s.as_str(),
+        }
+    }
+
+    fn below_max(&self, member: &str) -> bool {
+        match self {
+            LexBound::Min => false,
+            LexBound::Max => true,
+            LexBound::Inclusive(s) => member <= s.as_str(),
+            LexBound::Exclusive(s) => member < s.as_str(),
+        }
+    }
+}
+
+fn collect_range<'a>(
+    iter: impl Iterator<Item = (&'a str, f64)>,
+    offset: usize,
+    count: usize,
+) -> Vec<(String, f64)> {
+    iter.skip(offset)
+        .take(count)
+        .map(|(member, score)| (member.to_string(), score))
+        .collect()
+}
+
+impl ZSet {
+    pub fn len(&self) -> usize {
+        self.dict.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dict.is_empty()
+    }
+
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.dict.get(member).copied()
+    }
+
+    /// Insert the member or update its score, returns true if the member is new.
+    pub fn insert(&mut self, member: String, score: f64) -> bool {
+        match self.dict.get_mut(&member) {
+            Some(old) => {
+                if *old != score {
+                    self.list.remove(*old, &member);
+                    self.list.insert(score, member);
+                    *old = score;
+                }
+                false
+            }
+            None => {
+                self.list.insert(score, member.clone());
+                self.dict.insert(member, score);
+                true
+            }
+        }
+    }
+
+    pub fn remove(&mut self, member: &str) -> bool {
+        match self.dict.remove(member) {
+            Some(score) => self.list.remove(score, member),
+            None => false,
+        }
+    }
+
+    /// The 0 based rank of the member and its score, ranked from the highest score if `rev`.
+    pub fn rank(&self, member: &str, rev: bool) -> Option<(usize, f64)> {
+        let score = self.score(member)?;
+        let rank = self.list.rank(score, member)?;
+        match rev {
+            true => Some((self.len() - 1 - rank, score)),
+            false => Some((rank, score)),
+        }
+    }
+
+    /// The elements in the range, in descending order if `rev`.
+    /// `limit` is the (offset, count) of the BYSCORE / BYLEX ranges, a negative count returns
+    /// all the elements from the offset.
+    pub fn range(
+        &self,
+        range: &ZRangeBy,
+        rev: bool,
+        limit: Option<(i64, i64)>,
+    ) -> Vec<(String, f64)> {
+        let (offset, count) = limit.unwrap_or((0, -1));
+        if offset < 0 {
+            return vec![];
+        }
+        let offset = offset as usize;
+        let count = if count < 0 {
+            usize::MAX
+        } else {
+            count as usize
+        };
+
+        match range {
+            ZRangeBy::Rank(start, stop) => {
+                let Some((start, stop)) = normalize_range(*start, *stop, self.len()) else {
+                    return vec![];
+                };
+                let rank = if rev { self.len() - 1 - start } else { start };
+                collect_range(self.list.iter_from_rank(rank, rev), 0, stop - start + 1)
+            }
+            ZRangeBy::Score(min, max) => match rev {
+                true => collect_range(
+                    self.list
+                        .seek_last(|score, _| max.below_max(score))
+                        .take_while(|(_, score)| min.above_min(*score)),
+                    offset,
+                    count,
+                ),
+                false => collect_range(
+                    self.list
+                        .seek_first(|score, _| min.above_min(score))
+                        .take_while(|(_, score)| max.below_max(*score)),
+                    offset,
+                    count,
+                ),
+            },
+            // the lex ranges assume all the elements have the same score
+            ZRangeBy::Lex(min, max) => match rev {
+                true => collect_range(
+                    self.list
+                        .seek_last(|_, member| max.below_max(member))
+                        .take_while(|(member, _)| min.above_min(member)),
+                    offset,
+                    count,
+                ),
+                false => collect_range(
+                    self.list
+                        .seek_first(|_, member| min.above_min(member))
+                        .take_while(|(member, _)| max.below_max(member)),
+                    offset,
+                    count,
+                ),
+            },
+        }
+    }
+
+    /// Remove and return up to `count` elements with the lowest scores, or the highest if `max`.
+    pub fn pop(&mut self, count: usize, max: bool) -> Vec<(String, f64)> {
+        let popped = collect_range(self.list.iter(max), 0, count);
+        for (member, _) in &popped {
+            self.remove(member);
+        }
+        popped
+    }
+
+    /// All the elements in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.list.iter(false)
+    }
+}
+
+impl PartialEq for ZSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.dict == other.dict
+    }
+}
+
+impl Backend {
+    /// Add the elements or update their scores, returns the number of the new elements,
+    /// or the number of the changed elements as well with the CH flag.
+    pub fn zadd(
+        &self,
+        key: &str,
+        elements: Vec<(f64, String)>,
+        flags: ZAddFlags,
+    ) -> Result<i64, BackendError> {
+        let (added, changed) = self.write(
+            key,
+            |zset: &mut ZSet| {
+                let (mut added, mut changed) = (0, 0);
+                for (score, member) in elements {
+                    match zset_update(zset, &member, score, flags) {
+                        Some(None) => {
+                            zset.insert(member, score);
+                            added += 1;
+                        }
+                        Some(Some(old)) if old != score => {
+                            zset.insert(member, score);
+                            changed += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                (added, changed)
+            },
+            |&(added, changed)| added + changed > 0,
+        )?;
+        Ok(if flags.ch { added + changed } else { added })
+    }
+
+    /// Increment the score of the member, returns the new score, or None if the flags
+    /// prevent the update.
+    pub fn zincrby(
+        &self,
+        key: &str,
+        member: String,
+        increment: f64,
+        flags: ZAddFlags,
+    ) -> Result<Option<f64>, BackendError> {
+        self.write(
+            key,
+            |zset: &mut ZSet| {
+                let score = zset.score(&member).unwrap_or(0.0) + increment;
+                if score.is_nan() {
+                    return Err(BackendError::NotANumber);
+                }
+                match zset_update(zset, &member, score, flags) {
+                    Some(_) => {
+                        zset.insert(member, score);
+                        Ok(Some(score))
+                    }
+                    None => Ok(None),
+                }
+            },
+            |ret| matches!(ret, Ok(Some(_))),
+        )?
+    }
+
+    /// Remove the members, returns the number of the removed members.
+    pub fn zrem(&self, key: &str, members: &[String]) -> Result<i64, BackendError> {
+        self.write(
+            key,
+            |zset: &mut ZSet| members.iter().filter(|m| zset.remove(m)).count() as i64,
+            |&n| n > 0,
+        )
+    }
+
+    pub fn zcard(&self, key: &str) -> Result<i64, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| zset.len() as i64)?;
+        Ok(ret.unwrap_or(0))
+    }
+
+    pub fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| zset.score(member))?;
+        Ok(ret.flatten())
+    }
+
+    pub fn zrank(
+        &self,
+        key: &str,
+        member: &str,
+        rev: bool,
+    ) -> Result<Option<(usize, f64)>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| zset.rank(member, rev))?;
+        Ok(ret.flatten())
+    }
+
+    pub fn zrange(
+        &self,
+        key: &str,
+        range: &ZRangeBy,
+        rev: bool,
+        limit: Option<(i64, i64)>,
+    ) -> Result<Vec<(String, f64)>, BackendError> {
+        let ret = self.read(key, |zset: &ZSet| zset.range(range, rev, limit))?;
+        Ok(ret.unwrap_or_default())
+    }
+
+    /// Remove and return the elements with the lowest scores, or the highest if `max`.
+    pub fn zpop(
+        &self,
+        key: &str,
+        count: usize,
+        max: bool,
+    ) -> Result<Vec<(String, f64)>, BackendError> {
+        self.write(
+            key,
+            |zset: &mut ZSet| zset.pop(count, max),
+            |popped| !popped.is_empty(),
+        )
+    }
+}
+
+/// Check the ZADD flags against the current score of the member.
+/// Returns None if the member shouldn't be updated, otherwise its current score.
+fn zset_update(zset: &ZSet, member: &str, score: f64, flags: ZAddFlags) -> Option<Option<f64>> {
+    match zset.score(member) {
+        None if flags.condition == SetCondition::Xx => None,
+        None => Some(None),
+        Some(_) if flags.condition == SetCondition::Nx => None,
+        Some(old) => match flags.compare {
+            Some(ScoreCompare::Gt) if score <= old => None,
+            Some(ScoreCompare::Lt) if score >= old => None,
+            _ => Some(Some(old)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound(value: f64, exclusive: bool) -> ScoreBound {
+        ScoreBound { value, exclusive }
+    }
+
+    #[test]
+    fn test_zadd_flags() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let elements = vec![(1.0, "a".to_string()), (2.0, "b".to_string())];
+        assert_eq!(backend.zadd("z", elements, ZAddFlags::default())?, 2);
+
+        let mut flags = ZAddFlags {
+            compare: Some(ScoreCompare::Gt),
+            ch: true,
+            ..Default::default()
+        };
+        let elements = vec![(0.5, "a".to_string()), (3.0, "b".to_string())];
+        assert_eq!(backend.zadd("z", elements, flags)?, 1);
+        assert_eq!(backend.zscore("z", "a")?, Some(1.0));
+        assert_eq!(backend.zscore("z", "b")?, Some(3.0));
+
+        flags.condition = SetCondition::Xx;
+        flags.compare = None;
+        assert_eq!(backend.zincrby("z", "c".to_string(), 1.0, flags)?, None);
+        assert_eq!(
+            backend.zincrby("z", "a".to_string(), 1.5, flags)?,
+            Some(2.5)
+        );
+        assert_eq!(backend.zrank("z", "b", true)?, Some((0, 3.0)));
+        assert_eq!(
+            backend.zincrby("inf", "a".to_string(), f64::INFINITY, ZAddFlags::default())?,
+            Some(f64::INFINITY)
+        );
+        assert_eq!(
+            backend.zincrby(
+                "inf",
+                "a".to_string(),
+                f64::NEG_INFINITY,
+                ZAddFlags::default()
+            ),
+            Err(BackendError::NotANumber)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_zrange() -> Result<(), BackendError> {
+        let backend = Backend::new();
+        let elements = ["a", "b", "c", "d", "e"]
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (i as f64, m.to_string()))
+            .collect();
+        backend.zadd("z", elements, ZAddFlags::default())?;
+
+        let members = |v: Vec<(String, f64)>| v.into_iter().map(|(m, _)| m).collect::<Vec<_>>();
+        let range = ZRangeBy::Rank(1, -2);
+        assert_eq!(
+            members(backend.zrange("z", &range, false, None)?),
+            ["b", "c", "d"]
+        );
+        assert_eq!(
+            members(backend.zrange("z", &range, true, None)?),
+            ["d", "c", "b"]
+        );
+
+        let range = ZRangeBy::Score(bound(1.0, true), bound(f64::INFINITY, false));
+        assert_eq!(
+            members(backend.zrange("z", &range, false, Some((1, 2)))?),
+            ["d", "e"]
+        );
+        assert_eq!(
+            members(backend.zrange("z", &range, true, Some((0, 2)))?),
+            ["e", "d"]
+        );
+
+        let range = ZRangeBy::Lex(LexBound::Exclusive("b".to_string()), LexBound::Max);
+        assert_eq!(
+            members(backend.zrange("z", &range, false, None)?),
+            ["c", "d", "e"]
+        );
+
+        assert_eq!(members(backend.zpop("z", 2, true)?), ["e", "d"]);
+        assert_eq!(backend.zrem("z", &["a".to_string(), "x".to_string()])?, 1);
+        assert_eq!(backend.zcard("z")?, 2);
+        Ok(())
+    }
+}
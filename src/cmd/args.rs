@@ -0,0 +1,107 @@
+use crate::{RespArray, RespFrame};
+
+use super::{
+    extract_args, extract_bytes, extract_float, extract_integer, extract_string, CommandError,
+};
+
+/// A cursor over the arguments of a command, for commands with optional
+/// arguments / flags which can't be validated by a fixed arity.
+#[derive(Debug)]
+pub struct CommandArgs {
+    args: std::vec::IntoIter<RespFrame>,
+}
+
+impl CommandArgs {
+    /// Skip the first `start` frames (the command name) of the array.
+    pub fn new(value: RespArray, start: usize) -> Result<Self, CommandError> {
+        Ok(CommandArgs {
+            args: extract_args(value, start)?.into_iter(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All the remaining arguments as strings.
+    pub fn rest_strings(self) -> Result<Vec<String>, CommandError> {
+        self.args.map(extract_string).collect()
+    }
+
+    pub fn next_frame(&mut self) -> Result<RespFrame, CommandError> {
+        self.args.next().ok_or_else(syntax_error)
+    }
+
+    pub fn next_string(&mut self) -> Result<String, CommandError> {
+        extract_string(self.next_frame()?)
+    }
+
+    pub fn next_bytes(&mut self) -> Result<Vec<u8>, CommandError> {
+        extract_bytes(self.next_frame()?)
+    }
+
+    pub fn next_integer(&mut self) -> Result<i64, CommandError> {
+        extract_integer(self.next_frame()?)
+    }
+
+    pub fn next_float(&mut self) -> Result<f64, CommandError> {
+        extract_float(self.next_frame()?)
+    }
+
+    /// The next argument as a lowercase keyword without consuming it.
+    pub fn peek_option(&self) -> Option<String> {
+        match self.args.as_slice().first() {
+            Some(RespFrame::BulkString(s)) => Some(String::from_utf8_lossy(s).to_ascii_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// The next argument as a lowercase keyword, None if there are no more arguments.
+    pub fn next_option(&mut self) -> Result<Option<String>, CommandError> {
+        match self.args.next() {
+            Some(frame) => Ok(Some(extract_string(frame)?.to_ascii_lowercase())),
+            None => Ok(None),
+        }
+    }
+}
+
+pub fn syntax_error() -> CommandError {
+    CommandError::SyntaxError
+}
+
+/// The arity error, also for the argument counts the command table can't check,
+/// e.g. the odd number of arguments of MSET.
+pub fn wrong_arity(name: &str) -> CommandError {
+    CommandError::InvalidArgument(format!("wrong number of arguments for '{}' command", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BulkString;
+    use anyhow::Result;
+
+    #[test]
+    fn test_command_args() -> Result<()> {
+        let value = RespArray::new([
+            BulkString::new("set").into(),
+            BulkString::new("hello").into(),
+            BulkString::new("EX").into(),
+            BulkString::new("10").into(),
+        ]);
+        let mut args = CommandArgs::new(value, 1)?;
+        assert_eq!(args.len(), 3);
+        assert_eq!(args.next_string()?, "hello");
+        assert_eq!(args.next_option()?, Some("ex".to_string()));
+        assert_eq!(args.next_integer()?, 10);
+        assert!(args.is_empty());
+        assert_eq!(args.next_option()?, None);
+        assert!(args.next_frame().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BitUnit, RespArray, RespFrame,
+};
+
+use super::next_bit_unit;
+
+// bitcount: https://redis.io/docs/latest/commands/bitcount/
+// BITCOUNT key [start end [BYTE | BIT]]
+
+#[derive(Debug)]
+pub struct BitCount {
+    key: String,
+    range: Option<(i64, i64, BitUnit)>,
+}
+
+impl CommandExecutor for BitCount {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.bitcount(&self.key, self.range) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for BitCount {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        // a start needs an end
+        if value.len() == 3 || value.len() > 5 {
+            return Err(syntax_error());
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let range = match args.is_empty() {
+            true => None,
+            false => {
+                let start = args.next_integer()?;
+                let end = args.next_integer()?;
+                Some((start, end, next_bit_unit(&mut args)?))
+            }
+        };
+        Ok(BitCount { key, range })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_bitcount_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$8\r\nbitcount\r\n$3\r\nkey\r\n$1\r\n5\r\n$2\r\n30\r\n$3\r\nBIT\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BitCount = frame.try_into()?;
+        assert_eq!(result.key, "key");
+        assert_eq!(result.range, Some((5, 30, BitUnit::Bit)));
+
+        let backend = Backend::new();
+        backend.set("key".to_string(), "foobar");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(17));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BitOverflow, BitfieldOp, BitfieldType, RespArray, RespFrame, RespNull,
+};
+
+use super::MAX_BIT_OFFSET;
+
+// bitfield: https://redis.io/docs/latest/commands/bitfield/
+// BITFIELD key [GET encoding offset | [OVERFLOW <WRAP | SAT | FAIL>]
+//   <SET encoding offset value | INCRBY encoding offset increment>
+//   [GET encoding offset | [OVERFLOW <WRAP | SAT | FAIL>]
+//   <SET encoding offset value | INCRBY encoding offset increment> ...]]
+
+#[derive(Debug)]
+pub struct BitField {
+    key: String,
+    ops: Vec<BitfieldOp>,
+}
+
+impl CommandExecutor for BitField {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.bitfield(&self.key, &self.ops) {
+            Ok(values) => {
+                let frames = values
+                    .into_iter()
+                    .map(|value| match value {
+                        Some(value) => value.into(),
+                        None => RespFrame::Null(RespNull),
+                    })
+                    .collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+/// Parse the encoding, `i1` to `i64` or `u1` to `u63`.
+fn parse_type(encoding: &str) -> Result<BitfieldType, CommandError> {
+    let ty = match encoding.split_at_checked(1) {
+        Some(("i", bits)) => bits.parse().ok().map(|bits| (true, bits)),
+        Some(("u", bits)) => bits.parse().ok().map(|bits| (false, bits)),
+        _ => None,
+    };
+    match ty {
+        Some((signed @ true, bits @ 1..=64)) | Some((signed @ false, bits @ 1..=63)) => {
+            Ok(BitfieldType { signed, bits })
+        }
+        _ => Err(CommandError::InvalidArgument(
+            "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is."
+                .to_string(),
+        )),
+    }
+}
+
+/// Parse the offset in bits, `#N` means the N-th field of the type width.
+fn parse_offset(offset: &str, ty: BitfieldType) -> Result<usize, CommandError> {
+    let offset = match offset.strip_prefix('#') {
+        Some(index) => index
+            .parse::<i64>()
+            .ok()
+            .and_then(|index| index.checked_mul(ty.bits as i64)),
+        None => offset.parse::<i64>().ok(),
+    };
+    match offset {
+        Some(offset) if offset >= 0 && offset + ty.bits as i64 - 1 <= MAX_BIT_OFFSET => {
+            Ok(offset as usize)
+        }
+        _ => Err(CommandError::InvalidArgument(
+            "bit offset is not an integer or out of range".to_string(),
+        )),
+    }
+}
+
+impl TryFrom<RespArray> for BitField {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let mut ops = Vec::new();
+        let mut overflow = BitOverflow::Wrap;
+        while let Some(option) = args.next_option()? {
+            if option == "overflow" {
+                overflow = match args.next_option()?.as_deref() {
+                    Some("wrap") => BitOverflow::Wrap,
+                    Some("sat") => BitOverflow::Sat,
+                    Some("fail") => BitOverflow::Fail,
+                    _ => {
+                        return Err(CommandError::InvalidArgument(
+                            "Invalid OVERFLOW type specified".to_string(),
+                        ))
+                    }
+                };
+                continue;
+            }
+
+            let ty = parse_type(&args.next_string()?)?;
+            let offset = parse_offset(&args.next_string()?, ty)?;
+            ops.push(match option.as_str() {
+                "get" => BitfieldOp::Get { ty, offset },
+                "set" => BitfieldOp::Set {
+                    ty,
+                    offset,
+                    value: args.next_integer()?,
+                    overflow,
+                },
+                "incrby" => BitfieldOp::IncrBy {
+                    ty,
+                    offset,
+                    increment: args.next_integer()?,
+                    overflow,
+                },
+                _ => return Err(syntax_error()),
+            });
+        }
+        Ok(BitField { key, ops })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_bitfield_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*11\r\n$8\r\nbitfield\r\n$3\r\nkey\r\n$6\r\nINCRBY\r\n$2\r\nu2\r\n$3\r\n100\r\n$1\r\n1\r\n\
+              $8\r\nOVERFLOW\r\n$3\r\nSAT\r\n$3\r\nGET\r\n$2\r\ni8\r\n$2\r\n#1\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BitField = frame.try_into()?;
+        assert_eq!(result.key, "key");
+        let u2 = BitfieldType {
+            signed: false,
+            bits: 2,
+        };
+        let i8 = BitfieldType {
+            signed: true,
+            bits: 8,
+        };
+        assert_eq!(
+            result.ops,
+            vec![
+                BitfieldOp::IncrBy {
+                    ty: u2,
+                    offset: 100,
+                    increment: 1,
+                    overflow: BitOverflow::Wrap,
+                },
+                BitfieldOp::Get { ty: i8, offset: 8 },
+            ]
+        );
+
+        let backend = Backend::new();
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([RespFrame::Integer(1), RespFrame::Integer(0)]).into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitfield_invalid_type() {
+        let frame = RespArray::new([
+            BulkString::new("bitfield").into(),
+            BulkString::new("key").into(),
+            BulkString::new("get").into(),
+            BulkString::new("u64").into(),
+            BulkString::new("0").into(),
+        ]);
+        assert!(BitField::try_from(frame).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BitwiseOp, RespArray, RespFrame,
+};
+
+// bitop: https://redis.io/docs/latest/commands/bitop/
+// BITOP <AND | OR | XOR | NOT> destkey key [key ...]
+
+#[derive(Debug)]
+pub struct BitOp {
+    op: BitwiseOp,
+    destination: String,
+    keys: Vec<String>,
+}
+
+impl CommandExecutor for BitOp {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.bitop(self.op, &self.destination, &self.keys) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for BitOp {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let op = match args.next_option()?.as_deref() {
+            Some("and") => BitwiseOp::And,
+            Some("or") => BitwiseOp::Or,
+            Some("xor") => BitwiseOp::Xor,
+            Some("not") => BitwiseOp::Not,
+            _ => return Err(syntax_error()),
+        };
+        let destination = args.next_string()?;
+        let keys = args.rest_strings()?;
+        if op == BitwiseOp::Not && keys.len() != 1 {
+            return Err(CommandError::InvalidArgument(
+                "BITOP NOT must be called with a single source key.".to_string(),
+            ));
+        }
+        Ok(BitOp {
+            op,
+            destination,
+            keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_bitop_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$5\r\nbitop\r\n$2\r\nOR\r\n$4\r\ndest\r\n$1\r\na\r\n$1\r\nb\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BitOp = frame.try_into()?;
+        assert_eq!(result.op, BitwiseOp::Or);
+        assert_eq!(result.destination, "dest");
+        assert_eq!(result.keys, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.set("a".to_string(), vec![0x0f]);
+        backend.set("b".to_string(), vec![0xf0, 0x01]);
+        assert_eq!(result.execute(&backend), RespFrame::Integer(2));
+        assert_eq!(backend.get("dest")?, Some(vec![0xff, 0x01]));
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$5\r\nbitop\r\n$3\r\nNOT\r\n$4\r\ndest\r\n$1\r\na\r\n$1\r\nb\r\n",
+        );
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(BitOp::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BitUnit, RespArray, RespFrame,
+};
+
+use super::{next_bit, next_bit_unit};
+
+// bitpos: https://redis.io/docs/latest/commands/bitpos/
+// BITPOS key bit [start [end [BYTE | BIT]]]
+
+#[derive(Debug)]
+pub struct BitPos {
+    key: String,
+    bit: bool,
+    start: Option<i64>,
+    end: Option<i64>,
+    unit: BitUnit,
+}
+
+impl CommandExecutor for BitPos {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.bitpos(&self.key, self.bit, self.start, self.end, self.unit) {
+            Ok(pos) => pos.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for BitPos {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        if value.len() > 6 {
+            return Err(syntax_error());
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let bit = next_bit(&mut args)?;
+        let start = match args.is_empty() {
+            true => None,
+            false => Some(args.next_integer()?),
+        };
+        let end = match args.is_empty() {
+            true => None,
+            false => Some(args.next_integer()?),
+        };
+        Ok(BitPos {
+            key,
+            bit,
+            start,
+            end,
+            unit: next_bit_unit(&mut args)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_bitpos_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nbitpos\r\n$3\r\nkey\r\n$1\r\n0\r\n$1\r\n1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BitPos = frame.try_into()?;
+        assert_eq!(result.key, "key");
+        assert!(!result.bit);
+        assert_eq!(result.start, Some(1));
+        assert_eq!(result.end, None);
+        assert_eq!(result.unit, BitUnit::Byte);
+
+        let backend = Backend::new();
+        backend.set("key".to_string(), vec![0xff, 0xf0, 0x00]);
+        assert_eq!(result.execute(&backend), RespFrame::Integer(12));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+use super::next_bit_offset;
+
+// getbit: https://redis.io/docs/latest/commands/getbit/
+// GETBIT key offset
+
+#[derive(Debug)]
+pub struct GetBit {
+    key: String,
+    offset: usize,
+}
+
+impl CommandExecutor for GetBit {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.getbit(&self.key, self.offset) {
+            Ok(bit) => (bit as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GetBit {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GetBit {
+            key: args.next_string()?,
+            offset: next_bit_offset(&mut args)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_getbit_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\ngetbit\r\n$3\r\nkey\r\n$1\r\n1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GetBit = frame.try_into()?;
+        assert_eq!(result.key, "key");
+        assert_eq!(result.offset, 1);
+
+        let backend = Backend::new();
+        backend.set("key".to_string(), "@");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
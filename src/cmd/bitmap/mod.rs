@@ -0,0 +1,55 @@
+mod bitcount;
+mod bitfield;
+mod bitop;
+mod bitpos;
+mod getbit;
+mod setbit;
+
+pub(crate) use bitcount::BitCount;
+pub(crate) use bitfield::BitField;
+pub(crate) use bitop::BitOp;
+pub(crate) use bitpos::BitPos;
+pub(crate) use getbit::GetBit;
+pub(crate) use setbit::SetBit;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError},
+    BitUnit,
+};
+
+/// A string is at most 512MB, so is the bit offset at most 2^32 - 1.
+const MAX_BIT_OFFSET: i64 = (1 << 32) - 1;
+
+/// The next argument as a bit offset.
+fn next_bit_offset(args: &mut CommandArgs) -> Result<usize, CommandError> {
+    match args.next_integer() {
+        Ok(offset) if (0..=MAX_BIT_OFFSET).contains(&offset) => Ok(offset as usize),
+        _ => Err(CommandError::InvalidArgument(
+            "bit offset is not an integer or out of range".to_string(),
+        )),
+    }
+}
+
+/// The next argument as a bit, 0 or 1.
+fn next_bit(args: &mut CommandArgs) -> Result<bool, CommandError> {
+    match args.next_integer() {
+        Ok(0) => Ok(false),
+        Ok(1) => Ok(true),
+        _ => Err(CommandError::InvalidArgument(
+            "The bit argument must be 1 or 0.".to_string(),
+        )),
+    }
+}
+
+/// The optional BYTE | BIT unit of the range, the last argument.
+fn next_bit_unit(args: &mut CommandArgs) -> Result<BitUnit, CommandError> {
+    let unit = match args.next_option()?.as_deref() {
+        None | Some("byte") => BitUnit::Byte,
+        Some("bit") => BitUnit::Bit,
+        Some(_) => return Err(syntax_error()),
+    };
+    match args.is_empty() {
+        true => Ok(unit),
+        false => Err(syntax_error()),
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+use super::next_bit_offset;
+
+// setbit: https://redis.io/docs/latest/commands/setbit/
+// SETBIT key offset value
+
+#[derive(Debug)]
+pub struct SetBit {
+    key: String,
+    offset: usize,
+    bit: bool,
+}
+
+impl CommandExecutor for SetBit {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.setbit(&self.key, self.offset, self.bit) {
+            Ok(old) => (old as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SetBit {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let offset = next_bit_offset(&mut args)?;
+        let bit = match args.next_integer() {
+            Ok(0) => false,
+            Ok(1) => true,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "bit is not an integer or out of range".to_string(),
+                ))
+            }
+        };
+        Ok(SetBit { key, offset, bit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_setbit_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nsetbit\r\n$3\r\nkey\r\n$2\r\n10\r\n$1\r\n1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SetBit = frame.try_into()?;
+        assert_eq!(result.key, "key");
+        assert_eq!(result.offset, 10);
+        assert!(result.bit);
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(0));
+        assert_eq!(backend.get("key")?, Some(vec![0x00, 0x20]));
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nsetbit\r\n$3\r\nkey\r\n$2\r\n-1\r\n$1\r\n1\r\n");
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(SetBit::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{
+    cmd::{connection_only, syntax_error, CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, BulkString, ClientInfo, ClientKillFilter, RespArray, RespFrame, RespNull, Session,
+    SimpleError,
+};
+
+// client: https://redis.io/docs/latest/commands/client/
+// CLIENT ID
+// CLIENT SETNAME connection-name
+// CLIENT GETNAME
+// CLIENT LIST [TYPE <NORMAL | PUBSUB>] [ID client-id [client-id ...]]
+// CLIENT INFO
+// CLIENT KILL <ip:port | <[ID client-id] | [ADDR ip:port] | [LADDR ip:port] |
+//   [USER username] | [SKIPME <yes | no>]> [...]>
+// CLIENT NO-EVICT <ON | OFF>
+
+#[derive(Debug, PartialEq)]
+enum ClientOp {
+    Id,
+    SetName(String),
+    GetName,
+    List {
+        pubsub: Option<bool>, // Some for TYPE, whether to list the pubsub connections
+        ids: Vec<u64>,
+    },
+    Info,
+    KillAddr(String), // the old form, an error if there is no such client
+    Kill {
+        filter: ClientKillFilter,
+        skipme: bool, // whether the caller is not killed, yes by default
+    },
+    NoEvict(bool),
+}
+
+#[derive(Debug)]
+pub struct Client {
+    op: ClientOp,
+}
+
+/// A line of CLIENT LIST, the fields like redis.
+fn info_line(info: &ClientInfo) -> String {
+    let state = &info.state;
+    let mut flags = String::new();
+    if state.multi.is_some() {
+        flags.push('x');
+    }
+    if state.sub + state.psub + state.ssub > 0 {
+        flags.push('P');
+    }
+    if state.no_evict {
+        flags.push('e');
+    }
+    if flags.is_empty() {
+        flags.push('N');
+    }
+    format!(
+        "id={} addr={} laddr={} name={} age={} idle={} flags={} db={} sub={} psub={} ssub={} multi={} watch={} cmd={} user={} resp={}\n",
+        info.id,
+        info.addr,
+        info.laddr,
+        state.name.as_deref().unwrap_or_default(),
+        info.age,
+        info.idle,
+        flags,
+        state.db,
+        state.sub,
+        state.psub,
+        state.ssub,
+        state.multi.map_or(-1, |n| n as i64),
+        state.watch,
+        state.last_command,
+        info.user,
+        state.protocol,
+    )
+}
+
+/// A client name is a single word of printable chars, see CLIENT SETNAME and HELLO.
+pub(super) fn check_client_name(name: &str) -> Result<(), CommandError> {
+    if name.bytes().any(|b| !(b'!'..=b'~').contains(&b)) {
+        return Err(CommandError::InvalidArgument(
+            "Client names cannot contain spaces, newlines or special characters.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl Client {
+    pub fn execute_session(self, session: &Session, backend: &Backend) -> RespFrame {
+        match self.op {
+            ClientOp::Id => (session.id() as i64).into(),
+            ClientOp::SetName(name) => {
+                session.update(|state| state.name = (!name.is_empty()).then_some(name));
+                RESP_OK.clone()
+            }
+            ClientOp::GetName => match session.state().name {
+                Some(name) => BulkString::new(name).into(),
+                None => RespFrame::Null(RespNull),
+            },
+            ClientOp::List { pubsub, ids } => {
+                let list = backend
+                    .client_list()
+                    .iter()
+                    .filter(|info| ids.is_empty() || ids.contains(&info.id))
+                    .filter(|info| {
+                        let state = &info.state;
+                        pubsub.is_none_or(|p| p == (state.sub + state.psub + state.ssub > 0))
+                    })
+                    .map(info_line)
+                    .collect::<String>();
+                BulkString::new(list).into()
+            }
+            ClientOp::Info => BulkString::new(info_line(&session.info())).into(),
+            ClientOp::KillAddr(addr) => {
+                let filter = ClientKillFilter {
+                    addr: Some(addr),
+                    ..Default::default()
+                };
+                match backend.client_kill(&filter) {
+                    0 => SimpleError::new("ERR No such client".to_string()).into(),
+                    _ => RESP_OK.clone(),
+                }
+            }
+            ClientOp::Kill { mut filter, skipme } => {
+                filter.skip = skipme.then(|| session.id());
+                backend.client_kill(&filter).into()
+            }
+            ClientOp::NoEvict(on) => {
+                session.update(|state| state.no_evict = on);
+                RESP_OK.clone()
+            }
+        }
+    }
+}
+
+impl CommandExecutor for Client {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("CLIENT")
+    }
+}
+
+fn parse_id(args: &mut CommandArgs) -> Result<u64, CommandError> {
+    match args.next_integer()? {
+        id if id > 0 => Ok(id as u64),
+        _ => Err(CommandError::InvalidArgument(
+            "client-id should be greater than 0".to_string(),
+        )),
+    }
+}
+
+fn parse_kill(args: &mut CommandArgs) -> Result<ClientOp, CommandError> {
+    let (mut filter, mut skipme) = (ClientKillFilter::default(), true);
+    while let Some(option) = args.next_option()? {
+        match option.as_str() {
+            "id" => filter.id = Some(parse_id(args)?),
+            "addr" => filter.addr = Some(args.next_string()?),
+            "laddr" => filter.laddr = Some(args.next_string()?),
+            "user" => filter.user = Some(args.next_string()?),
+            "skipme" => {
+                skipme = match args.next_option()?.as_deref() {
+                    Some("yes") => true,
+                    Some("no") => false,
+                    _ => return Err(syntax_error()),
+                }
+            }
+            _ => return Err(syntax_error()),
+        }
+    }
+    Ok(ClientOp::Kill { filter, skipme })
+}
+
+impl TryFrom<RespArray> for Client {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let subcommand = args.next_option()?.unwrap_or_default();
+        let op = match (subcommand.as_str(), args.len()) {
+            ("id", 0) => ClientOp::Id,
+            ("setname", 1) => {
+                let name = args.next_string()?;
+                check_client_name(&name)?;
+                ClientOp::SetName(name)
+            }
+            ("getname", 0) => ClientOp::GetName,
+            ("list", _) => {
+                let (mut pubsub, mut ids) = (None, Vec::new());
+                while let Some(option) = args.next_option()? {
+                    match option.as_str() {
+                        "type" => {
+                            pubsub = match args.next_option()?.as_deref() {
+                                Some("normal") => Some(false),
+                                Some("pubsub") => Some(true),
+                                Some(ty) => {
+                                    return Err(CommandError::InvalidArgument(format!(
+                                        "Unknown client type '{}'",
+                                        ty
+                                    )))
+                                }
+                                None => return Err(syntax_error()),
+                            }
+                        }
+                        "id" => {
+                            while !args.is_empty() {
+                                ids.push(parse_id(&mut args)?);
+                            }
+                        }
+                        _ => return Err(syntax_error()),
+                    }
+                }
+                ClientOp::List { pubsub, ids }
+            }
+            ("info", 0) => ClientOp::Info,
+            ("kill", 1) => ClientOp::KillAddr(args.next_string()?),
+            ("kill", n) if n > 0 && n.is_multiple_of(2) => parse_kill(&mut args)?,
+            ("no-evict", 1) => match args.next_option()?.as_deref() {
+                Some("on") => ClientOp::NoEvict(true),
+                Some("off") => ClientOp::NoEvict(false),
+                _ => return Err(syntax_error()),
+            },
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand or wrong number of arguments for '{}'. Try CLIENT HELP.",
+                    subcommand
+                )))
+            }
+        };
+        Ok(Client { op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn client(buf: &[u8]) -> Result<Client> {
+        let mut buf = BytesMut::from(buf);
+        Ok(RespArray::decode(&mut buf)?.try_into()?)
+    }
+
+    #[test]
+    fn test_client_name_and_list() -> Result<()> {
+        let backend = Backend::new();
+        let session = backend.new_session("127.0.0.1:50001", "127.0.0.1:6379");
+
+        let setname = client(b"*3\r\n$6\r\nclient\r\n$7\r\nSETNAME\r\n$6\r\nworker\r\n")?;
+        assert_eq!(setname.op, ClientOp::SetName("worker".to_string()));
+        assert_eq!(setname.execute_session(&session, &backend), RESP_OK.clone());
+        let getname = client(b"*2\r\n$6\r\nclient\r\n$7\r\nGETNAME\r\n")?;
+        assert_eq!(
+            getname.execute_session(&session, &backend),
+            BulkString::new("worker").into()
+        );
+
+        let list = client(b"*2\r\n$6\r\nclient\r\n$4\r\nLIST\r\n")?;
+        let RespFrame::BulkString(list) = list.execute_session(&session, &backend) else {
+            panic!("expected a bulk string");
+        };
+        let list = String::from_utf8(list.0)?;
+        assert!(list.starts_with(&format!(
+            "id={} addr=127.0.0.1:50001 laddr=127.0.0.1:6379 name=worker ",
+            session.id()
+        )));
+        assert!(list.ends_with(
+            " flags=N db=0 sub=0 psub=0 ssub=0 multi=-1 watch=0 cmd=NULL user=default resp=2\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_kill() -> Result<()> {
+        let backend = Backend::new();
+        let session = backend.new_session("127.0.0.1:50001", "127.0.0.1:6379");
+        let other = backend.new_session("127.0.0.1:50002", "127.0.0.1:6379");
+
+        let kill = client(b"*4\r\n$6\r\nclient\r\n$4\r\nKILL\r\n$4\r\nUSER\r\n$7\r\ndefault\r\n")?;
+        assert_eq!(
+            kill.execute_session(&session, &backend),
+            RespFrame::Integer(1)
+        );
+        assert!(other.is_killed());
+        assert!(!session.is_killed());
+
+        let id = session.id();
+        let kill = client(
+            format!(
+                "*6\r\n$6\r\nclient\r\n$4\r\nKILL\r\n$2\r\nID\r\n${}\r\n{}\r\n$6\r\nSKIPME\r\n$2\r\nno\r\n",
+                id.to_string().len(),
+                id
+            )
+            .as_bytes(),
+        )?;
+        assert_eq!(
+            kill.op,
+            ClientOp::Kill {
+                filter: ClientKillFilter {
+                    id: Some(id),
+                    ..Default::default()
+                },
+                skipme: false,
+            }
+        );
+        assert_eq!(
+            kill.execute_session(&session, &backend),
+            RespFrame::Integer(1)
+        );
+        assert!(session.is_killed());
+
+        let kill = client(b"*3\r\n$6\r\nclient\r\n$4\r\nKILL\r\n$15\r\n127.0.0.1:50003\r\n")?;
+        assert_eq!(
+            kill.execute_session(&session, &backend),
+            SimpleError::new("ERR No such client".to_string()).into()
+        );
+
+        Ok(())
+    }
+}
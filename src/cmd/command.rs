@@ -1,14 +1,51 @@
 use enum_dispatch::enum_dispatch;
 use thiserror::Error;
 
-use crate::{RespArray, RespError, RespFrame};
+use crate::{Backend, RespArray, RespError, RespFrame, SimpleError};
 
 use super::{
+    bitmap::{BitCount, BitField, BitOp, BitPos, GetBit, SetBit},
+    client::Client,
+    commands::Commands,
     echo::Echo,
-    hmap::{HGet, HGetAll, HMGet, HSet},
-    map::{Get, Set},
-    set::{SAdd, SIsMember},
+    geo::{GeoAdd, GeoDist, GeoHash, GeoPos, GeoSearch, GeoSearchStore},
+    hello::Hello,
+    hmap::{
+        HDel, HExists, HGet, HGetAll, HIncrBy, HIncrByFloat, HKeys, HLen, HMGet, HRandField, HScan,
+        HSet, HSetNx, HStrLen, HVals,
+    },
+    hyperloglog::{PfAdd, PfCount, PfMerge},
+    info::Info,
+    key::{
+        Copy, DbSize, Del, Exists, Expire, ExpireAt, ExpireTime, FlushAll, FlushDb, Keys, PExpire,
+        PExpireAt, PExpireTime, PTtl, Persist, Rename, RenameNx, Scan, Ttl, Type, Unlink,
+    },
+    list::{
+        BLMove, BLPop, BRPop, LIndex, LInsert, LLen, LMove, LPop, LPush, LPushX, LRange, LRem,
+        LSet, LTrim, RPop, RPush, RPushX,
+    },
+    map::{
+        Append, Decr, DecrBy, Get, GetDel, GetEx, GetRange, GetSet, Incr, IncrBy, IncrByFloat,
+        MGet, MSet, MSetNx, Set, SetRange, StrLen,
+    },
+    ping::Ping,
+    pubsub::{
+        PSubscribe, PUnsubscribe, PubSub, Publish, SPublish, SSubscribe, SUnsubscribe, Subscribe,
+        Unsubscribe,
+    },
+    set::{
+        SAdd, SCard, SDiff, SDiffStore, SInter, SInterCard, SInterStore, SIsMember, SMIsMember,
+        SMembers, SMove, SPop, SRandMember, SRem, SScan, SUnion, SUnionStore,
+    },
+    stream::{
+        XAck, XAdd, XAutoClaim, XClaim, XDel, XGroup, XInfo, XLen, XPending, XRange, XRead,
+        XReadGroup, XRevRange, XTrim,
+    },
+    table::lookup_command,
+    transaction::{Discard, Exec, Multi, Unwatch, Watch},
     unrecognized::Unrecognized,
+    zset::{ZAdd, ZCard, ZIncrBy, ZPopMax, ZPopMin, ZRange, ZRangeByScore, ZRank, ZRem, ZScore},
+    CommandExecutor,
 };
 
 #[enum_dispatch(CommandExecutor)]
@@ -23,41 +60,214 @@ pub enum Command {
     HMGet(HMGet),
     SAdd(SAdd),
     SIsMember(SIsMember), // S 表示 Set
+    Expire(Expire),
+    PExpire(PExpire),
+    ExpireAt(ExpireAt),
+    PExpireAt(PExpireAt),
+    Ttl(Ttl),
+    PTtl(PTtl),
+    ExpireTime(ExpireTime),
+    PExpireTime(PExpireTime),
+    Persist(Persist),
+    Type(Type),
+    Del(Del),
+    Unlink(Unlink),
+    Exists(Exists),
+    Rename(Rename),
+    RenameNx(RenameNx),
+    Copy(Copy),
+    DbSize(DbSize),
+    FlushDb(FlushDb),
+    FlushAll(FlushAll),
+    Scan(Scan),
+    Keys(Keys),
+    HScan(HScan),
+    SScan(SScan),
+    LPush(LPush),
+    RPush(RPush),
+    LPushX(LPushX),
+    RPushX(RPushX),
+    LPop(LPop),
+    RPop(RPop),
+    LRange(LRange),
+    LIndex(LIndex),
+    LSet(LSet),
+    LRem(LRem),
+    LTrim(LTrim),
+    LInsert(LInsert),
+    LLen(LLen),
+    LMove(LMove),
+    BLPop(BLPop),
+    BRPop(BRPop),
+    BLMove(BLMove),
+    ZAdd(ZAdd),
+    ZCard(ZCard),
+    ZIncrBy(ZIncrBy),
+    ZPopMin(ZPopMin),
+    ZPopMax(ZPopMax),
+    ZRange(ZRange),
+    ZRangeByScore(ZRangeByScore),
+    ZRank(ZRank),
+    ZRem(ZRem),
+    ZScore(ZScore),
+    SCard(SCard),
+    SDiff(SDiff),
+    SDiffStore(SDiffStore),
+    SInter(SInter),
+    SInterStore(SInterStore),
+    SUnion(SUnion),
+    SUnionStore(SUnionStore),
+    SInterCard(SInterCard),
+    SMembers(SMembers),
+    SMIsMember(SMIsMember),
+    SMove(SMove),
+    SPop(SPop),
+    SRandMember(SRandMember),
+    SRem(SRem),
+    HDel(HDel),
+    HExists(HExists),
+    HIncrBy(HIncrBy),
+    HIncrByFloat(HIncrByFloat),
+    HKeys(HKeys),
+    HVals(HVals),
+    HLen(HLen),
+    HRandField(HRandField),
+    HSetNx(HSetNx),
+    HStrLen(HStrLen),
+    Append(Append),
+    GetDel(GetDel),
+    GetEx(GetEx),
+    GetRange(GetRange),
+    GetSet(GetSet),
+    Incr(Incr),
+    Decr(Decr),
+    IncrBy(IncrBy),
+    DecrBy(DecrBy),
+    IncrByFloat(IncrByFloat),
+    MGet(MGet),
+    MSet(MSet),
+    MSetNx(MSetNx),
+    SetRange(SetRange),
+    StrLen(StrLen),
+    SetBit(SetBit),
+    GetBit(GetBit),
+    BitCount(BitCount),
+    BitPos(BitPos),
+    BitOp(BitOp),
+    BitField(BitField),
+    PfAdd(PfAdd),
+    PfCount(PfCount),
+    PfMerge(PfMerge),
+    GeoAdd(GeoAdd),
+    GeoDist(GeoDist),
+    GeoHash(GeoHash),
+    GeoPos(GeoPos),
+    GeoSearch(GeoSearch),
+    GeoSearchStore(GeoSearchStore),
+    XAdd(XAdd),
+    XDel(XDel),
+    XLen(XLen),
+    XRange(XRange),
+    XRevRange(XRevRange),
+    XRead(XRead),
+    XTrim(XTrim),
+    XGroup(XGroup),
+    XReadGroup(XReadGroup),
+    XAck(XAck),
+    XPending(XPending),
+    XClaim(XClaim),
+    XAutoClaim(XAutoClaim),
+    XInfo(XInfo),
+    Ping(Ping),
+    Subscribe(Subscribe),
+    Unsubscribe(Unsubscribe),
+    PSubscribe(PSubscribe),
+    PUnsubscribe(PUnsubscribe),
+    Publish(Publish),
+    PubSub(PubSub),
+    SSubscribe(SSubscribe),
+    SUnsubscribe(SUnsubscribe),
+    SPublish(SPublish),
+    Multi(Multi),
+    Exec(Exec),
+    Discard(Discard),
+    Watch(Watch),
+    Unwatch(Unwatch),
+    Client(Client),
+    Info(Info),
+    Commands(Commands),
+    Hello(Hello),
     // unrecognized command
     Unrecognized(Unrecognized),
 }
 
+impl Command {
+    /// Execute the command, the blocking commands wait for their keys asynchronously
+    /// instead of blocking the thread.
+    pub async fn execute_async(self, backend: &Backend) -> RespFrame {
+        match self {
+            Command::BLPop(cmd) => cmd.execute_blocking(backend).await,
+            Command::BRPop(cmd) => cmd.execute_blocking(backend).await,
+            Command::BLMove(cmd) => cmd.execute_blocking(backend).await,
+            Command::XRead(cmd) => cmd.execute_blocking(backend).await,
+            Command::XReadGroup(cmd) => cmd.execute_blocking(backend).await,
+            cmd => {
+                let _guard = backend.lock_shared();
+                cmd.execute(backend)
+            }
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum CommandError {
     #[error("Invalid command: {0}")]
     InvalidCommand(String),
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
+    #[error("Syntax error")]
+    SyntaxError,
+    #[error("Wrong type: {0}")]
+    WrongType(String),
+    #[error("Unsupported protocol: {0}")]
+    NoProto(String),
     #[error("{0}")]
     RespError(#[from] RespError),
     #[error("Utf8 error: {0}")]
     Utf8Error(#[from] std::string::FromUtf8Error),
 }
 
+/// A command which can't be parsed is replied as an error with a redis style
+/// prefix, the connection stays open.
+impl From<CommandError> for RespFrame {
+    fn from(e: CommandError) -> Self {
+        let reply = match e {
+            CommandError::InvalidCommand(msg) | CommandError::InvalidArgument(msg) => {
+                format!("ERR {}", msg)
+            }
+            CommandError::SyntaxError => "SYNTAXERR syntax error".to_string(),
+            CommandError::WrongType(msg) => format!("WRONGTYPE {}", msg),
+            CommandError::NoProto(msg) => format!("NOPROTO {}", msg),
+            e @ (CommandError::RespError(_) | CommandError::Utf8Error(_)) => format!("ERR {}", e),
+        };
+        SimpleError::new(reply).into()
+    }
+}
+
 impl TryFrom<RespArray> for Command {
     type Error = CommandError;
     fn try_from(v: RespArray) -> Result<Self, Self::Error> {
-        match v.first() {
-            Some(RespFrame::BulkString(ref cmd)) => match cmd.as_ref() {
-                b"get" => Ok(Get::try_from(v)?.into()),
-                b"set" => Ok(Set::try_from(v)?.into()),
-                b"hget" => Ok(HGet::try_from(v)?.into()),
-                b"hset" => Ok(HSet::try_from(v)?.into()),
-                b"hgetall" => Ok(HGetAll::try_from(v)?.into()),
-                b"echo" => Ok(Echo::try_from(v)?.into()),
-                b"hmget" => Ok(HMGet::try_from(v)?.into()),
-                b"sadd" => Ok(SAdd::try_from(v)?.into()),
-                b"sismember" => Ok(SIsMember::try_from(v)?.into()),
-                _ => Ok(Unrecognized.into()),
-            },
-            _ => Err(CommandError::InvalidCommand(
-                "Command must have a BulkString as the first argument".to_string(),
-            )),
+        let spec = match v.first() {
+            Some(RespFrame::BulkString(ref cmd)) => lookup_command(cmd.as_ref()),
+            _ => {
+                return Err(CommandError::InvalidCommand(
+                    "Command must have a BulkString as the first argument".to_string(),
+                ))
+            }
+        };
+        match spec {
+            Some(spec) => spec.parse(v),
+            None => Ok(Unrecognized::try_from(v)?.into()),
         }
     }
 }
@@ -73,3 +283,29 @@ impl TryFrom<RespFrame> for Command {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BulkString;
+
+    #[test]
+    fn test_command_error_reply() {
+        let frame = RespArray::new([BulkString::new("get").into()]);
+        let err = Command::try_from(frame).unwrap_err();
+        assert_eq!(
+            RespFrame::from(err),
+            SimpleError::new("ERR wrong number of arguments for 'get' command").into()
+        );
+
+        let frame = RespFrame::from(BulkString::new("get"));
+        let err = Command::try_from(frame).unwrap_err();
+        assert_eq!(
+            RespFrame::from(err),
+            SimpleError::new("ERR Command must be an Array").into()
+        );
+
+        let reply: RespFrame = CommandError::SyntaxError.into();
+        assert_eq!(reply, SimpleError::new("SYNTAXERR syntax error").into());
+    }
+}
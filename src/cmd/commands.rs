@@ -0,0 +1,356 @@
+use crate::{
+    glob_match, Backend, BulkString, RespArray, RespFrame, RespMap, RespNull, RespSet, SimpleError,
+};
+
+use super::{
+    find_command, lookup_command, syntax_error, BeginSearch, CommandArgs, CommandError,
+    CommandExecutor, CommandFlag, CommandSpec, FindKeys, KeySpec, COMMAND_TABLE,
+};
+
+// command: https://redis.io/docs/latest/commands/command/
+// COMMAND
+// COMMAND COUNT
+// COMMAND INFO [command-name [command-name ...]]
+// COMMAND DOCS [command-name [command-name ...]]
+// COMMAND GETKEYS command [arg [arg ...]]
+// COMMAND LIST [FILTERBY <MODULE module-name | ACLCAT category | PATTERN pattern>]
+
+#[derive(Debug, PartialEq)]
+enum CommandOp {
+    Count,
+    Info(Option<Vec<String>>), // None for all the commands
+    Docs(Option<Vec<String>>),
+    GetKeys(Vec<Vec<u8>>),
+    List(Option<ListFilter>),
+}
+
+#[derive(Debug, PartialEq)]
+enum ListFilter {
+    Module, // there are no modules, nothing is listed
+    AclCat(String),
+    Pattern(String),
+}
+
+/// The COMMAND command, it reports the entries of the command table.
+#[derive(Debug)]
+pub struct Commands {
+    op: CommandOp,
+}
+
+fn simple_strings(names: impl IntoIterator<Item = impl AsRef<str>>) -> Vec<RespFrame> {
+    names.into_iter().map(|name| name.as_ref().into()).collect()
+}
+
+fn map(entries: impl IntoIterator<Item = (&'static str, RespFrame)>) -> RespFrame {
+    let mut map = RespMap::new();
+    for (key, value) in entries {
+        map.insert(key.to_string(), value);
+    }
+    map.into()
+}
+
+fn key_spec_reply(spec: &CommandSpec, key_spec: &KeySpec) -> RespFrame {
+    let flags = match spec.has_flag(CommandFlag::Write) {
+        true => ["RW", "update"],
+        false => ["RO", "access"],
+    };
+    let begin_search = match key_spec.begin_search {
+        BeginSearch::Index(index) => ("index", map([("index", index.into())])),
+        BeginSearch::Keyword {
+            keyword,
+            start_from,
+        } => (
+            "keyword",
+            map([
+                ("keyword", BulkString::new(keyword).into()),
+                ("startfrom", start_from.into()),
+            ]),
+        ),
+    };
+    let find_keys = match key_spec.find_keys {
+        FindKeys::Range {
+            last_key,
+            key_step,
+            limit,
+        } => (
+            "range",
+            map([
+                ("lastkey", last_key.into()),
+                ("keystep", key_step.into()),
+                ("limit", limit.into()),
+            ]),
+        ),
+        FindKeys::KeyNum {
+            key_num_idx,
+            first_key,
+            key_step,
+        } => (
+            "keynum",
+            map([
+                ("keynumidx", key_num_idx.into()),
+                ("firstkey", first_key.into()),
+                ("keystep", key_step.into()),
+            ]),
+        ),
+    };
+    let search = |(kind, spec): (&str, RespFrame)| {
+        map([("type", BulkString::new(kind).into()), ("spec", spec)])
+    };
+    map([
+        ("flags", RespSet::new(simple_strings(flags)).into()),
+        ("begin_search", search(begin_search)),
+        ("find_keys", search(find_keys)),
+    ])
+}
+
+/// The reply of COMMAND INFO for a command, like redis:
+/// name, arity, flags, first key, last key, step, ACL categories, tips, key specs and subcommands.
+fn info_reply(spec: &CommandSpec) -> RespFrame {
+    let flags = spec.flags.iter().map(|flag| flag.name());
+    let categories = spec.acl_categories().into_iter().map(|c| format!("@{}", c));
+    let key_specs = spec
+        .key_specs()
+        .into_iter()
+        .map(|k| key_spec_reply(spec, &k));
+    let subcommands = spec.subcommands.iter().map(info_reply);
+    RespArray::new([
+        BulkString::new(spec.name).into(),
+        spec.arity.into(),
+        RespSet::new(simple_strings(flags)).into(),
+        spec.first_key.into(),
+        spec.last_key.into(),
+        spec.step.into(),
+        RespSet::new(simple_strings(categories)).into(),
+        RespArray::new(vec![]).into(),
+        RespArray::new(key_specs.collect::<Vec<_>>()).into(),
+        RespArray::new(subcommands.collect::<Vec<_>>()).into(),
+    ])
+    .into()
+}
+
+/// The reply of COMMAND DOCS for a command, there are no summaries so only the group is documented.
+fn docs_reply(spec: &CommandSpec) -> RespFrame {
+    let mut docs = RespMap::new();
+    docs.insert("group".to_string(), BulkString::new(spec.group()).into());
+    if !spec.subcommands.is_empty() {
+        let mut subcommands = RespMap::new();
+        for subcommand in spec.subcommands {
+            subcommands.insert(subcommand.name.to_string(), docs_reply(subcommand));
+        }
+        docs.insert("subcommands".to_string(), subcommands.into());
+    }
+    docs.into()
+}
+
+/// The commands and their subcommands, by their full names.
+fn all_commands() -> impl Iterator<Item = &'static CommandSpec> {
+    COMMAND_TABLE
+        .iter()
+        .flat_map(|spec| std::iter::once(spec).chain(spec.subcommands))
+}
+
+impl CommandExecutor for Commands {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        match self.op {
+            CommandOp::Count => (COMMAND_TABLE.len() as i64).into(),
+            CommandOp::Info(None) => {
+                RespArray::new(COMMAND_TABLE.iter().map(info_reply).collect::<Vec<_>>()).into()
+            }
+            CommandOp::Info(Some(names)) => {
+                let frames = names
+                    .iter()
+                    .map(|name| match find_command(name) {
+                        Some(spec) => info_reply(spec),
+                        None => RespFrame::Null(RespNull),
+                    })
+                    .collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            CommandOp::Docs(names) => {
+                let mut docs = RespMap::new();
+                let specs = match names {
+                    Some(names) => names.iter().filter_map(|name| find_command(name)).collect(),
+                    None => COMMAND_TABLE.iter().collect::<Vec<_>>(),
+                };
+                for spec in specs {
+                    docs.insert(spec.name.to_string(), docs_reply(spec));
+                }
+                docs.into()
+            }
+            CommandOp::GetKeys(args) => get_keys(args),
+            CommandOp::List(filter) => {
+                let names = all_commands()
+                    .filter(|spec| match &filter {
+                        None => true,
+                        Some(ListFilter::Module) => false,
+                        Some(ListFilter::AclCat(category)) => {
+                            spec.acl_categories().contains(&category.as_str())
+                        }
+                        Some(ListFilter::Pattern(pattern)) => {
+                            glob_match(pattern.as_bytes(), spec.name.as_bytes())
+                        }
+                    })
+                    .map(|spec| BulkString::new(spec.name).into())
+                    .collect::<Vec<RespFrame>>();
+                RespArray::new(names).into()
+            }
+        }
+    }
+}
+
+/// The keys of a full command, errors like redis if it can't be resolved.
+fn get_keys(args: Vec<Vec<u8>>) -> RespFrame {
+    let error = |msg: &str| SimpleError::new(format!("ERR {}", msg)).into();
+    let Some(spec) = lookup_command(&args[0]) else {
+        return error("Invalid command specified");
+    };
+    let frames = args
+        .iter()
+        .map(|arg| BulkString::new(arg.clone()).into())
+        .collect::<Vec<RespFrame>>();
+    let Ok(spec) = spec.resolve(&frames) else {
+        return error("Invalid number of arguments specified for command");
+    };
+    let keys = spec.key_positions(&args);
+    if keys.is_empty() {
+        return error("The command has no key arguments");
+    }
+    let keys = keys
+        .into_iter()
+        .map(|i| frames[i].clone())
+        .collect::<Vec<_>>();
+    RespArray::new(keys).into()
+}
+
+impl TryFrom<RespArray> for Commands {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let op = match args.next_option()?.as_deref() {
+            None => CommandOp::Info(None),
+            Some("count") => CommandOp::Count,
+            Some("info") if args.is_empty() => CommandOp::Info(None),
+            Some("info") => CommandOp::Info(Some(args.rest_strings()?)),
+            Some("docs") if args.is_empty() => CommandOp::Docs(None),
+            Some("docs") => CommandOp::Docs(Some(args.rest_strings()?)),
+            Some("getkeys") => {
+                let mut keys = Vec::new();
+                while !args.is_empty() {
+                    keys.push(args.next_bytes()?);
+                }
+                CommandOp::GetKeys(keys)
+            }
+            Some("list") if args.is_empty() => CommandOp::List(None),
+            Some("list") => {
+                if args.len() != 3 || args.next_option()?.as_deref() != Some("filterby") {
+                    return Err(syntax_error());
+                }
+                let filter = match args.next_option()?.as_deref() {
+                    Some("module") => {
+                        args.next_string()?;
+                        ListFilter::Module
+                    }
+                    Some("aclcat") => ListFilter::AclCat(args.next_string()?.to_ascii_lowercase()),
+                    Some("pattern") => ListFilter::Pattern(args.next_string()?),
+                    _ => return Err(syntax_error()),
+                };
+                CommandOp::List(Some(filter))
+            }
+            Some(subcommand) => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try COMMAND HELP.",
+                    subcommand
+                )))
+            }
+        };
+        Ok(Commands { op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, RespEncode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn command(buf: &[u8]) -> Result<Commands> {
+        let mut buf = BytesMut::from(buf);
+        Ok(RespArray::decode(&mut buf)?.try_into()?)
+    }
+
+    #[test]
+    fn test_command_info() -> Result<()> {
+        let result = command(b"*4\r\n$7\r\ncommand\r\n$4\r\nINFO\r\n$3\r\nGET\r\n$3\r\nfoo\r\n")?;
+        assert_eq!(
+            result.op,
+            CommandOp::Info(Some(vec!["GET".to_string(), "foo".to_string()]))
+        );
+
+        let RespFrame::Array(infos) = result.execute(&Backend::new()) else {
+            panic!("expected an array");
+        };
+        let RespFrame::Array(get) = &infos[0] else {
+            panic!("expected an array");
+        };
+        assert_eq!(get[0], BulkString::new("get").into());
+        assert_eq!(get[1], 2.into());
+        assert_eq!(
+            get[2],
+            RespSet::new(simple_strings(["readonly", "fast"])).into()
+        );
+        assert_eq!(&get[3..6], &[1.into(), 1.into(), 1.into()]);
+        assert_eq!(
+            get[6],
+            RespSet::new(simple_strings(["@read", "@string", "@fast"])).into()
+        );
+        // a key spec is a map, a flat array in RESP2
+        let RespFrame::Array(key_specs) = &get[8] else {
+            panic!("expected an array");
+        };
+        let RespFrame::Array(key_spec) = key_specs[0].clone().into_resp2() else {
+            panic!("expected an array");
+        };
+        assert_eq!(key_spec.len(), 6);
+        assert_eq!(key_spec[0], BulkString::new("begin_search").into());
+        // no tips and no subcommands are empty arrays, not nils
+        assert_eq!(get[7].clone().encode(), b"*0\r\n");
+        assert_eq!(get[9].clone().encode(), b"*0\r\n");
+        assert_eq!(infos[1], RespFrame::Null(RespNull));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_getkeys_and_list() -> Result<()> {
+        let backend = Backend::new();
+        let result = command(
+            b"*8\r\n$7\r\ncommand\r\n$7\r\ngetkeys\r\n$5\r\nXREAD\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n\
+              $7\r\nstreams\r\n$2\r\ns1\r\n$1\r\n0\r\n",
+        )?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([BulkString::new("s1").into()]).into()
+        );
+
+        let result = command(
+            b"*5\r\n$7\r\ncommand\r\n$7\r\ngetkeys\r\n$4\r\nmset\r\n$1\r\na\r\n$1\r\n1\r\n",
+        )?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([BulkString::new("a").into()]).into()
+        );
+
+        let result = command(
+            b"*5\r\n$7\r\ncommand\r\n$4\r\nlist\r\n$8\r\nFILTERBY\r\n$7\r\npattern\r\n$8\r\nclient|*\r\n",
+        )?;
+        let RespFrame::Array(names) = result.execute(&backend) else {
+            panic!("expected an array");
+        };
+        assert_eq!(names.len(), 7);
+        assert_eq!(names[0], BulkString::new("client|id").into());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{Backend, RespArray, RespFrame};
 
-use super::{extract_args, validate_command, CommandError, CommandExecutor};
+use super::{extract_args, CommandError, CommandExecutor};
 
 // echo: https://redis.io/docs/latest/commands/echo/
 
@@ -18,8 +18,6 @@ impl CommandExecutor for Echo {
 impl TryFrom<RespArray> for Echo {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["echo"], 1)?; // validate get
-
         let mut args = extract_args(value, 1)?.into_iter();
         match args.next() {
             Some(RespFrame::BulkString(message)) => Ok(Echo {
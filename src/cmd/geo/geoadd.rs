@@ -0,0 +1,104 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, SetCondition, ZAddFlags,
+};
+
+use super::next_lonlat;
+
+// geoadd: https://redis.io/docs/latest/commands/geoadd/
+// GEOADD key [NX | XX] [CH] longitude latitude member [longitude latitude member ...]
+
+#[derive(Debug)]
+pub struct GeoAdd {
+    key: String,
+    flags: ZAddFlags,
+    points: Vec<(f64, f64, String)>,
+}
+
+impl CommandExecutor for GeoAdd {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.geoadd(&self.key, self.points, self.flags) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GeoAdd {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let mut flags = ZAddFlags::default();
+        while let Some(option) = args.peek_option() {
+            match option.as_str() {
+                "nx" | "xx" if flags.condition != SetCondition::Always => {
+                    return Err(CommandError::InvalidArgument(
+                        "XX and NX options at the same time are not compatible".to_string(),
+                    ))
+                }
+                "nx" => flags.condition = SetCondition::Nx,
+                "xx" => flags.condition = SetCondition::Xx,
+                "ch" => flags.ch = true,
+                _ => break,
+            }
+            args.next_frame()?;
+        }
+
+        if args.is_empty() || args.len() % 3 != 0 {
+            return Err(syntax_error());
+        }
+        let mut points = Vec::with_capacity(args.len() / 3);
+        while !args.is_empty() {
+            let (lon, lat) = next_lonlat(&mut args)?;
+            points.push((lon, lat, args.next_string()?));
+        }
+        Ok(GeoAdd { key, flags, points })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_geoadd_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$6\r\ngeoadd\r\n$6\r\nSicily\r\n$2\r\nCH\r\n\
+              $9\r\n13.361389\r\n$9\r\n38.115556\r\n$7\r\nPalermo\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoAdd = frame.try_into()?;
+        assert_eq!(result.key, "Sicily");
+        assert!(result.flags.ch);
+        assert_eq!(
+            result.points,
+            vec![(13.361389, 38.115556, "Palermo".to_string())]
+        );
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(
+            backend.zscore("Sicily", "Palermo")?,
+            Some(3479099956230698.0)
+        );
+
+        let frame = RespArray::new([
+            BulkString::new("geoadd").into(),
+            BulkString::new("Sicily").into(),
+            BulkString::new("181").into(),
+            BulkString::new("10").into(),
+            BulkString::new("member").into(),
+        ]);
+        assert!(GeoAdd::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull,
+};
+
+use super::{dist_reply, next_unit};
+
+// geodist: https://redis.io/docs/latest/commands/geodist/
+// GEODIST key member1 member2 [M | KM | FT | MI]
+
+#[derive(Debug)]
+pub struct GeoDist {
+    key: String,
+    from: String,
+    to: String,
+    unit: f64,
+}
+
+impl CommandExecutor for GeoDist {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.geodist(&self.key, &self.from, &self.to) {
+            Ok(Some(dist)) => dist_reply(dist / self.unit),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GeoDist {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        if value.len() > 5 {
+            return Err(syntax_error());
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let from = args.next_string()?;
+        let to = args.next_string()?;
+        let unit = match args.is_empty() {
+            true => 1.0,
+            false => next_unit(&mut args)?,
+        };
+        Ok(GeoDist {
+            key,
+            from,
+            to,
+            unit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_geodist_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$7\r\ngeodist\r\n$6\r\nSicily\r\n$7\r\nPalermo\r\n$7\r\nCatania\r\n$2\r\nKM\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoDist = frame.try_into()?;
+        assert_eq!(result.key, "Sicily");
+        assert_eq!(result.unit, 1000.0);
+
+        let backend = Backend::new();
+        let points = vec![
+            (13.361389, 38.115556, "Palermo".to_string()),
+            (15.087269, 37.502669, "Catania".to_string()),
+        ];
+        backend.geoadd("Sicily", points, ZAddFlags::default())?;
+        assert_eq!(result.execute(&backend), BulkString::new("166.2742").into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+// geohash: https://redis.io/docs/latest/commands/geohash/
+// GEOHASH key [member [member ...]]
+
+#[derive(Debug)]
+pub struct GeoHash {
+    key: String,
+    members: Vec<String>,
+}
+
+impl CommandExecutor for GeoHash {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.geohash(&self.key, &self.members) {
+            Ok(hashes) => {
+                let frames = hashes
+                    .into_iter()
+                    .map(|hash| match hash {
+                        Some(hash) => BulkString::new(hash).into(),
+                        None => RespFrame::Null(RespNull),
+                    })
+                    .collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GeoHash {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GeoHash {
+            key: args.next_string()?,
+            members: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_geohash_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\ngeohash\r\n$6\r\nSicily\r\n$7\r\nCatania\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoHash = frame.try_into()?;
+        assert_eq!(result.key, "Sicily");
+        assert_eq!(result.members, vec!["Catania"]);
+
+        let backend = Backend::new();
+        let points = vec![(15.087269, 37.502669, "Catania".to_string())];
+        backend.geoadd("Sicily", points, ZAddFlags::default())?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([BulkString::new("sqdtr74hyu0").into()]).into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNullArray,
+};
+
+use super::coords_reply;
+
+// geopos: https://redis.io/docs/latest/commands/geopos/
+// GEOPOS key [member [member ...]]
+
+#[derive(Debug)]
+pub struct GeoPos {
+    key: String,
+    members: Vec<String>,
+}
+
+impl CommandExecutor for GeoPos {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.geopos(&self.key, &self.members) {
+            Ok(positions) => {
+                let frames = positions
+                    .into_iter()
+                    .map(|pos| match pos {
+                        Some(pos) => coords_reply(pos),
+                        None => RespFrame::NullArray(RespNullArray),
+                    })
+                    .collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GeoPos {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GeoPos {
+            key: args.next_string()?,
+            members: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_geopos_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*4\r\n$6\r\ngeopos\r\n$6\r\nSicily\r\n$7\r\nPalermo\r\n$4\r\nnone\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoPos = frame.try_into()?;
+        assert_eq!(result.key, "Sicily");
+        assert_eq!(result.members, vec!["Palermo", "none"]);
+
+        let backend = Backend::new();
+        let points = vec![(13.361389, 38.115556, "Palermo".to_string())];
+        backend.geoadd("Sicily", points, ZAddFlags::default())?;
+        let RespFrame::Array(reply) = result.execute(&backend) else {
+            panic!("expected an array");
+        };
+        assert!(matches!(reply[0], RespFrame::Array(_)));
+        assert_eq!(reply[1], RespFrame::NullArray(RespNullArray));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, GeoMatch, GeoOrigin, GeoQuery, GeoShape, GeoSort, RespArray, RespFrame,
+};
+
+use super::{coords_reply, dist_reply, next_lonlat, next_unit};
+
+// geosearch: https://redis.io/docs/latest/commands/geosearch/
+// GEOSEARCH key <FROMMEMBER member | FROMLONLAT longitude latitude>
+//   <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>>
+//   [ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH]
+// GEOSEARCHSTORE destination source <FROMMEMBER member | FROMLONLAT longitude latitude>
+//   <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>>
+//   [ASC | DESC] [COUNT count [ANY]] [STOREDIST]
+
+#[derive(Debug)]
+pub struct GeoSearch {
+    key: String,
+    query: GeoQuery,
+    unit: f64, // the unit of the shape, for the distances of WITHDIST
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+}
+
+#[derive(Debug)]
+pub struct GeoSearchStore {
+    destination: String,
+    key: String,
+    query: GeoQuery,
+    unit: f64,
+    store_dist: bool,
+}
+
+impl CommandExecutor for GeoSearch {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let matches = match backend.geosearch(&self.key, &self.query) {
+            Ok(matches) => matches,
+            Err(e) => return e.into(),
+        };
+        let frames = matches
+            .into_iter()
+            .map(|m| self.match_reply(m))
+            .collect::<Vec<_>>();
+        RespArray::new(frames).into()
+    }
+}
+
+impl GeoSearch {
+    /// The member, or an array of the member with its distance, hash and coordinates.
+    fn match_reply(&self, m: GeoMatch) -> RespFrame {
+        if !(self.with_coord || self.with_dist || self.with_hash) {
+            return BulkString::new(m.member).into();
+        }
+        let mut frames = vec![BulkString::new(m.member).into()];
+        if self.with_dist {
+            frames.push(dist_reply(m.dist / self.unit));
+        }
+        if self.with_hash {
+            frames.push((m.hash as i64).into());
+        }
+        if self.with_coord {
+            frames.push(coords_reply((m.lon, m.lat)));
+        }
+        RespArray::new(frames).into()
+    }
+}
+
+impl CommandExecutor for GeoSearchStore {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let dist_unit = self.store_dist.then_some(self.unit);
+        match backend.geosearch_store(&self.destination, &self.key, &self.query, dist_unit) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+/// The options of GEOSEARCH and GEOSEARCHSTORE.
+#[derive(Debug)]
+struct SearchOptions {
+    query: GeoQuery,
+    unit: f64,
+    with_coord: bool,
+    with_dist: bool,
+    with_hash: bool,
+    store_dist: bool,
+}
+
+impl SearchOptions {
+    fn parse(args: &mut CommandArgs, store: bool) -> Result<Self, CommandError> {
+        let (mut origin, mut shape, mut sort, mut count, mut any) = (None, None, None, None, false);
+        let (mut with_coord, mut with_dist, mut with_hash, mut store_dist) =
+            (false, false, false, false);
+        let (mut origins, mut shapes) = (0, 0);
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "frommember" => {
+                    origin = Some(GeoOrigin::Member(args.next_string()?));
+                    origins += 1;
+                }
+                "fromlonlat" => {
+                    let (lon, lat) = next_lonlat(args)?;
+                    origin = Some(GeoOrigin::LonLat(lon, lat));
+                    origins += 1;
+                }
+                "byradius" => {
+                    let radius = args.next_float()?;
+                    if radius < 0.0 {
+                        return Err(CommandError::InvalidArgument(
+                            "radius cannot be negative".to_string(),
+                        ));
+                    }
+                    let unit = next_unit(args)?;
+                    shape = Some((GeoShape::Radius(radius * unit), unit));
+                    shapes += 1;
+                }
+                "bybox" => {
+                    let (width, height) = (args.next_float()?, args.next_float()?);
+                    if width < 0.0 || height < 0.0 {
+                        return Err(CommandError::InvalidArgument(
+                            "height or width cannot be negative".to_string(),
+                        ));
+                    }
+                    let unit = next_unit(args)?;
+                    let bbox = GeoShape::Box {
+                        width: width * unit,
+                        height: height * unit,
+                    };
+                    shape = Some((bbox, unit));
+                    shapes += 1;
+                }
+                "asc" => sort = Some(GeoSort::Asc),
+                "desc" => sort = Some(GeoSort::Desc),
+                "count" => match args.next_integer()? {
+                    n if n > 0 => count = Some(n as usize),
+                    _ => {
+                        return Err(CommandError::InvalidArgument(
+                            "COUNT must be > 0".to_string(),
+                        ))
+                    }
+                },
+                "any" => any = true,
+                "withcoord" if !store => with_coord = true,
+                "withdist" if !store => with_dist = true,
+                "withhash" if !store => with_hash = true,
+                "storedist" if store => store_dist = true,
+                _ => return Err(syntax_error()),
+            }
+        }
+
+        let (Some(origin), Some((shape, unit)), 1, 1) = (origin, shape, origins, shapes) else {
+            return Err(CommandError::InvalidArgument(match origins {
+                1 => "exactly one of BYRADIUS and BYBOX can be specified".to_string(),
+                _ => "exactly one of FROMMEMBER or FROMLONLAT can be specified".to_string(),
+            }));
+        };
+        if any && count.is_none() {
+            return Err(CommandError::InvalidArgument(
+                "the ANY argument requires COUNT argument".to_string(),
+            ));
+        }
+        // the nearest ones are returned with COUNT
+        if count.is_some() && !any && sort.is_none() {
+            sort = Some(GeoSort::Asc);
+        }
+        Ok(SearchOptions {
+            query: GeoQuery {
+                origin,
+                shape,
+                sort,
+                count,
+                any,
+            },
+            unit,
+            with_coord,
+            with_dist,
+            with_hash,
+            store_dist,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for GeoSearch {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let opts = SearchOptions::parse(&mut args, false)?;
+        Ok(GeoSearch {
+            key,
+            query: opts.query,
+            unit: opts.unit,
+            with_coord: opts.with_coord,
+            with_dist: opts.with_dist,
+            with_hash: opts.with_hash,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for GeoSearchStore {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let destination = args.next_string()?;
+        let key = args.next_string()?;
+        let opts = SearchOptions::parse(&mut args, true)?;
+        Ok(GeoSearchStore {
+            destination,
+            key,
+            query: opts.query,
+            unit: opts.unit,
+            store_dist: opts.store_dist,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn sicily() -> Result<Backend> {
+        let backend = Backend::new();
+        let points = vec![
+            (13.361389, 38.115556, "Palermo".to_string()),
+            (15.087269, 37.502669, "Catania".to_string()),
+        ];
+        backend.geoadd("Sicily", points, ZAddFlags::default())?;
+        Ok(backend)
+    }
+
+    #[test]
+    fn test_geosearch_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*10\r\n$9\r\ngeosearch\r\n$6\r\nSicily\r\n$10\r\nFROMLONLAT\r\n$2\r\n15\r\n$2\r\n37\r\n\
+              $8\r\nBYRADIUS\r\n$3\r\n200\r\n$2\r\nkm\r\n$3\r\nASC\r\n$8\r\nWITHDIST\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoSearch = frame.try_into()?;
+        assert_eq!(result.key, "Sicily");
+        assert_eq!(result.query.origin, GeoOrigin::LonLat(15.0, 37.0));
+        assert_eq!(result.query.shape, GeoShape::Radius(200_000.0));
+        assert_eq!(result.query.sort, Some(GeoSort::Asc));
+        assert!(result.with_dist);
+
+        let backend = sicily()?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([
+                RespArray::new([
+                    BulkString::new("Catania").into(),
+                    BulkString::new("56.4413").into(),
+                ])
+                .into(),
+                RespArray::new([
+                    BulkString::new("Palermo").into(),
+                    BulkString::new("190.4424").into(),
+                ])
+                .into(),
+            ])
+            .into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_geosearchstore_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*11\r\n$14\r\ngeosearchstore\r\n$3\r\ndst\r\n$6\r\nSicily\r\n$10\r\nFROMMEMBER\r\n\
+              $7\r\nPalermo\r\n$5\r\nBYBOX\r\n$3\r\n400\r\n$3\r\n400\r\n$2\r\nkm\r\n\
+              $5\r\nCOUNT\r\n$1\r\n1\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GeoSearchStore = frame.try_into()?;
+        assert_eq!(result.destination, "dst");
+        assert_eq!(result.query.count, Some(1));
+        assert_eq!(result.query.sort, Some(GeoSort::Asc));
+
+        let backend = sicily()?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(backend.zscore("dst", "Palermo")?, Some(3479099956230698.0));
+
+        let frame = RespArray::new([
+            BulkString::new("geosearchstore").into(),
+            BulkString::new("dst").into(),
+            BulkString::new("Sicily").into(),
+            BulkString::new("FROMMEMBER").into(),
+            BulkString::new("Palermo").into(),
+            BulkString::new("BYRADIUS").into(),
+            BulkString::new("10").into(),
+            BulkString::new("km").into(),
+            BulkString::new("WITHDIST").into(),
+        ]);
+        assert!(GeoSearchStore::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
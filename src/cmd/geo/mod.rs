@@ -0,0 +1,51 @@
+mod geoadd;
+mod geodist;
+mod geohash;
+mod geopos;
+mod geosearch;
+
+pub(crate) use geoadd::GeoAdd;
+pub(crate) use geodist::GeoDist;
+pub(crate) use geohash::GeoHash;
+pub(crate) use geopos::GeoPos;
+pub(crate) use geosearch::{GeoSearch, GeoSearchStore};
+
+use crate::{
+    cmd::{float_reply, CommandArgs, CommandError},
+    BulkString, RespArray, RespFrame, GEO_LAT_MAX, GEO_LAT_MIN, GEO_LONG_MAX, GEO_LONG_MIN,
+};
+
+/// The next two arguments as a valid (longitude, latitude) pair.
+fn next_lonlat(args: &mut CommandArgs) -> Result<(f64, f64), CommandError> {
+    let (lon, lat) = (args.next_float()?, args.next_float()?);
+    if !(GEO_LONG_MIN..=GEO_LONG_MAX).contains(&lon) || !(GEO_LAT_MIN..=GEO_LAT_MAX).contains(&lat)
+    {
+        return Err(CommandError::InvalidArgument(format!(
+            "invalid longitude,latitude pair {:.6},{:.6}",
+            lon, lat
+        )));
+    }
+    Ok((lon, lat))
+}
+
+/// The next argument as a distance unit, in meters.
+fn next_unit(args: &mut CommandArgs) -> Result<f64, CommandError> {
+    match args.next_option()?.as_deref() {
+        Some("m") => Ok(1.0),
+        Some("km") => Ok(1000.0),
+        Some("ft") => Ok(0.3048),
+        Some("mi") => Ok(1609.34),
+        _ => Err(CommandError::InvalidArgument(
+            "unsupported unit provided. please use M, KM, FT, MI".to_string(),
+        )),
+    }
+}
+
+/// Distances are replied with 4 decimals.
+fn dist_reply(dist: f64) -> RespFrame {
+    BulkString::new(format!("{:.4}", dist)).into()
+}
+
+fn coords_reply((lon, lat): (f64, f64)) -> RespFrame {
+    RespArray::new([float_reply(lon), float_reply(lat)]).into()
+}
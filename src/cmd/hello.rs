@@ -0,0 +1,154 @@
+use crate::{
+    Backend, BulkString, RespArray, RespFrame, RespMap, Session, SimpleError, DEFAULT_USER,
+};
+
+use super::{
+    client::check_client_name, connection_only, CommandArgs, CommandError, CommandExecutor,
+};
+
+// hello: https://redis.io/docs/latest/commands/hello/
+// HELLO [protover [AUTH username password] [SETNAME clientname]]
+
+#[derive(Debug)]
+pub struct Hello {
+    protocol: Option<u8>,     // 2 or 3, None keeps the protocol of the connection
+    username: Option<String>, // the password is not checked, the default user has none
+    name: Option<String>,
+}
+
+impl Hello {
+    /// Switch the protocol of the connection, then reply the server info, in
+    /// the new protocol.
+    pub fn execute_session(self, session: &Session) -> RespFrame {
+        if self.username.is_some_and(|user| user != DEFAULT_USER) {
+            return SimpleError::new(
+                "WRONGPASS invalid username-password pair or user is disabled.",
+            )
+            .into();
+        }
+        let protocol = session.update(|state| {
+            if let Some(protocol) = self.protocol {
+                state.protocol = protocol;
+            }
+            if let Some(name) = self.name {
+                state.name = (!name.is_empty()).then_some(name);
+            }
+            state.protocol
+        });
+
+        let mut info = RespMap::new();
+        let bulk = |s: &str| RespFrame::from(BulkString::new(s));
+        info.insert("server".to_string(), bulk("simple-redis"));
+        info.insert("version".to_string(), bulk(env!("CARGO_PKG_VERSION")));
+        info.insert("proto".to_string(), (protocol as i64).into());
+        info.insert("id".to_string(), (session.id() as i64).into());
+        info.insert("mode".to_string(), bulk("standalone"));
+        info.insert("role".to_string(), bulk("master"));
+        info.insert("modules".to_string(), RespArray::new(vec![]).into());
+        info.into()
+    }
+}
+
+impl CommandExecutor for Hello {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("HELLO")
+    }
+}
+
+impl TryFrom<RespArray> for Hello {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let protocol = match args.is_empty() {
+            true => None,
+            false => Some(args.next_integer().map_err(|_| {
+                CommandError::InvalidArgument(
+                    "Protocol version is not an integer or out of range".to_string(),
+                )
+            })?),
+        };
+
+        let (mut username, mut name) = (None, None);
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "auth" if args.len() >= 2 => {
+                    username = Some(args.next_string()?);
+                    args.next_bytes()?;
+                }
+                "setname" if !args.is_empty() => {
+                    let clientname = args.next_string()?;
+                    check_client_name(&clientname)?;
+                    name = Some(clientname);
+                }
+                _ => {
+                    return Err(CommandError::InvalidArgument(format!(
+                        "Syntax error in HELLO option '{}'",
+                        option
+                    )))
+                }
+            }
+        }
+
+        let protocol = match protocol {
+            None => None,
+            Some(protocol @ (2 | 3)) => Some(protocol as u8),
+            Some(_) => {
+                return Err(CommandError::NoProto(
+                    "unsupported protocol version".to_string(),
+                ))
+            }
+        };
+        Ok(Hello {
+            protocol,
+            username,
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, RespEncode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn hello(buf: &[u8]) -> Result<Hello, CommandError> {
+        let mut buf = BytesMut::from(buf);
+        RespArray::decode(&mut buf)?.try_into()
+    }
+
+    #[test]
+    fn test_hello() -> Result<()> {
+        let backend = Backend::new();
+        let session = backend.new_session("127.0.0.1:50001", "127.0.0.1:6379");
+
+        let result = hello(b"*4\r\n$5\r\nhello\r\n$1\r\n3\r\n$7\r\nSETNAME\r\n$6\r\nworker\r\n")?;
+        let RespFrame::Map(info) = result.execute_session(&session) else {
+            panic!("expected a map");
+        };
+        assert_eq!(info["proto"], 3.into());
+        assert_eq!(info["id"], (session.id() as i64).into());
+        assert_eq!(session.state().protocol, 3);
+        assert_eq!(session.state().name, Some("worker".to_string()));
+        assert_eq!(info["modules"].clone().encode(), b"*0\r\n");
+
+        let result = hello(b"*2\r\n$5\r\nhello\r\n$1\r\n4\r\n");
+        assert_eq!(
+            RespFrame::from(result.unwrap_err()),
+            SimpleError::new("NOPROTO unsupported protocol version").into()
+        );
+
+        let result =
+            hello(b"*5\r\n$5\r\nhello\r\n$1\r\n2\r\n$4\r\nAUTH\r\n$3\r\nbob\r\n$1\r\np\r\n")?;
+        assert_eq!(
+            result.execute_session(&session),
+            SimpleError::new("WRONGPASS invalid username-password pair or user is disabled.")
+                .into()
+        );
+        assert_eq!(session.state().protocol, 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hdel: https://redis.io/docs/latest/commands/hdel/
+// HDEL key field [field ...]
+
+#[derive(Debug)]
+pub struct HDel {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl CommandExecutor for HDel {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hdel(&self.key, &self.fields) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HDel {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HDel {
+            key: args.next_string()?,
+            fields: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hdel_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nhdel\r\n$3\r\nmap\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HDel = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.fields, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.hset("map", vec![("a".to_string(), BulkString::new("1").into())])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert!(!backend.exists("map"));
+
+        Ok(())
+    }
+}
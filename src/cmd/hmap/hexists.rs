@@ -0,0 +1,56 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hexists: https://redis.io/docs/latest/commands/hexists/
+// HEXISTS key field
+
+#[derive(Debug)]
+pub struct HExists {
+    key: String,
+    field: String,
+}
+
+impl CommandExecutor for HExists {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hexists(&self.key, &self.field) {
+            Ok(exists) => (exists as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HExists {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HExists {
+            key: args.next_string()?,
+            field: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hexists_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\nhexists\r\n$3\r\nmap\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HExists = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.field, "a");
+
+        Ok(())
+    }
+}
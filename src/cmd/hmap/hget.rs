@@ -1,5 +1,5 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor},
+    cmd::{extract_args, CommandError, CommandExecutor},
     RespArray, RespFrame,
 };
 #[derive(Debug)]
@@ -11,8 +11,9 @@ pub struct HGet {
 impl CommandExecutor for HGet {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
         match backend.hget(&self.key, &self.field) {
-            Some(value) => value,
-            None => RespFrame::Null(crate::RespNull),
+            Ok(Some(value)) => value,
+            Ok(None) => RespFrame::Null(crate::RespNull),
+            Err(e) => e.into(),
         }
     }
 }
@@ -20,8 +21,6 @@ impl CommandExecutor for HGet {
 impl TryFrom<RespArray> for HGet {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["hget"], 2)?;
-
         let mut args = extract_args(value, 1)?.into_iter();
         match (args.next(), args.next()) {
             (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(field))) => Ok(HGet {
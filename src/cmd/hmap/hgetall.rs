@@ -1,36 +1,20 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor},
-    BulkString, RespArray, RespFrame,
+    cmd::{extract_args, CommandError, CommandExecutor},
+    RespArray, RespFrame, RespMap,
 };
 #[derive(Debug)]
 pub struct HGetAll {
     key: String,
-    sort: bool, // for test
 }
 
 impl CommandExecutor for HGetAll {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
-        let hmap = backend.hmap.get(&self.key);
+        let hmap = backend.hgetall(&self.key);
 
+        // a map, a flat array of the fields and values for a RESP2 connection
         match hmap {
-            Some(hmap) => {
-                let mut data = Vec::with_capacity(hmap.len());
-                for v in hmap.iter() {
-                    let key = v.key().to_owned();
-                    data.push((key, v.value().clone()));
-                }
-                // sort: because the order of the fields in a hash is not guaranteed
-                if self.sort {
-                    data.sort_by(|a, b| a.0.cmp(&b.0));
-                }
-                let ret = data
-                    .into_iter()
-                    .flat_map(|(k, v)| vec![BulkString::from(k).into(), v])
-                    .collect::<Vec<RespFrame>>();
-
-                RespArray::new(ret).into()
-            }
-            None => RespArray::new([]).into(),
+            Ok(hmap) => RespMap(hmap.into_iter().flatten().collect()).into(),
+            Err(e) => e.into(),
         }
     }
 }
@@ -38,13 +22,10 @@ impl CommandExecutor for HGetAll {
 impl TryFrom<RespArray> for HGetAll {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["hgetall"], 1)?;
-
         let mut args = extract_args(value, 1)?.into_iter();
         match args.next() {
             Some(RespFrame::BulkString(key)) => Ok(HGetAll {
                 key: String::from_utf8(key.0)?,
-                sort: false,
             }),
             _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
         }
@@ -53,7 +34,7 @@ impl TryFrom<RespArray> for HGetAll {
 
 #[cfg(test)]
 mod tests {
-    use crate::RespDecode;
+    use crate::{BulkString, RespDecode, RespEncode};
 
     use super::*;
     use anyhow::Result;
@@ -71,4 +52,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_hgetall_command() {
+        let backend = crate::Backend::new();
+        let cmd = HGetAll {
+            key: "map".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), RespMap::new().into());
+
+        let fields = vec![
+            ("b".to_string(), BulkString::new("2").into()),
+            ("a".to_string(), BulkString::new("1").into()),
+        ];
+        backend.hset("map", fields).unwrap();
+        let cmd = HGetAll {
+            key: "map".to_string(),
+        };
+        let reply = cmd.execute(&backend);
+        assert_eq!(
+            reply.clone().encode(),
+            b"%2\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n"
+        );
+        assert_eq!(
+            reply.into_resp2().encode(),
+            b"*4\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n"
+        );
+    }
 }
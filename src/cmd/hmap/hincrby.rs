@@ -0,0 +1,115 @@
+use crate::{
+    cmd::{float_reply, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hincrby: https://redis.io/docs/latest/commands/hincrby/
+// HINCRBY key field increment
+// hincrbyfloat: https://redis.io/docs/latest/commands/hincrbyfloat/
+// HINCRBYFLOAT key field increment
+
+#[derive(Debug)]
+pub struct HIncrBy {
+    key: String,
+    field: String,
+    increment: i64,
+}
+
+#[derive(Debug)]
+pub struct HIncrByFloat {
+    key: String,
+    field: String,
+    increment: f64,
+}
+
+impl CommandExecutor for HIncrBy {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hincrby(&self.key, &self.field, self.increment) {
+            Ok(value) => value.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for HIncrByFloat {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hincrbyfloat(&self.key, &self.field, self.increment) {
+            Ok(value) => float_reply(value),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HIncrBy {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HIncrBy {
+            key: args.next_string()?,
+            field: args.next_string()?,
+            increment: args.next_integer()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for HIncrByFloat {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HIncrByFloat {
+            key: args.next_string()?,
+            field: args.next_string()?,
+            increment: args.next_float()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hincrby_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$7\r\nhincrby\r\n$3\r\nmap\r\n$1\r\nn\r\n$2\r\n-3\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HIncrBy = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.field, "n");
+        assert_eq!(result.increment, -3);
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(-3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hincrbyfloat_not_a_float() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*4\r\n$12\r\nhincrbyfloat\r\n$3\r\nmap\r\n$1\r\nn\r\n$3\r\n1.5\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+        let result: HIncrByFloat = frame.try_into()?;
+
+        let backend = Backend::new();
+        backend.hset(
+            "map",
+            vec![("n".to_string(), BulkString::new("abc").into())],
+        )?;
+        assert_eq!(
+            result.execute(&backend),
+            crate::SimpleError::new("ERR hash value is not a float").into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// hkeys: https://redis.io/docs/latest/commands/hkeys/
+// HKEYS key
+// hvals: https://redis.io/docs/latest/commands/hvals/
+// HVALS key
+
+#[derive(Debug)]
+pub struct HKeys {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct HVals {
+    key: String,
+}
+
+impl CommandExecutor for HKeys {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hkeys(&self.key) {
+            Ok(fields) => {
+                let frames = fields
+                    .into_iter()
+                    .map(|f| BulkString::new(f).into())
+                    .collect::<Vec<RespFrame>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for HVals {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hvals(&self.key) {
+            Ok(values) => RespArray::new(values).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HKeys {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HKeys {
+            key: args.next_string()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for HVals {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HVals {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hkeys_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$5\r\nhkeys\r\n$3\r\nmap\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HKeys = frame.try_into()?;
+        assert_eq!(result.key, "map");
+
+        let backend = Backend::new();
+        backend.hset("map", vec![("a".to_string(), BulkString::new("1").into())])?;
+        let expected = RespArray::new([BulkString::new("a").into()]);
+        assert_eq!(result.execute(&backend), expected.into());
+
+        Ok(())
+    }
+}
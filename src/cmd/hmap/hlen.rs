@@ -0,0 +1,53 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hlen: https://redis.io/docs/latest/commands/hlen/
+// HLEN key
+
+#[derive(Debug)]
+pub struct HLen {
+    key: String,
+}
+
+impl CommandExecutor for HLen {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hlen(&self.key) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HLen {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HLen {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hlen_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$4\r\nhlen\r\n$3\r\nmap\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HLen = frame.try_into()?;
+        assert_eq!(result.key, "map");
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor},
+    cmd::{extract_args, CommandError, CommandExecutor},
     Backend, RespArray, RespFrame, RespNull,
 };
 #[derive(Debug)]
@@ -12,7 +12,7 @@ impl CommandExecutor for HMGet {
     fn execute(self, backend: &Backend) -> RespFrame {
         let hmap = backend.hgetall(&self.key);
         match hmap {
-            Some(hmap) => {
+            Ok(Some(hmap)) => {
                 let mut data = Vec::with_capacity(self.fields.len());
                 for field in self.fields.iter() {
                     let value = hmap.get(field);
@@ -23,7 +23,8 @@ impl CommandExecutor for HMGet {
                 }
                 RespArray::new(data).into()
             }
-            None => RespArray::new([]).into(),
+            Ok(None) => RespArray::new(vec![RespNull.into(); self.fields.len()]).into(),
+            Err(e) => e.into(),
         }
     }
 }
@@ -31,7 +32,6 @@ impl CommandExecutor for HMGet {
 impl TryFrom<RespArray> for HMGet {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["hmget"], usize::MAX)?;
         let args = extract_args(value, 1)?.into_iter();
         let mut data = Vec::with_capacity(args.len());
         for arg in args {
@@ -0,0 +1,122 @@
+use crate::{
+    cmd::{parse_random_count, syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+// hrandfield: https://redis.io/docs/latest/commands/hrandfield/
+// HRANDFIELD key [count [WITHVALUES]]
+// a positive count returns distinct fields, a negative count allows the same field
+// to be returned multiple times
+
+#[derive(Debug)]
+pub struct HRandField {
+    key: String,
+    count: Option<i64>,
+    with_values: bool,
+}
+
+impl CommandExecutor for HRandField {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let fields = match backend.hrandfield(&self.key, self.count.unwrap_or(1)) {
+            Ok(fields) => fields,
+            Err(e) => return e.into(),
+        };
+        if self.count.is_none() {
+            return match fields.into_iter().next() {
+                Some((field, _)) => BulkString::new(field).into(),
+                None => RespFrame::Null(RespNull),
+            };
+        }
+
+        let mut frames = Vec::with_capacity(fields.len() * 2);
+        for (field, value) in fields {
+            frames.push(BulkString::new(field).into());
+            if self.with_values {
+                frames.push(value);
+            }
+        }
+        RespArray::new(frames).into()
+    }
+}
+
+impl TryFrom<RespArray> for HRandField {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let count = match args.is_empty() {
+            true => None,
+            false => Some(parse_random_count(args.next_integer()?)?),
+        };
+        let with_values = match args.next_option()?.as_deref() {
+            None => false,
+            Some("withvalues") => true,
+            Some(_) => return Err(syntax_error()),
+        };
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        // the reply has twice as many elements with the values
+        if with_values && count.is_some_and(|count| count < -i64::MAX / 2) {
+            return Err(CommandError::InvalidArgument(
+                "value is out of range".to_string(),
+            ));
+        }
+        Ok(HRandField {
+            key,
+            count,
+            with_values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hrandfield_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*4\r\n$10\r\nhrandfield\r\n$3\r\nmap\r\n$2\r\n-2\r\n$10\r\nWITHVALUES\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HRandField = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.count, Some(-2));
+        assert!(result.with_values);
+
+        let backend = Backend::new();
+        backend.hset("map", vec![("a".to_string(), BulkString::new("1").into())])?;
+        let (a, one): (RespFrame, RespFrame) =
+            (BulkString::new("a").into(), BulkString::new("1").into());
+        let expected = RespArray::new([a.clone(), one.clone(), a, one]);
+        assert_eq!(result.execute(&backend), expected.into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hrandfield_count_out_of_range() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*3\r\n$10\r\nhrandfield\r\n$3\r\nmap\r\n$20\r\n-9223372036854775808\r\n",
+        );
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(HRandField::try_from(frame).is_err());
+
+        buf.extend_from_slice(
+            b"*4\r\n$10\r\nhrandfield\r\n$3\r\nmap\r\n$20\r\n-4611686018427387904\r\n$10\r\nWITHVALUES\r\n",
+        );
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(HRandField::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
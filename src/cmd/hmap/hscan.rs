@@ -0,0 +1,106 @@
+use crate::{
+    cmd::{
+        key::{scan_reply, ScanOptions},
+        CommandArgs, CommandError, CommandExecutor,
+    },
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// hscan: https://redis.io/docs/latest/commands/hscan/
+// HSCAN key cursor [MATCH pattern] [COUNT count] [NOVALUES]
+
+#[derive(Debug)]
+pub struct HScan {
+    key: String,
+    cursor: u64,
+    options: ScanOptions,
+}
+
+impl CommandExecutor for HScan {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let options = self.options;
+        let ret = backend.hscan(
+            &self.key,
+            self.cursor,
+            options.pattern.as_deref(),
+            options.count,
+        );
+        match ret {
+            Ok((cursor, fields)) => {
+                let items = fields
+                    .into_iter()
+                    .flat_map(|(k, v)| match options.no_values {
+                        true => vec![BulkString::from(k).into()],
+                        false => vec![BulkString::from(k).into(), v],
+                    })
+                    .collect();
+                scan_reply(cursor, items)
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HScan {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let (cursor, options) = ScanOptions::parse(&mut args, "hscan")?;
+        Ok(HScan {
+            key,
+            cursor,
+            options,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hscan_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$5\r\nhscan\r\n$3\r\nmap\r\n$1\r\n0\r\n$5\r\nmatch\r\n$2\r\nf*\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HScan = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.cursor, 0);
+        assert_eq!(result.options.pattern, Some("f*".to_string()));
+
+        let backend = Backend::new();
+        backend
+            .hset(
+                "map",
+                vec![("field".to_string(), BulkString::new("value").into())],
+            )
+            .unwrap();
+        backend
+            .hset(
+                "map",
+                vec![("other".to_string(), BulkString::new("value").into())],
+            )
+            .unwrap();
+        assert_eq!(
+            result.execute(&backend),
+            scan_reply(
+                0,
+                vec![
+                    BulkString::new("field").into(),
+                    BulkString::new("value").into()
+                ]
+            )
+        );
+
+        Ok(())
+    }
+}
@@ -1,45 +1,47 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor, RESP_OK},
+    cmd::{wrong_arity, CommandArgs, CommandError, CommandExecutor},
     RespArray, RespFrame,
 };
+
+// hset: https://redis.io/docs/latest/commands/hset/
+// HSET key field value [field value ...]
+
 #[derive(Debug)]
 pub struct HSet {
     key: String,
-    field: String,
-    value: RespFrame,
+    fields: Vec<(String, RespFrame)>,
 }
 
 impl CommandExecutor for HSet {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
-        backend.hset(self.key, self.field, self.value);
-        RESP_OK.clone()
+        match backend.hset(&self.key, self.fields) {
+            Ok(added) => added.into(),
+            Err(e) => e.into(),
+        }
     }
 }
 
 impl TryFrom<RespArray> for HSet {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["hset"], 3)?;
-
-        let mut args = extract_args(value, 1)?.into_iter();
-        match (args.next(), args.next(), args.next()) {
-            (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(field)), Some(value)) => {
-                Ok(HSet {
-                    key: String::from_utf8(key.0)?,
-                    field: String::from_utf8(field.0)?,
-                    value,
-                })
-            }
-            _ => Err(CommandError::InvalidArgument(
-                "Invalid key, field or value".to_string(),
-            )),
+        // a field without a value
+        if !value.len().is_multiple_of(2) {
+            return Err(wrong_arity("hset"));
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let mut fields = Vec::with_capacity(args.len() / 2);
+        while !args.is_empty() {
+            fields.push((args.next_string()?, args.next_frame()?));
         }
+        Ok(HSet { key, fields })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::RespDecode;
+    use crate::{Backend, RespDecode};
 
     use super::*;
     use anyhow::Result;
@@ -54,8 +56,31 @@ mod tests {
 
         let result: HSet = frame.try_into()?;
         assert_eq!(result.key, "map");
-        assert_eq!(result.field, "hello");
-        assert_eq!(result.value, RespFrame::BulkString(b"world".into()));
+        assert_eq!(
+            result.fields,
+            vec![("hello".to_string(), RespFrame::BulkString(b"world".into()))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hset_multiple_fields() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$4\r\nhset\r\n$3\r\nmap\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+        let result: HSet = frame.try_into()?;
+
+        let backend = Backend::new();
+        backend.hset(
+            "map",
+            vec![("a".to_string(), RespFrame::BulkString(b"0".into()))],
+        )?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(backend.hlen("map")?, 2);
 
         Ok(())
     }
@@ -0,0 +1,64 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hsetnx: https://redis.io/docs/latest/commands/hsetnx/
+// HSETNX key field value
+
+#[derive(Debug)]
+pub struct HSetNx {
+    key: String,
+    field: String,
+    value: RespFrame,
+}
+
+impl CommandExecutor for HSetNx {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hsetnx(&self.key, self.field, self.value) {
+            Ok(set) => (set as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HSetNx {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HSetNx {
+            key: args.next_string()?,
+            field: args.next_string()?,
+            value: args.next_frame()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hsetnx_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nhsetnx\r\n$3\r\nmap\r\n$1\r\na\r\n$1\r\n2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HSetNx = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.field, "a");
+        assert_eq!(result.value, BulkString::new("2").into());
+
+        let backend = Backend::new();
+        backend.hset("map", vec![("a".to_string(), BulkString::new("1").into())])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(0));
+        assert_eq!(backend.hget("map", "a")?, Some(BulkString::new("1").into()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// hstrlen: https://redis.io/docs/latest/commands/hstrlen/
+// HSTRLEN key field
+
+#[derive(Debug)]
+pub struct HStrLen {
+    key: String,
+    field: String,
+}
+
+impl CommandExecutor for HStrLen {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.hstrlen(&self.key, &self.field) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for HStrLen {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(HStrLen {
+            key: args.next_string()?,
+            field: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_hstrlen_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\nhstrlen\r\n$3\r\nmap\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: HStrLen = frame.try_into()?;
+        assert_eq!(result.key, "map");
+        assert_eq!(result.field, "a");
+
+        Ok(())
+    }
+}
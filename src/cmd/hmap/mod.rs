@@ -1,9 +1,27 @@
+mod hdel;
+mod hexists;
 mod hget;
 mod hgetall;
+mod hincrby;
+mod hkeys;
+mod hlen;
 mod hmget;
+mod hrandfield;
+mod hscan;
 mod hset;
+mod hsetnx;
+mod hstrlen;
 
+pub(crate) use hdel::HDel;
+pub(crate) use hexists::HExists;
 pub(crate) use hget::HGet;
 pub(crate) use hgetall::HGetAll;
+pub(crate) use hincrby::{HIncrBy, HIncrByFloat};
+pub(crate) use hkeys::{HKeys, HVals};
+pub(crate) use hlen::HLen;
 pub(crate) use hmget::HMGet;
+pub(crate) use hrandfield::HRandField;
+pub(crate) use hscan::HScan;
 pub(crate) use hset::HSet;
+pub(crate) use hsetnx::HSetNx;
+pub(crate) use hstrlen::HStrLen;
@@ -0,0 +1,7 @@
+mod pfadd;
+mod pfcount;
+mod pfmerge;
+
+pub(crate) use pfadd::PfAdd;
+pub(crate) use pfcount::PfCount;
+pub(crate) use pfmerge::PfMerge;
@@ -0,0 +1,62 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// pfadd: https://redis.io/docs/latest/commands/pfadd/
+// PFADD key [element [element ...]]
+
+#[derive(Debug)]
+pub struct PfAdd {
+    key: String,
+    elements: Vec<Vec<u8>>,
+}
+
+impl CommandExecutor for PfAdd {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.pfadd(&self.key, &self.elements) {
+            Ok(updated) => (updated as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for PfAdd {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let mut elements = Vec::with_capacity(args.len());
+        while !args.is_empty() {
+            elements.push(args.next_bytes()?);
+        }
+        Ok(PfAdd { key, elements })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_pfadd_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\npfadd\r\n$3\r\nhll\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: PfAdd = frame.try_into()?;
+        assert_eq!(result.key, "hll");
+        assert_eq!(result.elements, vec![b"a".to_vec(), b"b".to_vec()]);
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(backend.key_type("hll"), "string");
+
+        Ok(())
+    }
+}
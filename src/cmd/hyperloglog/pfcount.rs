@@ -0,0 +1,57 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// pfcount: https://redis.io/docs/latest/commands/pfcount/
+// PFCOUNT key [key ...]
+
+#[derive(Debug)]
+pub struct PfCount {
+    keys: Vec<String>,
+}
+
+impl CommandExecutor for PfCount {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.pfcount(&self.keys) {
+            Ok(count) => (count as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for PfCount {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(PfCount {
+            keys: CommandArgs::new(value, 1)?.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_pfcount_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\npfcount\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: PfCount = frame.try_into()?;
+        assert_eq!(result.keys, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.pfadd("a", &[b"x".to_vec(), b"y".to_vec()])?;
+        backend.pfadd("b", &[b"y".to_vec(), b"z".to_vec()])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(3));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// pfmerge: https://redis.io/docs/latest/commands/pfmerge/
+// PFMERGE destkey [sourcekey [sourcekey ...]]
+
+#[derive(Debug)]
+pub struct PfMerge {
+    destination: String,
+    sources: Vec<String>,
+}
+
+impl CommandExecutor for PfMerge {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.pfmerge(&self.destination, &self.sources) {
+            Ok(()) => RESP_OK.clone(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for PfMerge {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(PfMerge {
+            destination: args.next_string()?,
+            sources: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_pfmerge_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$7\r\npfmerge\r\n$3\r\ndst\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: PfMerge = frame.try_into()?;
+        assert_eq!(result.destination, "dst");
+        assert_eq!(result.sources, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.pfadd("a", &[b"x".to_vec(), b"y".to_vec()])?;
+        backend.pfadd("b", &[b"y".to_vec(), b"z".to_vec()])?;
+        assert_eq!(result.execute(&backend), RESP_OK.clone());
+        assert_eq!(backend.pfcount(&["dst".to_string()])?, 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Backend, BulkString, RespArray, RespFrame};
+
+use super::{CommandArgs, CommandError, CommandExecutor};
+
+// info: https://redis.io/docs/latest/commands/info/
+// INFO [section [section ...]]
+
+const SECTIONS: [&str; 4] = ["server", "clients", "stats", "keyspace"];
+
+#[derive(Debug)]
+pub struct Info {
+    sections: Vec<&'static str>,
+}
+
+impl CommandExecutor for Info {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let stats = backend.stats();
+        let mut info = Vec::new();
+        for section in self.sections {
+            let lines = match section {
+                "server" => vec![
+                    format!("simple_redis_version:{}", env!("CARGO_PKG_VERSION")),
+                    "redis_mode:standalone".to_string(),
+                    format!("process_id:{}", std::process::id()),
+                ],
+                "clients" => vec![format!("connected_clients:{}", stats.connected_clients)],
+                "stats" => vec![format!("unknown_commands:{}", stats.unknown_commands)],
+                // a keyspace without keys is not listed
+                _ if stats.keys == 0 => vec![],
+                _ => vec![format!(
+                    "db0:keys={},expires={},avg_ttl=0",
+                    stats.keys, stats.expires
+                )],
+            };
+            let mut title = section.to_string();
+            title[..1].make_ascii_uppercase();
+            let lines = lines.iter().map(|line| format!("{}\r\n", line));
+            info.push(format!("# {}\r\n{}", title, lines.collect::<String>()));
+        }
+        BulkString::new(info.join("\r\n")).into()
+    }
+}
+
+impl TryFrom<RespArray> for Info {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let mut sections = Vec::new();
+        if args.is_empty() {
+            sections.extend(SECTIONS);
+        }
+        while let Some(section) = args.next_option()? {
+            match section.as_str() {
+                "all" | "default" | "everything" => sections.extend(SECTIONS),
+                // an unknown section is ignored
+                _ => sections.extend(SECTIONS.iter().find(|s| **s == section)),
+            }
+        }
+        // each section is reported once, in the usual order
+        sections.sort_by_key(|s| SECTIONS.iter().position(|x| x == s));
+        sections.dedup();
+        Ok(Info { sections })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_info_stats() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$4\r\ninfo\r\n$5\r\nSTATS\r\n$8\r\nkeyspace\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Info = frame.try_into()?;
+        assert_eq!(result.sections, vec!["stats", "keyspace"]);
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        backend.incr_unknown_commands();
+        assert_eq!(
+            result.execute(&backend),
+            BulkString::new("# Stats\r\nunknown_commands:1\r\n\r\n# Keyspace\r\ndb0:keys=1,expires=0,avg_ttl=0\r\n")
+                .into()
+        );
+
+        Ok(())
+    }
+}
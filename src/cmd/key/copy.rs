@@ -0,0 +1,73 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// copy: https://redis.io/docs/latest/commands/copy/
+// COPY source destination [DB destination-db] [REPLACE]
+// NOTE: there is only one database, DB 0
+
+#[derive(Debug)]
+pub struct Copy {
+    source: String,
+    destination: String,
+    replace: bool,
+}
+
+impl CommandExecutor for Copy {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        (backend.copy(&self.source, &self.destination, self.replace) as i64).into()
+    }
+}
+
+impl TryFrom<RespArray> for Copy {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let mut copy = Copy {
+            source: args.next_string()?,
+            destination: args.next_string()?,
+            replace: false,
+        };
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "replace" => copy.replace = true,
+                "db" => {
+                    if args.next_integer()? != 0 {
+                        return Err(CommandError::InvalidArgument(
+                            "DB index is out of range".to_string(),
+                        ));
+                    }
+                }
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok(copy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_copy_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$4\r\ncopy\r\n$5\r\nhello\r\n$5\r\nworld\r\n$2\r\ndb\r\n$1\r\n0\r\n$7\r\nREPLACE\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Copy = frame.try_into()?;
+        assert_eq!(result.source, "hello");
+        assert_eq!(result.destination, "world");
+        assert!(result.replace);
+
+        Ok(())
+    }
+}
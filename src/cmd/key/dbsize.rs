@@ -0,0 +1,46 @@
+use crate::{
+    cmd::{CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// dbsize: https://redis.io/docs/latest/commands/dbsize/
+
+#[derive(Debug)]
+pub struct DbSize;
+
+impl CommandExecutor for DbSize {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.dbsize().into()
+    }
+}
+
+impl TryFrom<RespArray> for DbSize {
+    type Error = CommandError;
+    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
+        Ok(DbSize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_dbsize_command() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n$6\r\ndbsize\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+        let result: DbSize = frame.try_into()?;
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// del: https://redis.io/docs/latest/commands/del/
+// unlink: https://redis.io/docs/latest/commands/unlink/
+
+#[derive(Debug)]
+pub struct Del {
+    keys: Vec<String>,
+}
+
+/// Like DEL, but reclaims the memory in another thread.
+#[derive(Debug)]
+pub struct Unlink {
+    keys: Vec<String>,
+}
+
+impl CommandExecutor for Del {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.del(&self.keys).into()
+    }
+}
+
+impl CommandExecutor for Unlink {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.unlink(&self.keys).into()
+    }
+}
+
+impl TryFrom<RespArray> for Del {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Del {
+            keys: CommandArgs::new(value, 1)?.rest_strings()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for Unlink {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Unlink {
+            keys: CommandArgs::new(value, 1)?.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_del_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$3\r\ndel\r\n$5\r\nhello\r\n$5\r\nworld\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Del = frame.try_into()?;
+        assert_eq!(result.keys, vec!["hello", "world"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unlink_command() {
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        let cmd = Unlink {
+            keys: vec!["hello".to_string(), "world".to_string()],
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(1));
+        assert!(!backend.exists("hello"));
+    }
+}
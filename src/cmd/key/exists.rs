@@ -0,0 +1,52 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// exists: https://redis.io/docs/latest/commands/exists/
+
+#[derive(Debug)]
+pub struct Exists {
+    keys: Vec<String>,
+}
+
+impl CommandExecutor for Exists {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.exists_count(&self.keys).into()
+    }
+}
+
+impl TryFrom<RespArray> for Exists {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Exists {
+            keys: CommandArgs::new(value, 1)?.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_exists_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\nexists\r\n$5\r\nhello\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Exists = frame.try_into()?;
+        assert_eq!(result.keys, vec!["hello", "hello"]);
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(2));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor},
+    now_ms, Backend, ExpireCondition, RespArray, RespFrame, SimpleError,
+};
+
+// expire: https://redis.io/docs/latest/commands/expire/
+// EXPIRE key seconds [NX | XX | GT | LT]
+// PEXPIRE key milliseconds [NX | XX | GT | LT]
+// EXPIREAT key unix-time-seconds [NX | XX | GT | LT]
+// PEXPIREAT key unix-time-milliseconds [NX | XX | GT | LT]
+
+#[derive(Debug)]
+struct ExpireArgs {
+    key: String,
+    time: i64,
+    condition: ExpireCondition,
+}
+
+#[derive(Debug)]
+pub struct Expire(ExpireArgs);
+
+#[derive(Debug)]
+pub struct PExpire(ExpireArgs);
+
+#[derive(Debug)]
+pub struct ExpireAt(ExpireArgs);
+
+#[derive(Debug)]
+pub struct PExpireAt(ExpireArgs);
+
+impl ExpireArgs {
+    /// `deadline` converts the time argument into an absolute deadline in milliseconds.
+    fn execute(
+        self,
+        backend: &Backend,
+        name: &str,
+        deadline: impl Fn(i64) -> Option<i64>,
+    ) -> RespFrame {
+        match deadline(self.time) {
+            Some(at) => (backend.expire_at(&self.key, at, self.condition) as i64).into(),
+            None => {
+                SimpleError::new(format!("ERR invalid expire time in '{}' command", name)).into()
+            }
+        }
+    }
+}
+
+impl CommandExecutor for Expire {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, "expire", |secs| {
+            secs.checked_mul(1000)
+                .and_then(|ms| ms.checked_add(now_ms()))
+        })
+    }
+}
+
+impl CommandExecutor for PExpire {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0
+            .execute(backend, "pexpire", |ms| ms.checked_add(now_ms()))
+    }
+}
+
+impl CommandExecutor for ExpireAt {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0
+            .execute(backend, "expireat", |secs| secs.checked_mul(1000))
+    }
+}
+
+impl CommandExecutor for PExpireAt {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, "pexpireat", Some)
+    }
+}
+
+fn parse_expire_args(value: RespArray) -> Result<ExpireArgs, CommandError> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    let (Some(key), Some(time)) = (args.next(), args.next()) else {
+        return Err(CommandError::InvalidArgument(
+            "Invalid key or time".to_string(),
+        ));
+    };
+    // like redis, the flags can be repeated and XX combines with GT or LT
+    let (mut nx, mut xx, mut gt, mut lt) = (false, false, false, false);
+    for flag in args {
+        let flag = extract_string(flag)?;
+        match flag.to_ascii_lowercase().as_str() {
+            "nx" => nx = true,
+            "xx" => xx = true,
+            "gt" => gt = true,
+            "lt" => lt = true,
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "Unsupported option {}",
+                    flag
+                )))
+            }
+        }
+    }
+    if nx && (xx || gt || lt) {
+        return Err(CommandError::InvalidArgument(
+            "NX and XX, GT or LT options at the same time are not compatible".to_string(),
+        ));
+    }
+    if gt && lt {
+        return Err(CommandError::InvalidArgument(
+            "GT and LT options at the same time are not compatible".to_string(),
+        ));
+    }
+    // GT already needs an expiry, as a key without ttl has an infinite one
+    let condition = match (nx, xx, gt, lt) {
+        (true, ..) => ExpireCondition::Nx,
+        (_, _, true, _) => ExpireCondition::Gt,
+        (_, true, _, true) => ExpireCondition::XxLt,
+        (_, false, _, true) => ExpireCondition::Lt,
+        (_, true, ..) => ExpireCondition::Xx,
+        _ => ExpireCondition::Always,
+    };
+
+    Ok(ExpireArgs {
+        key: extract_string(key)?,
+        time: extract_integer(time)?,
+        condition,
+    })
+}
+
+impl TryFrom<RespArray> for Expire {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Expire(parse_expire_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for PExpire {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(PExpire(parse_expire_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for ExpireAt {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(ExpireAt(parse_expire_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for PExpireAt {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(PExpireAt(parse_expire_args(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_expire_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nexpire\r\n$5\r\nhello\r\n$2\r\n10\r\n$2\r\nnx\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Expire = frame.try_into()?;
+        assert_eq!(result.0.key, "hello");
+        assert_eq!(result.0.time, 10);
+        assert_eq!(result.0.condition, ExpireCondition::Nx);
+
+        buf.extend_from_slice(
+            b"*5\r\n$6\r\nexpire\r\n$5\r\nhello\r\n$2\r\n10\r\n$2\r\nxx\r\n$2\r\nlt\r\n",
+        );
+        let result: Expire = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(result.0.condition, ExpireCondition::XxLt);
+
+        buf.extend_from_slice(
+            b"*5\r\n$6\r\nexpire\r\n$5\r\nhello\r\n$2\r\n10\r\n$2\r\nnx\r\n$2\r\nxx\r\n",
+        );
+        let result = Expire::try_from(RespArray::decode(&mut buf)?);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid argument: NX and XX, GT or LT options at the same time are not compatible"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expire_command() {
+        let backend = Backend::new();
+        let cmd = Expire(ExpireArgs {
+            key: "hello".to_string(),
+            time: 10,
+            condition: ExpireCondition::Always,
+        });
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(0));
+
+        backend.set("hello".to_string(), "world");
+        let cmd = PExpireAt(ExpireArgs {
+            key: "hello".to_string(),
+            time: now_ms() - 1,
+            condition: ExpireCondition::Always,
+        });
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(backend.get("hello"), Ok(None));
+    }
+}
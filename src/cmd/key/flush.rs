@@ -0,0 +1,86 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// flushdb: https://redis.io/docs/latest/commands/flushdb/
+// flushall: https://redis.io/docs/latest/commands/flushall/
+// FLUSHDB [ASYNC | SYNC]
+// NOTE: there is only one database, so FLUSHALL is the same as FLUSHDB
+
+#[derive(Debug)]
+pub struct FlushDb {
+    lazy: bool,
+}
+
+#[derive(Debug)]
+pub struct FlushAll {
+    lazy: bool,
+}
+
+impl CommandExecutor for FlushDb {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.flush(self.lazy);
+        RESP_OK.clone()
+    }
+}
+
+impl CommandExecutor for FlushAll {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.flush(self.lazy);
+        RESP_OK.clone()
+    }
+}
+
+fn parse_lazy(value: RespArray) -> Result<bool, CommandError> {
+    let mut args = CommandArgs::new(value, 1)?;
+    let lazy = match args.next_option()?.as_deref() {
+        None | Some("sync") => false,
+        Some("async") => true,
+        _ => return Err(syntax_error()),
+    };
+    if !args.is_empty() {
+        return Err(syntax_error());
+    }
+    Ok(lazy)
+}
+
+impl TryFrom<RespArray> for FlushDb {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(FlushDb {
+            lazy: parse_lazy(value)?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for FlushAll {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(FlushAll {
+            lazy: parse_lazy(value)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_flushall_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$8\r\nflushall\r\n$5\r\nASYNC\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: FlushAll = frame.try_into()?;
+        assert!(result.lazy);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, SimpleString,
+};
+
+// type: https://redis.io/docs/latest/commands/type/
+
+#[derive(Debug)]
+pub struct Type {
+    key: String,
+}
+
+impl CommandExecutor for Type {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        SimpleString::new(backend.key_type(&self.key)).into()
+    }
+}
+
+impl TryFrom<RespArray> for Type {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match args.next() {
+            Some(key) => Ok(Type {
+                key: extract_string(key)?,
+            }),
+            None => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_type_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$4\r\ntype\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Type = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_command() {
+        let backend = Backend::new();
+        backend.sadd("set", vec!["hello".to_string()]).unwrap();
+        backend.set("hello".to_string(), "world");
+
+        let cmd = Type {
+            key: "set".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), SimpleString::new("set").into());
+
+        let cmd = Type {
+            key: "hello".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), SimpleString::new("string").into());
+
+        let cmd = Type {
+            key: "none".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), SimpleString::new("none").into());
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// keys: https://redis.io/docs/latest/commands/keys/
+
+#[derive(Debug)]
+pub struct Keys {
+    pattern: String,
+}
+
+impl CommandExecutor for Keys {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let keys = backend
+            .keys(&self.pattern)
+            .into_iter()
+            .map(|k| BulkString::from(k).into())
+            .collect::<Vec<RespFrame>>();
+        RespArray::new(keys).into()
+    }
+}
+
+impl TryFrom<RespArray> for Keys {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match args.next() {
+            Some(pattern) => Ok(Keys {
+                pattern: extract_string(pattern)?,
+            }),
+            None => Err(CommandError::InvalidArgument("Invalid pattern".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_keys_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$4\r\nkeys\r\n$3\r\nh*o\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Keys = frame.try_into()?;
+        assert_eq!(result.pattern, "h*o");
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        backend.set("world".to_string(), "hello");
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([BulkString::new("hello").into()]).into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+mod copy;
+mod dbsize;
+mod del;
+mod exists;
+mod expire;
+mod flush;
+mod key_type;
+mod keys;
+mod persist;
+mod rename;
+mod scan;
+mod ttl;
+
+pub(crate) use copy::Copy;
+pub(crate) use dbsize::DbSize;
+pub(crate) use del::{Del, Unlink};
+pub(crate) use exists::Exists;
+pub(crate) use expire::{Expire, ExpireAt, PExpire, PExpireAt};
+pub(crate) use flush::{FlushAll, FlushDb};
+pub(crate) use key_type::Type;
+pub(crate) use keys::Keys;
+pub(crate) use persist::Persist;
+pub(crate) use rename::{Rename, RenameNx};
+pub(crate) use scan::{scan_reply, Scan, ScanOptions};
+pub(crate) use ttl::{ExpireTime, PExpireTime, PTtl, Ttl};
@@ -0,0 +1,52 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// persist: https://redis.io/docs/latest/commands/persist/
+
+#[derive(Debug)]
+pub struct Persist {
+    key: String,
+}
+
+impl CommandExecutor for Persist {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        (backend.persist(&self.key) as i64).into()
+    }
+}
+
+impl TryFrom<RespArray> for Persist {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match args.next() {
+            Some(key) => Ok(Persist {
+                key: extract_string(key)?,
+            }),
+            None => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_persist_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$7\r\npersist\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Persist = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+
+        Ok(())
+    }
+}
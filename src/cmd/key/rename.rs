@@ -0,0 +1,105 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// rename: https://redis.io/docs/latest/commands/rename/
+// renamenx: https://redis.io/docs/latest/commands/renamenx/
+
+#[derive(Debug)]
+pub struct Rename {
+    key: String,
+    new_key: String,
+}
+
+#[derive(Debug)]
+pub struct RenameNx {
+    key: String,
+    new_key: String,
+}
+
+impl CommandExecutor for Rename {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.rename(&self.key, &self.new_key, false) {
+            Ok(_) => RESP_OK.clone(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for RenameNx {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.rename(&self.key, &self.new_key, true) {
+            Ok(renamed) => (renamed as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+fn parse_keys(value: RespArray) -> Result<(String, String), CommandError> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match (args.next(), args.next()) {
+        (Some(key), Some(new_key)) => Ok((extract_string(key)?, extract_string(new_key)?)),
+        _ => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+}
+
+impl TryFrom<RespArray> for Rename {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let (key, new_key) = parse_keys(value)?;
+        Ok(Rename { key, new_key })
+    }
+}
+
+impl TryFrom<RespArray> for RenameNx {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let (key, new_key) = parse_keys(value)?;
+        Ok(RenameNx { key, new_key })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, SimpleError};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_rename_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\nrename\r\n$5\r\nhello\r\n$5\r\nworld\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Rename = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!(result.new_key, "world");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_command() {
+        let backend = Backend::new();
+        let cmd = Rename {
+            key: "hello".to_string(),
+            new_key: "world".to_string(),
+        };
+        assert_eq!(
+            cmd.execute(&backend),
+            SimpleError::new("ERR no such key").into()
+        );
+
+        backend.set("hello".to_string(), "world");
+        backend.set("world".to_string(), "world");
+        let cmd = RenameNx {
+            key: "hello".to_string(),
+            new_key: "world".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(0));
+    }
+}
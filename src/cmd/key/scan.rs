@@ -0,0 +1,138 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// scan: https://redis.io/docs/latest/commands/scan/
+// SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]
+
+const DEFAULT_COUNT: usize = 10;
+
+#[derive(Debug)]
+pub struct Scan {
+    cursor: u64,
+    options: ScanOptions,
+}
+
+/// Options shared by SCAN, HSCAN and SSCAN.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ScanOptions {
+    pub(crate) pattern: Option<String>,
+    pub(crate) count: usize,
+    pub(crate) key_type: Option<String>, // SCAN only
+    pub(crate) no_values: bool,          // HSCAN only
+}
+
+impl ScanOptions {
+    /// Parse the cursor and the options of the scan command `name`.
+    pub(crate) fn parse(
+        args: &mut CommandArgs,
+        name: &str,
+    ) -> Result<(u64, ScanOptions), CommandError> {
+        let cursor = args
+            .next_string()?
+            .parse::<u64>()
+            .map_err(|_| CommandError::InvalidArgument("invalid cursor".to_string()))?;
+
+        let mut options = ScanOptions {
+            pattern: None,
+            count: DEFAULT_COUNT,
+            key_type: None,
+            no_values: false,
+        };
+        while let Some(option) = args.next_option()? {
+            match (option.as_str(), name) {
+                ("match", _) => options.pattern = Some(args.next_string()?),
+                ("count", _) => match args.next_integer()? {
+                    count if count >= 1 => options.count = count as usize,
+                    _ => return Err(syntax_error()),
+                },
+                ("type", "scan") => options.key_type = Some(args.next_string()?),
+                ("novalues", "hscan") => options.no_values = true,
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok((cursor, options))
+    }
+}
+
+/// The reply of the scan commands: the next cursor and the elements.
+pub(crate) fn scan_reply(cursor: u64, items: Vec<RespFrame>) -> RespFrame {
+    RespArray::new([
+        BulkString::new(cursor.to_string()).into(),
+        RespArray::new(items).into(),
+    ])
+    .into()
+}
+
+impl CommandExecutor for Scan {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let options = self.options;
+        let (cursor, keys) = backend.scan(
+            self.cursor,
+            options.pattern.as_deref(),
+            options.count,
+            options.key_type.as_deref(),
+        );
+        let keys = keys
+            .into_iter()
+            .map(|k| BulkString::from(k).into())
+            .collect();
+        scan_reply(cursor, keys)
+    }
+}
+
+impl TryFrom<RespArray> for Scan {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (cursor, options) = ScanOptions::parse(&mut args, "scan")?;
+        Ok(Scan { cursor, options })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_scan_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*8\r\n$4\r\nscan\r\n$1\r\n0\r\n$5\r\nMATCH\r\n$6\r\nuser:*\r\n$5\r\nCOUNT\r\n$3\r\n100\r\n$4\r\nTYPE\r\n$6\r\nstring\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Scan = frame.try_into()?;
+        assert_eq!(result.cursor, 0);
+        assert_eq!(result.options.pattern, Some("user:*".to_string()));
+        assert_eq!(result.options.count, 100);
+        assert_eq!(result.options.key_type, Some("string".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_command() {
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        let cmd = Scan {
+            cursor: 0,
+            options: ScanOptions {
+                pattern: None,
+                count: 10,
+                key_type: None,
+                no_values: false,
+            },
+        };
+        assert_eq!(
+            cmd.execute(&backend),
+            scan_reply(0, vec![BulkString::new("hello").into()])
+        );
+    }
+}
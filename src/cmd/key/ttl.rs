@@ -0,0 +1,145 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// ttl: https://redis.io/docs/latest/commands/ttl/
+// TTL / PTTL return the remaining time to live, EXPIRETIME / PEXPIRETIME the absolute deadline.
+// All of them reply -2 if the key does not exist and -1 if the key has no associated expire.
+
+#[derive(Debug)]
+pub struct Ttl {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct PTtl {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct ExpireTime {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct PExpireTime {
+    key: String,
+}
+
+impl CommandExecutor for Ttl {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.pttl(&self.key) {
+            ttl if ttl < 0 => ttl.into(),
+            ttl => ((ttl + 500) / 1000).into(),
+        }
+    }
+}
+
+impl CommandExecutor for PTtl {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.pttl(&self.key).into()
+    }
+}
+
+impl CommandExecutor for ExpireTime {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.expire_time(&self.key) {
+            at if at < 0 => at.into(),
+            at => (at / 1000).into(),
+        }
+    }
+}
+
+impl CommandExecutor for PExpireTime {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.expire_time(&self.key).into()
+    }
+}
+
+fn parse_key(value: RespArray) -> Result<String, CommandError> {
+    let mut args = extract_args(value, 1)?.into_iter();
+    match args.next() {
+        Some(key) => extract_string(key),
+        None => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+    }
+}
+
+impl TryFrom<RespArray> for Ttl {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Ttl {
+            key: parse_key(value)?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for PTtl {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(PTtl {
+            key: parse_key(value)?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for ExpireTime {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(ExpireTime {
+            key: parse_key(value)?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for PExpireTime {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(PExpireTime {
+            key: parse_key(value)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{now_ms, ExpireCondition, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_ttl_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$3\r\nttl\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Ttl = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ttl_command() {
+        let backend = Backend::new();
+        let cmd = Ttl {
+            key: "hello".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(-2));
+
+        backend.set("hello".to_string(), "world");
+        let cmd = Ttl {
+            key: "hello".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(-1));
+
+        backend.expire_at("hello", now_ms() + 100_000, ExpireCondition::Always);
+        let cmd = Ttl {
+            key: "hello".to_string(),
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Integer(100));
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BlockOp, RespArray, RespFrame, RespNull,
+};
+
+use super::{blpop::parse_timeout, lmove::parse_list_end};
+
+// blmove: https://redis.io/docs/latest/commands/blmove/
+// BLMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT> timeout
+
+#[derive(Debug)]
+pub struct BLMove {
+    source: String,
+    op: BlockOp,
+    timeout: Option<Duration>,
+}
+
+/// Without blocking, e.g. inside a transaction, it behaves like LMOVE
+impl CommandExecutor for BLMove {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.try_pop_first(&[self.source], &self.op) {
+            Ok(Some(value)) => value,
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl BLMove {
+    pub async fn execute_blocking(self, backend: &Backend) -> RespFrame {
+        match backend
+            .block_pop(&[self.source], self.op, self.timeout)
+            .await
+        {
+            Ok(Some(value)) => value,
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for BLMove {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let source = args.next_string()?;
+        let destination = args.next_string()?;
+        let from = parse_list_end(&mut args)?;
+        let to = parse_list_end(&mut args)?;
+        Ok(BLMove {
+            source,
+            op: BlockOp::Move {
+                destination,
+                from,
+                to,
+            },
+            timeout: parse_timeout(args.next_frame()?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, ListEnd, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_blmove_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$6\r\nblmove\r\n$3\r\nsrc\r\n$3\r\ndst\r\n$4\r\nLEFT\r\n$5\r\nRIGHT\r\n$1\r\n0\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BLMove = frame.try_into()?;
+        assert_eq!(result.source, "src");
+        assert_eq!(
+            result.op,
+            BlockOp::Move {
+                destination: "dst".to_string(),
+                from: ListEnd::Left,
+                to: ListEnd::Right
+            }
+        );
+        assert_eq!(result.timeout, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_blmove_wakeup() -> Result<()> {
+        let backend = Backend::new();
+        let cloned = backend.clone();
+        let handle = tokio::spawn(async move {
+            let cmd = BLMove {
+                source: "src".to_string(),
+                op: BlockOp::Move {
+                    destination: "dst".to_string(),
+                    from: ListEnd::Left,
+                    to: ListEnd::Right,
+                },
+                timeout: Some(Duration::from_secs(5)),
+            };
+            cmd.execute_blocking(&cloned).await
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        backend.push(
+            "src",
+            ListEnd::Right,
+            vec![BulkString::new("a").into()],
+            false,
+        )?;
+        assert_eq!(handle.await?, BulkString::new("a").into());
+        assert_eq!(
+            backend.lrange("dst", 0, -1)?,
+            vec![BulkString::new("a").into()]
+        );
+        assert!(!backend.exists("src"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+use crate::{
+    cmd::{extract_string, CommandArgs, CommandError, CommandExecutor},
+    Backend, BlockOp, ListEnd, RespArray, RespFrame, RespNullArray,
+};
+
+// blpop: https://redis.io/docs/latest/commands/blpop/
+// BLPOP key [key ...] timeout
+// BRPOP key [key ...] timeout
+// timeout is in seconds (a double), 0 blocks forever
+
+#[derive(Debug)]
+struct BPopArgs {
+    keys: Vec<String>,
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+pub struct BLPop(BPopArgs);
+
+#[derive(Debug)]
+pub struct BRPop(BPopArgs);
+
+impl BPopArgs {
+    fn execute(self, backend: &Backend, end: ListEnd) -> RespFrame {
+        match backend.try_pop_first(&self.keys, &BlockOp::Pop(end)) {
+            Ok(Some(reply)) => reply,
+            Ok(None) => RespFrame::NullArray(RespNullArray),
+            Err(e) => e.into(),
+        }
+    }
+
+    async fn execute_blocking(self, backend: &Backend, end: ListEnd) -> RespFrame {
+        match backend
+            .block_pop(&self.keys, BlockOp::Pop(end), self.timeout)
+            .await
+        {
+            Ok(Some(reply)) => reply,
+            Ok(None) => RespFrame::NullArray(RespNullArray),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+/// Without blocking, e.g. inside a transaction, it behaves like LPOP
+impl CommandExecutor for BLPop {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Left)
+    }
+}
+
+impl CommandExecutor for BRPop {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Right)
+    }
+}
+
+impl BLPop {
+    pub async fn execute_blocking(self, backend: &Backend) -> RespFrame {
+        self.0.execute_blocking(backend, ListEnd::Left).await
+    }
+}
+
+impl BRPop {
+    pub async fn execute_blocking(self, backend: &Backend) -> RespFrame {
+        self.0.execute_blocking(backend, ListEnd::Right).await
+    }
+}
+
+/// Parse the timeout of the blocking commands, None means blocking forever.
+pub(crate) fn parse_timeout(frame: RespFrame) -> Result<Option<Duration>, CommandError> {
+    let timeout = extract_string(frame)?
+        .parse::<f64>()
+        .ok()
+        .filter(|t| t.is_finite())
+        .ok_or_else(|| {
+            CommandError::InvalidArgument("timeout is not a float or out of range".to_string())
+        })?;
+    if timeout < 0.0 {
+        return Err(CommandError::InvalidArgument(
+            "timeout is negative".to_string(),
+        ));
+    }
+    if timeout == 0.0 {
+        return Ok(None);
+    }
+    Duration::try_from_secs_f64(timeout)
+        .map(Some)
+        .map_err(|_| CommandError::InvalidArgument("timeout is out of range".to_string()))
+}
+
+fn parse_bpop_args(value: RespArray) -> Result<BPopArgs, CommandError> {
+    let mut args = CommandArgs::new(value, 1)?;
+    let mut keys = Vec::with_capacity(args.len() - 1);
+    while args.len() > 1 {
+        keys.push(args.next_string()?);
+    }
+    let timeout = parse_timeout(args.next_frame()?)?;
+    Ok(BPopArgs { keys, timeout })
+}
+
+impl TryFrom<RespArray> for BLPop {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(BLPop(parse_bpop_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for BRPop {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(BRPop(parse_bpop_args(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_blpop_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nblpop\r\n$2\r\nl1\r\n$2\r\nl2\r\n$3\r\n0.5\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: BLPop = frame.try_into()?;
+        assert_eq!(result.0.keys, vec!["l1", "l2"]);
+        assert_eq!(result.0.timeout, Some(Duration::from_millis(500)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        let timeout = |t: &str| parse_timeout(BulkString::new(t).into());
+        assert_eq!(timeout("0").unwrap(), None);
+        assert_eq!(timeout("1.5").unwrap(), Some(Duration::from_millis(1500)));
+        assert_eq!(
+            timeout("1e20").unwrap_err().to_string(),
+            "Invalid argument: timeout is out of range"
+        );
+        assert!(timeout("-1").is_err());
+        assert!(timeout("inf").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blpop_timeout() {
+        let backend = Backend::new();
+        let cmd = BLPop(BPopArgs {
+            keys: vec!["list".to_string()],
+            timeout: Some(Duration::from_millis(10)),
+        });
+        assert_eq!(
+            cmd.execute_blocking(&backend).await,
+            RespFrame::NullArray(RespNullArray)
+        );
+        assert!(backend.blocked_is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blpop_fifo_wakeup() -> Result<()> {
+        let backend = Backend::new();
+        let mut handles = vec![];
+        for _ in 0..2 {
+            let cloned = backend.clone();
+            handles.push(tokio::spawn(async move {
+                let cmd = BRPop(BPopArgs {
+                    keys: vec!["list".to_string()],
+                    timeout: None,
+                });
+                cmd.execute_blocking(&cloned).await
+            }));
+            // make sure the clients are blocked in order
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let values = vec![BulkString::new("a").into(), BulkString::new("b").into()];
+        backend.push("list", ListEnd::Right, values, false)?;
+
+        let reply = |v: &str| -> RespFrame {
+            RespArray::new([BulkString::new("list").into(), BulkString::new(v).into()]).into()
+        };
+        assert_eq!(handles.remove(0).await?, reply("b"));
+        assert_eq!(handles.remove(0).await?, reply("a"));
+        assert!(!backend.exists("list"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_blpop_cancelled() -> Result<()> {
+        let backend = Backend::new();
+        let cmd = BLPop(BPopArgs {
+            keys: vec!["list".to_string()],
+            timeout: None,
+        });
+        // the client blocks, then its connection is closed
+        let blocked = cmd.execute_blocking(&backend);
+        let cancelled = tokio::time::timeout(Duration::from_millis(10), blocked).await;
+        assert!(cancelled.is_err());
+        assert!(backend.blocked_is_empty());
+
+        backend.push(
+            "list",
+            ListEnd::Left,
+            vec![BulkString::new("a").into()],
+            false,
+        )?;
+        assert_eq!(backend.llen("list"), Ok(1));
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_blpop_during_exec() -> Result<()> {
+        let backend = Backend::new();
+        let values = vec![BulkString::new("a").into()];
+        backend.push("list", ListEnd::Left, values, false)?;
+
+        // a transaction of another connection is running
+        let exec = backend.lock_exclusive();
+        let cloned = backend.clone();
+        let handle = tokio::spawn(async move {
+            let cmd = BLPop(BPopArgs {
+                keys: vec!["list".to_string()],
+                timeout: None,
+            });
+            cmd.execute_blocking(&cloned).await
+        });
+        // NOTE: the lock is held by this thread, the blocked client runs on the other worker
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!handle.is_finished());
+        // the transaction deletes the list, the client doesn't see its element
+        backend.del(&["list".to_string()]);
+        drop(exec);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!handle.is_finished());
+        let values = vec![BulkString::new("b").into()];
+        backend.push("list", ListEnd::Left, values, false)?;
+        let reply = RespArray::new([BulkString::new("list").into(), BulkString::new("b").into()]);
+        assert_eq!(handle.await?, reply.into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull,
+};
+
+// lindex: https://redis.io/docs/latest/commands/lindex/
+
+#[derive(Debug)]
+pub struct LIndex {
+    key: String,
+    index: i64,
+}
+
+impl CommandExecutor for LIndex {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.lindex(&self.key, self.index) {
+            Ok(Some(value)) => value,
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LIndex {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match (args.next(), args.next()) {
+            (Some(key), Some(index)) => Ok(LIndex {
+                key: extract_string(key)?,
+                index: extract_integer(index)?,
+            }),
+            _ => Err(CommandError::InvalidArgument(
+                "Invalid key or index".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lindex_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\nlindex\r\n$4\r\nlist\r\n$2\r\n-1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LIndex = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.index, -1);
+
+        Ok(())
+    }
+}
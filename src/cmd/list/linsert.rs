@@ -0,0 +1,70 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// linsert: https://redis.io/docs/latest/commands/linsert/
+// LINSERT key <BEFORE | AFTER> pivot element
+
+#[derive(Debug)]
+pub struct LInsert {
+    key: String,
+    before: bool,
+    pivot: RespFrame,
+    value: RespFrame,
+}
+
+impl CommandExecutor for LInsert {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.linsert(&self.key, self.before, &self.pivot, self.value) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LInsert {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let before = match args.next_option()?.as_deref() {
+            Some("before") => true,
+            Some("after") => false,
+            _ => return Err(syntax_error()),
+        };
+        Ok(LInsert {
+            key,
+            before,
+            pivot: args.next_frame()?,
+            value: args.next_frame()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_linsert_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$7\r\nlinsert\r\n$4\r\nlist\r\n$5\r\nAFTER\r\n$1\r\na\r\n$1\r\nb\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LInsert = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert!(!result.before);
+        assert_eq!(result.pivot, BulkString::new("a").into());
+        assert_eq!(result.value, BulkString::new("b").into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    cmd::{extract_args, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// llen: https://redis.io/docs/latest/commands/llen/
+
+#[derive(Debug)]
+pub struct LLen {
+    key: String,
+}
+
+impl CommandExecutor for LLen {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.llen(&self.key) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LLen {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match args.next() {
+            Some(key) => Ok(LLen {
+                key: extract_string(key)?,
+            }),
+            None => Err(CommandError::InvalidArgument("Invalid key".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_llen_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$4\r\nllen\r\n$4\r\nlist\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LLen = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.execute(&Backend::new()), RespFrame::Integer(0));
+
+        Ok(())
+    }
+}
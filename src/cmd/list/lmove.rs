@@ -0,0 +1,88 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, ListEnd, RespArray, RespFrame, RespNull,
+};
+
+// lmove: https://redis.io/docs/latest/commands/lmove/
+// LMOVE source destination <LEFT | RIGHT> <LEFT | RIGHT>
+
+#[derive(Debug)]
+pub struct LMove {
+    source: String,
+    destination: String,
+    from: ListEnd,
+    to: ListEnd,
+}
+
+impl CommandExecutor for LMove {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.lmove(&self.source, &self.destination, self.from, self.to) {
+            Ok(Some(value)) => value,
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+/// Parse the LEFT / RIGHT argument of LMOVE like commands.
+pub(crate) fn parse_list_end(args: &mut CommandArgs) -> Result<ListEnd, CommandError> {
+    match args.next_option()?.as_deref() {
+        Some("left") => Ok(ListEnd::Left),
+        Some("right") => Ok(ListEnd::Right),
+        _ => Err(syntax_error()),
+    }
+}
+
+impl TryFrom<RespArray> for LMove {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(LMove {
+            source: args.next_string()?,
+            destination: args.next_string()?,
+            from: parse_list_end(&mut args)?,
+            to: parse_list_end(&mut args)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lmove_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$5\r\nlmove\r\n$3\r\nsrc\r\n$3\r\ndst\r\n$5\r\nRIGHT\r\n$4\r\nLEFT\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LMove = frame.try_into()?;
+        assert_eq!(result.source, "src");
+        assert_eq!(result.destination, "dst");
+        assert_eq!(result.from, ListEnd::Right);
+        assert_eq!(result.to, ListEnd::Left);
+
+        let backend = Backend::new();
+        backend.push(
+            "src",
+            ListEnd::Right,
+            vec![BulkString::new("a").into()],
+            false,
+        )?;
+        backend.set("dst".to_string(), "hello");
+        assert_eq!(
+            result.execute(&backend),
+            crate::BackendError::WrongType.into()
+        );
+        assert_eq!(backend.llen("src")?, 1);
+
+        Ok(())
+    }
+}
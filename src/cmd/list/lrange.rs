@@ -0,0 +1,63 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// lrange: https://redis.io/docs/latest/commands/lrange/
+
+#[derive(Debug)]
+pub struct LRange {
+    key: String,
+    start: i64,
+    stop: i64,
+}
+
+impl CommandExecutor for LRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.lrange(&self.key, self.start, self.stop) {
+            Ok(values) => RespArray::new(values).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match (args.next(), args.next(), args.next()) {
+            (Some(key), Some(start), Some(stop)) => Ok(LRange {
+                key: extract_string(key)?,
+                start: extract_integer(start)?,
+                stop: extract_integer(stop)?,
+            }),
+            _ => Err(CommandError::InvalidArgument(
+                "Invalid key or range".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$6\r\nlrange\r\n$4\r\nlist\r\n$1\r\n0\r\n$2\r\n-1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LRange = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.start, 0);
+        assert_eq!(result.stop, -1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// lrem: https://redis.io/docs/latest/commands/lrem/
+// LREM key count element
+
+#[derive(Debug)]
+pub struct LRem {
+    key: String,
+    count: i64,
+    value: RespFrame,
+}
+
+impl CommandExecutor for LRem {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.lrem(&self.key, self.count, &self.value) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LRem {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match (args.next(), args.next(), args.next()) {
+            (Some(key), Some(count), Some(value)) => Ok(LRem {
+                key: extract_string(key)?,
+                count: extract_integer(count)?,
+                value,
+            }),
+            _ => Err(CommandError::InvalidArgument(
+                "Invalid key, count or value".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, ListEnd, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lrem_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nlrem\r\n$4\r\nlist\r\n$2\r\n-2\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LRem = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.count, -2);
+
+        let backend = Backend::new();
+        let values = ["a", "b", "a", "a"].map(|v| BulkString::new(v).into());
+        backend.push("list", ListEnd::Right, values.to_vec(), false)?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(2));
+        assert_eq!(
+            backend.lrange("list", 0, -1)?,
+            vec![BulkString::new("a").into(), BulkString::new("b").into()]
+        );
+
+        Ok(())
+    }
+}
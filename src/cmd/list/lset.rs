@@ -0,0 +1,69 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// lset: https://redis.io/docs/latest/commands/lset/
+
+#[derive(Debug)]
+pub struct LSet {
+    key: String,
+    index: i64,
+    value: RespFrame,
+}
+
+impl CommandExecutor for LSet {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.lset(&self.key, self.index, self.value) {
+            Ok(()) => RESP_OK.clone(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LSet {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match (args.next(), args.next(), args.next()) {
+            (Some(key), Some(index), Some(value)) => Ok(LSet {
+                key: extract_string(key)?,
+                index: extract_integer(index)?,
+                value,
+            }),
+            _ => Err(CommandError::InvalidArgument(
+                "Invalid key, index or value".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, SimpleError};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lset_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nlset\r\n$4\r\nlist\r\n$1\r\n0\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LSet = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.index, 0);
+        assert_eq!(result.value, BulkString::new("hello").into());
+
+        let backend = Backend::new();
+        assert_eq!(
+            result.execute(&backend),
+            SimpleError::new("ERR no such key").into()
+        );
+
+        Ok(())
+    }
+}
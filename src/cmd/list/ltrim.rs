@@ -0,0 +1,63 @@
+use crate::{
+    cmd::{extract_args, extract_integer, extract_string, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// ltrim: https://redis.io/docs/latest/commands/ltrim/
+
+#[derive(Debug)]
+pub struct LTrim {
+    key: String,
+    start: i64,
+    stop: i64,
+}
+
+impl CommandExecutor for LTrim {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.ltrim(&self.key, self.start, self.stop) {
+            Ok(()) => RESP_OK.clone(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for LTrim {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = extract_args(value, 1)?.into_iter();
+        match (args.next(), args.next(), args.next()) {
+            (Some(key), Some(start), Some(stop)) => Ok(LTrim {
+                key: extract_string(key)?,
+                start: extract_integer(start)?,
+                stop: extract_integer(stop)?,
+            }),
+            _ => Err(CommandError::InvalidArgument(
+                "Invalid key or range".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_ltrim_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nltrim\r\n$4\r\nlist\r\n$1\r\n1\r\n$2\r\n-1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LTrim = frame.try_into()?;
+        assert_eq!(result.key, "list");
+        assert_eq!(result.start, 1);
+        assert_eq!(result.stop, -1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+mod blmove;
+mod blpop;
+mod lindex;
+mod linsert;
+mod llen;
+mod lmove;
+mod lrange;
+mod lrem;
+mod lset;
+mod ltrim;
+mod pop;
+mod push;
+
+pub(crate) use blmove::BLMove;
+pub(crate) use blpop::{BLPop, BRPop};
+pub(crate) use lindex::LIndex;
+pub(crate) use linsert::LInsert;
+pub(crate) use llen::LLen;
+pub(crate) use lmove::LMove;
+pub(crate) use lrange::LRange;
+pub(crate) use lrem::LRem;
+pub(crate) use lset::LSet;
+pub(crate) use ltrim::LTrim;
+pub(crate) use pop::{LPop, RPop};
+pub(crate) use push::{LPush, LPushX, RPush, RPushX};
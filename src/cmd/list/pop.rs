@@ -0,0 +1,133 @@
+use crate::{
+    cmd::{wrong_arity, CommandArgs, CommandError, CommandExecutor},
+    Backend, ListEnd, RespArray, RespFrame, RespNull, RespNullArray,
+};
+
+// lpop: https://redis.io/docs/latest/commands/lpop/
+// LPOP key [count]
+// RPOP key [count]
+
+#[derive(Debug)]
+struct PopArgs {
+    key: String,
+    count: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct LPop(PopArgs);
+
+#[derive(Debug)]
+pub struct RPop(PopArgs);
+
+impl PopArgs {
+    fn execute(self, backend: &Backend, end: ListEnd) -> RespFrame {
+        let values = match backend.pop(&self.key, end, self.count.unwrap_or(1)) {
+            Ok(values) => values,
+            Err(e) => return e.into(),
+        };
+        match (self.count, values.is_empty()) {
+            (None, true) => RespFrame::Null(RespNull),
+            (Some(_), true) => RespFrame::NullArray(RespNullArray),
+            // without count, reply the element itself
+            (None, false) => values.into_iter().next().unwrap_or(RespNull.into()),
+            (Some(_), false) => RespArray::new(values).into(),
+        }
+    }
+}
+
+impl CommandExecutor for LPop {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Left)
+    }
+}
+
+impl CommandExecutor for RPop {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Right)
+    }
+}
+
+fn parse_pop_args(value: RespArray, name: &'static str) -> Result<PopArgs, CommandError> {
+    let mut args = CommandArgs::new(value, 1)?;
+    let key = args.next_string()?;
+    let count = match args.is_empty() {
+        true => None,
+        false => match args.next_integer()? {
+            count if count >= 0 => Some(count as usize),
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "value is out of range, must be positive".to_string(),
+                ))
+            }
+        },
+    };
+    if !args.is_empty() {
+        return Err(wrong_arity(name));
+    }
+    Ok(PopArgs { key, count })
+}
+
+impl TryFrom<RespArray> for LPop {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(LPop(parse_pop_args(value, "lpop")?))
+    }
+}
+
+impl TryFrom<RespArray> for RPop {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(RPop(parse_pop_args(value, "rpop")?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_rpop_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$4\r\nrpop\r\n$4\r\nlist\r\n$1\r\n2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: RPop = frame.try_into()?;
+        assert_eq!(result.0.key, "list");
+        assert_eq!(result.0.count, Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_command() {
+        let backend = Backend::new();
+        let values = vec![BulkString::new("a").into(), BulkString::new("b").into()];
+        backend.push("list", ListEnd::Right, values, false).unwrap();
+
+        let cmd = LPop(PopArgs {
+            key: "list".to_string(),
+            count: None,
+        });
+        assert_eq!(cmd.execute(&backend), BulkString::new("a").into());
+
+        let cmd = RPop(PopArgs {
+            key: "list".to_string(),
+            count: Some(5),
+        });
+        assert_eq!(
+            cmd.execute(&backend),
+            RespArray::new([BulkString::new("b").into()]).into()
+        );
+
+        let cmd = RPop(PopArgs {
+            key: "list".to_string(),
+            count: None,
+        });
+        assert_eq!(cmd.execute(&backend), RespFrame::Null(RespNull));
+    }
+}
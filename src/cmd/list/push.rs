@@ -0,0 +1,131 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, ListEnd, RespArray, RespFrame,
+};
+
+// lpush: https://redis.io/docs/latest/commands/lpush/
+// LPUSH key element [element ...]
+// RPUSH key element [element ...]
+// LPUSHX / RPUSHX push only if the key already holds a list
+
+#[derive(Debug)]
+struct PushArgs {
+    key: String,
+    values: Vec<RespFrame>,
+}
+
+#[derive(Debug)]
+pub struct LPush(PushArgs);
+
+#[derive(Debug)]
+pub struct RPush(PushArgs);
+
+#[derive(Debug)]
+pub struct LPushX(PushArgs);
+
+#[derive(Debug)]
+pub struct RPushX(PushArgs);
+
+impl PushArgs {
+    fn execute(self, backend: &Backend, end: ListEnd, only_existing: bool) -> RespFrame {
+        match backend.push(&self.key, end, self.values, only_existing) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for LPush {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Left, false)
+    }
+}
+
+impl CommandExecutor for RPush {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Right, false)
+    }
+}
+
+impl CommandExecutor for LPushX {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Left, true)
+    }
+}
+
+impl CommandExecutor for RPushX {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, ListEnd::Right, true)
+    }
+}
+
+fn parse_push_args(value: RespArray) -> Result<PushArgs, CommandError> {
+    let mut args = CommandArgs::new(value, 1)?;
+    let key = args.next_string()?;
+    let mut values = Vec::with_capacity(args.len());
+    while !args.is_empty() {
+        values.push(args.next_frame()?);
+    }
+    Ok(PushArgs { key, values })
+}
+
+impl TryFrom<RespArray> for LPush {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(LPush(parse_push_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for RPush {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(RPush(parse_push_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for LPushX {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(LPushX(parse_push_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for RPushX {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(RPushX(parse_push_args(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_lpush_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nlpush\r\n$4\r\nlist\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: LPush = frame.try_into()?;
+        assert_eq!(result.0.key, "list");
+        assert_eq!(
+            result.0.values,
+            vec![BulkString::new("a").into(), BulkString::new("b").into()]
+        );
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(2));
+        assert_eq!(
+            backend.lrange("list", 0, -1)?,
+            vec![BulkString::new("b").into(), BulkString::new("a").into()]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// append: https://redis.io/docs/latest/commands/append/
+// APPEND key value
+
+#[derive(Debug)]
+pub struct Append {
+    key: String,
+    value: Vec<u8>,
+}
+
+impl CommandExecutor for Append {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.append(&self.key, &self.value) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for Append {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(Append {
+            key: args.next_string()?,
+            value: args.next_bytes()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_append_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\nappend\r\n$5\r\nhello\r\n$6\r\n world\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Append = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!(result.value, b" world");
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "hello");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(11));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor},
-    RespArray, RespFrame, RespNull,
+    cmd::{extract_args, CommandError, CommandExecutor},
+    BulkString, RespArray, RespFrame, RespNull,
 };
 
 #[derive(Debug)]
@@ -11,8 +11,9 @@ pub struct Get {
 impl CommandExecutor for Get {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
         match backend.get(&self.key) {
-            Some(value) => value,
-            None => RespFrame::Null(RespNull),
+            Ok(Some(value)) => BulkString::new(value).into(),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
         }
     }
 }
@@ -20,8 +21,6 @@ impl CommandExecutor for Get {
 impl TryFrom<RespArray> for Get {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["get"], 1)?; // validate get
-
         let mut args = extract_args(value, 1)?.into_iter();
         match args.next() {
             Some(RespFrame::BulkString(key)) => Ok(Get {
@@ -55,4 +54,21 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_get_wrong_type() {
+        let backend = crate::Backend::new();
+        backend.sadd("hello", vec!["world".to_string()]).unwrap();
+
+        let cmd = Get {
+            key: "hello".to_string(),
+        };
+        assert_eq!(
+            cmd.execute(&backend),
+            crate::SimpleError::new(
+                "WRONGTYPE Operation against a key holding the wrong kind of value"
+            )
+            .into()
+        );
+    }
 }
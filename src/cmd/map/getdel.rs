@@ -0,0 +1,59 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+// getdel: https://redis.io/docs/latest/commands/getdel/
+// GETDEL key
+
+#[derive(Debug)]
+pub struct GetDel {
+    key: String,
+}
+
+impl CommandExecutor for GetDel {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.getdel(&self.key) {
+            Ok(Some(value)) => BulkString::new(value).into(),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GetDel {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GetDel {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_getdel_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$6\r\ngetdel\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GetDel = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), BulkString::new("world").into());
+        assert!(!backend.exists("hello"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+use super::set::Expiration;
+
+// getex: https://redis.io/docs/latest/commands/getex/
+// GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds |
+//   PXAT unix-time-milliseconds | PERSIST]
+
+#[derive(Debug)]
+pub struct GetEx {
+    key: String,
+    expire: Option<Expiration>,
+    persist: bool,
+}
+
+impl CommandExecutor for GetEx {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let expire_at = self.expire.as_ref().and_then(Expiration::deadline);
+        match backend.getex(&self.key, expire_at, self.persist) {
+            Ok(Some(value)) => BulkString::new(value).into(),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GetEx {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let mut getex = GetEx {
+            key: args.next_string()?,
+            expire: None,
+            persist: false,
+        };
+        if let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "persist" => getex.persist = true,
+                "ex" | "px" | "exat" | "pxat" => {
+                    getex.expire = Some(Expiration::parse(&option, &mut args, "getex")?)
+                }
+                _ => return Err(syntax_error()),
+            }
+        }
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        Ok(getex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_getex_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\ngetex\r\n$5\r\nhello\r\n$2\r\nPX\r\n$4\r\n5000\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GetEx = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!(result.expire, Some(Expiration::Ttl(5000)));
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), BulkString::new("world").into());
+        let ttl = backend.pttl("hello");
+        assert!(ttl > 4_000 && ttl <= 5_000);
+
+        Ok(())
+    }
+}
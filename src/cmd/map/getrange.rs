@@ -0,0 +1,70 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// getrange: https://redis.io/docs/latest/commands/getrange/
+// GETRANGE key start end
+
+#[derive(Debug)]
+pub struct GetRange {
+    key: String,
+    start: i64,
+    end: i64,
+}
+
+impl CommandExecutor for GetRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.getrange(&self.key, self.start, self.end) {
+            Ok(value) => BulkString::new(value).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GetRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GetRange {
+            key: args.next_string()?,
+            start: args.next_integer()?,
+            end: args.next_integer()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, RespEncode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_getrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$8\r\ngetrange\r\n$5\r\nhello\r\n$1\r\n0\r\n$2\r\n-2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GetRange = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!((result.start, result.end), (0, -2));
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), BulkString::new("worl").into());
+
+        // an empty range is an empty string, not a nil
+        let result = GetRange {
+            key: "hello".to_string(),
+            start: 10,
+            end: 20,
+        };
+        assert_eq!(result.execute(&backend).encode(), b"$0\r\n\r\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull, SetCondition,
+};
+
+// getset: https://redis.io/docs/latest/commands/getset/
+// GETSET key value
+
+#[derive(Debug)]
+pub struct GetSet {
+    key: String,
+    value: Vec<u8>,
+}
+
+/// Same as SET key value GET
+impl CommandExecutor for GetSet {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.set_with(
+            self.key,
+            self.value,
+            SetCondition::Always,
+            None,
+            false,
+            true,
+        ) {
+            Ok((_, Some(old))) => BulkString::new(old).into(),
+            Ok((_, None)) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for GetSet {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(GetSet {
+            key: args.next_string()?,
+            value: args.next_bytes()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_getset_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\ngetset\r\n$5\r\nhello\r\n$5\r\nredis\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: GetSet = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!(result.value, b"redis");
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "world");
+        assert_eq!(result.execute(&backend), BulkString::new("world").into());
+        assert_eq!(backend.get("hello")?, Some(b"redis".to_vec()));
+
+        Ok(())
+    }
+}
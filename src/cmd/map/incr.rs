@@ -0,0 +1,178 @@
+use crate::{
+    cmd::{float_reply, CommandArgs, CommandError, CommandExecutor},
+    Backend, BackendError, RespArray, RespFrame,
+};
+
+// incr: https://redis.io/docs/latest/commands/incr/
+// INCR key
+// DECR key
+// INCRBY key increment
+// DECRBY key decrement
+// INCRBYFLOAT key increment
+
+#[derive(Debug)]
+pub struct Incr {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct Decr {
+    key: String,
+}
+
+#[derive(Debug)]
+pub struct IncrBy {
+    key: String,
+    increment: i64,
+}
+
+#[derive(Debug)]
+pub struct DecrBy {
+    key: String,
+    decrement: i64,
+}
+
+#[derive(Debug)]
+pub struct IncrByFloat {
+    key: String,
+    increment: f64,
+}
+
+fn incr_reply(ret: Result<i64, BackendError>) -> RespFrame {
+    match ret {
+        Ok(n) => n.into(),
+        Err(e) => e.into(),
+    }
+}
+
+impl CommandExecutor for Incr {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        incr_reply(backend.incr_by(&self.key, 1))
+    }
+}
+
+impl CommandExecutor for Decr {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        incr_reply(backend.incr_by(&self.key, -1))
+    }
+}
+
+impl CommandExecutor for IncrBy {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        incr_reply(backend.incr_by(&self.key, self.increment))
+    }
+}
+
+impl CommandExecutor for DecrBy {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match self.decrement.checked_neg() {
+            Some(increment) => incr_reply(backend.incr_by(&self.key, increment)),
+            None => BackendError::Overflow.into(),
+        }
+    }
+}
+
+impl CommandExecutor for IncrByFloat {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.incr_by_float(&self.key, self.increment) {
+            Ok(n) => float_reply(n),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for Incr {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(Incr {
+            key: args.next_string()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for Decr {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(Decr {
+            key: args.next_string()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for IncrBy {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(IncrBy {
+            key: args.next_string()?,
+            increment: args.next_integer()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for DecrBy {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(DecrBy {
+            key: args.next_string()?,
+            decrement: args.next_integer()?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for IncrByFloat {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(IncrByFloat {
+            key: args.next_string()?,
+            increment: args.next_float()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_decrby_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\ndecrby\r\n$7\r\ncounter\r\n$1\r\n5\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: DecrBy = frame.try_into()?;
+        assert_eq!(result.key, "counter");
+        assert_eq!(result.decrement, 5);
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RespFrame::Integer(-5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_incrbyfloat_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$11\r\nincrbyfloat\r\n$7\r\ncounter\r\n$3\r\n0.1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: IncrByFloat = frame.try_into()?;
+        assert_eq!(result.increment, 0.1);
+
+        let backend = Backend::new();
+        backend.set("counter".to_string(), "10.5");
+        assert_eq!(result.execute(&backend), BulkString::new("10.6").into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+// mget: https://redis.io/docs/latest/commands/mget/
+// MGET key [key ...]
+
+#[derive(Debug)]
+pub struct MGet {
+    keys: Vec<String>,
+}
+
+impl CommandExecutor for MGet {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let frames = backend
+            .mget(&self.keys)
+            .into_iter()
+            .map(|value| match value {
+                Some(value) => BulkString::new(value).into(),
+                None => RespFrame::Null(RespNull),
+            })
+            .collect::<Vec<_>>();
+        RespArray::new(frames).into()
+    }
+}
+
+impl TryFrom<RespArray> for MGet {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let args = CommandArgs::new(value, 1)?;
+        Ok(MGet {
+            keys: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_mget_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nmget\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\nc\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: MGet = frame.try_into()?;
+        assert_eq!(result.keys, vec!["a", "b", "c"]);
+
+        let backend = Backend::new();
+        backend.set("a".to_string(), "1");
+        backend.sadd("b", vec!["x".to_string()])?;
+        let expected = RespArray::new([
+            BulkString::new("1").into(),
+            RespFrame::Null(RespNull),
+            RespFrame::Null(RespNull),
+        ]);
+        assert_eq!(result.execute(&backend), expected.into());
+
+        Ok(())
+    }
+}
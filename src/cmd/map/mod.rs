@@ -1,5 +1,25 @@
+mod append;
 mod get;
+mod getdel;
+mod getex;
+mod getrange;
+mod getset;
+mod incr;
+mod mget;
+mod mset;
 mod set;
+mod setrange;
+mod strlen;
 
+pub(crate) use append::Append;
 pub(crate) use get::Get;
+pub(crate) use getdel::GetDel;
+pub(crate) use getex::GetEx;
+pub(crate) use getrange::GetRange;
+pub(crate) use getset::GetSet;
+pub(crate) use incr::{Decr, DecrBy, Incr, IncrBy, IncrByFloat};
+pub(crate) use mget::MGet;
+pub(crate) use mset::{MSet, MSetNx};
 pub(crate) use set::Set;
+pub(crate) use setrange::SetRange;
+pub(crate) use strlen::StrLen;
@@ -0,0 +1,101 @@
+use crate::{
+    cmd::{wrong_arity, CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame,
+};
+
+// mset: https://redis.io/docs/latest/commands/mset/
+// MSET key value [key value ...]
+// MSETNX key value [key value ...]
+
+#[derive(Debug)]
+pub struct MSet {
+    pairs: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Debug)]
+pub struct MSetNx {
+    pairs: Vec<(String, Vec<u8>)>,
+}
+
+impl CommandExecutor for MSet {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.mset(self.pairs);
+        RESP_OK.clone()
+    }
+}
+
+impl CommandExecutor for MSetNx {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        (backend.msetnx(self.pairs) as i64).into()
+    }
+}
+
+fn parse_pairs(
+    value: RespArray,
+    name: &'static str,
+) -> Result<Vec<(String, Vec<u8>)>, CommandError> {
+    // a key without a value
+    if value.len().is_multiple_of(2) {
+        return Err(wrong_arity(name));
+    }
+
+    let mut args = CommandArgs::new(value, 1)?;
+    let mut pairs = Vec::with_capacity(args.len() / 2);
+    while !args.is_empty() {
+        pairs.push((args.next_string()?, args.next_bytes()?));
+    }
+    Ok(pairs)
+}
+
+impl TryFrom<RespArray> for MSet {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(MSet {
+            pairs: parse_pairs(value, "mset")?,
+        })
+    }
+}
+
+impl TryFrom<RespArray> for MSetNx {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(MSetNx {
+            pairs: parse_pairs(value, "msetnx")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_msetnx_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$6\r\nmsetnx\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: MSetNx = frame.try_into()?;
+        assert_eq!(
+            result.pairs,
+            vec![
+                ("a".to_string(), b"1".to_vec()),
+                ("b".to_string(), b"2".to_vec())
+            ]
+        );
+
+        let backend = Backend::new();
+        backend.set("b".to_string(), "0");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(0));
+        assert!(!backend.exists("a"));
+
+        Ok(())
+    }
+}
@@ -1,42 +1,118 @@
 use crate::{
-    cmd::{extract_args, validate_command, CommandError, CommandExecutor, RESP_OK},
-    RespArray, RespFrame,
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    now_ms, BulkString, RespArray, RespFrame, RespNull, SetCondition,
 };
 
+// set: https://redis.io/docs/latest/commands/set/
+// SET key value [NX | XX] [GET] [EX seconds | PX milliseconds |
+//   EXAT unix-time-seconds | PXAT unix-time-milliseconds | KEEPTTL]
+
 #[derive(Debug)]
 pub struct Set {
     key: String,
-    value: RespFrame,
+    value: Vec<u8>,
+    condition: SetCondition,
+    get: bool,
+    expire: Option<Expiration>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Expiration {
+    Ttl(i64), // relative, in milliseconds
+    At(i64),  // absolute unix time, in milliseconds
+    KeepTtl,
+}
+
+impl Expiration {
+    /// The absolute deadline in milliseconds, None for KEEPTTL.
+    pub(super) fn deadline(&self) -> Option<i64> {
+        match self {
+            Expiration::Ttl(ttl) => Some(now_ms().saturating_add(*ttl)),
+            Expiration::At(at) => Some(*at),
+            Expiration::KeepTtl => None,
+        }
+    }
+
+    /// Parse the EX / PX / EXAT / PXAT option followed by its time.
+    pub(super) fn parse(
+        unit: &str,
+        args: &mut CommandArgs,
+        name: &str,
+    ) -> Result<Expiration, CommandError> {
+        let time = args.next_integer()?;
+        let invalid =
+            || CommandError::InvalidArgument(format!("invalid expire time in '{}' command", name));
+        if time <= 0 {
+            return Err(invalid());
+        }
+        Ok(match unit {
+            "ex" => Expiration::Ttl(time.checked_mul(1000).ok_or_else(invalid)?),
+            "px" => Expiration::Ttl(time),
+            "exat" => Expiration::At(time.checked_mul(1000).ok_or_else(invalid)?),
+            "pxat" => Expiration::At(time),
+            _ => return Err(syntax_error()),
+        })
+    }
 }
 
 impl CommandExecutor for Set {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
-        backend.set(self.key, self.value);
-        RESP_OK.clone()
+        let expire_at = self.expire.as_ref().and_then(Expiration::deadline);
+        let keep_ttl = self.expire == Some(Expiration::KeepTtl);
+
+        let ret = backend.set_with(
+            self.key,
+            self.value,
+            self.condition,
+            expire_at,
+            keep_ttl,
+            self.get,
+        );
+        match (self.get, ret) {
+            (_, Err(e)) => e.into(),
+            (true, Ok((_, Some(old)))) => BulkString::new(old).into(),
+            (true, Ok((_, None))) => RespFrame::Null(RespNull),
+            (false, Ok((true, _))) => RESP_OK.clone(),
+            (false, Ok((false, _))) => RespFrame::Null(RespNull),
+        }
     }
 }
 
 impl TryFrom<RespArray> for Set {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["set"], 2)?;
-
-        let mut args = extract_args(value, 1)?.into_iter();
-        match (args.next(), args.next()) {
-            (Some(RespFrame::BulkString(key)), Some(value)) => Ok(Set {
-                key: String::from_utf8(key.0)?,
-                value,
-            }),
-            _ => Err(CommandError::InvalidArgument(
-                "Invalid key or value".to_string(),
-            )),
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let value = args.next_bytes()?;
+
+        let mut set = Set {
+            key,
+            value,
+            condition: SetCondition::Always,
+            get: false,
+            expire: None,
+        };
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "nx" | "xx" if set.condition != SetCondition::Always => return Err(syntax_error()),
+                "nx" => set.condition = SetCondition::Nx,
+                "xx" => set.condition = SetCondition::Xx,
+                "get" => set.get = true,
+                "ex" | "px" | "exat" | "pxat" | "keepttl" if set.expire.is_some() => {
+                    return Err(syntax_error())
+                }
+                "keepttl" => set.expire = Some(Expiration::KeepTtl),
+                unit => set.expire = Some(Expiration::parse(unit, &mut args, "set")?),
+            }
         }
+
+        Ok(set)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::RespDecode;
+    use crate::{Backend, RespDecode};
 
     use super::*;
     use anyhow::Result;
@@ -51,8 +127,72 @@ mod tests {
 
         let result: Set = frame.try_into()?;
         assert_eq!(result.key, "hello");
-        assert_eq!(result.value, RespFrame::BulkString(b"world".into()));
+        assert_eq!(result.value, b"world");
 
         Ok(())
     }
+
+    #[test]
+    fn test_set_with_options() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$3\r\nset\r\n$5\r\nhello\r\n$5\r\nworld\r\n$2\r\nNX\r\n$2\r\nEX\r\n$2\r\n10\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Set = frame.try_into()?;
+        assert_eq!(result.condition, SetCondition::Nx);
+        assert_eq!(result.expire, Some(Expiration::Ttl(10_000)));
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RESP_OK.clone());
+        let ttl = backend.pttl("hello");
+        assert!(ttl > 9_000 && ttl <= 10_000);
+
+        let result = Set::try_from(RespArray::new([
+            BulkString::new("set").into(),
+            BulkString::new("hello").into(),
+            BulkString::new("world").into(),
+            BulkString::new("NX").into(),
+            BulkString::new("XX").into(),
+        ]));
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_condition_and_get() {
+        let backend = Backend::new();
+        let cmd = Set {
+            key: "hello".to_string(),
+            value: b"world".to_vec(),
+            condition: SetCondition::Xx,
+            get: false,
+            expire: None,
+        };
+        assert_eq!(cmd.execute(&backend), RespFrame::Null(RespNull));
+
+        backend.set("hello".to_string(), "world");
+        let cmd = Set {
+            key: "hello".to_string(),
+            value: b"redis".to_vec(),
+            condition: SetCondition::Nx,
+            get: true,
+            expire: None,
+        };
+        assert_eq!(cmd.execute(&backend), BulkString::new("world").into());
+        assert_eq!(backend.get("hello"), Ok(Some(b"world".to_vec())));
+
+        let cmd = Set {
+            key: "hello".to_string(),
+            value: b"redis".to_vec(),
+            condition: SetCondition::Always,
+            get: true,
+            expire: Some(Expiration::KeepTtl),
+        };
+        assert_eq!(cmd.execute(&backend), BulkString::new("world").into());
+        assert_eq!(backend.get("hello"), Ok(Some(b"redis".to_vec())));
+    }
 }
@@ -0,0 +1,73 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// setrange: https://redis.io/docs/latest/commands/setrange/
+// SETRANGE key offset value
+
+#[derive(Debug)]
+pub struct SetRange {
+    key: String,
+    offset: usize,
+    value: Vec<u8>,
+}
+
+impl CommandExecutor for SetRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.setrange(&self.key, self.offset, &self.value) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SetRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let offset = match args.next_integer()? {
+            offset if offset >= 0 => offset as usize,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "offset is out of range".to_string(),
+                ))
+            }
+        };
+        Ok(SetRange {
+            key,
+            offset,
+            value: args.next_bytes()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_setrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$8\r\nsetrange\r\n$5\r\nhello\r\n$1\r\n6\r\n$5\r\nRedis\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SetRange = frame.try_into()?;
+        assert_eq!(result.key, "hello");
+        assert_eq!(result.offset, 6);
+        assert_eq!(result.value, b"Redis");
+
+        let backend = Backend::new();
+        backend.set("hello".to_string(), "Hello World");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(11));
+        assert_eq!(backend.get("hello")?, Some(b"Hello Redis".to_vec()));
+
+        Ok(())
+    }
+}
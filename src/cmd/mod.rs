@@ -1,22 +1,78 @@
+mod args;
+mod bitmap;
+mod client;
 mod command;
+mod commands;
 mod echo;
+mod geo;
+mod hello;
 mod hmap;
+mod hyperloglog;
+mod info;
+mod key;
+mod list;
 mod map;
+mod ping;
+mod pubsub;
 mod set;
+mod stream;
+mod table;
+mod transaction;
 mod unrecognized;
+mod zset;
 
+pub use table::{
+    find_command, lookup_command, BeginSearch, CommandFlag, CommandSpec, FindKeys, KeySpec,
+    COMMAND_TABLE,
+};
 use {
-    crate::{Backend, RespArray, RespFrame, SimpleString},
+    crate::{Backend, BulkString, RespArray, RespFrame, SimpleError, SimpleString},
     enum_dispatch::enum_dispatch,
     std::sync::LazyLock,
 };
 pub(crate) use {
+    args::{syntax_error, wrong_arity, CommandArgs},
+    bitmap::{BitCount, BitField, BitOp, BitPos, GetBit, SetBit},
+    client::Client,
     command::{Command, CommandError},
+    commands::Commands,
     echo::Echo,
-    hmap::{HGet, HGetAll, HMGet, HSet},
-    map::{Get, Set},
-    set::{SAdd, SIsMember},
+    geo::{GeoAdd, GeoDist, GeoHash, GeoPos, GeoSearch, GeoSearchStore},
+    hello::Hello,
+    hmap::{
+        HDel, HExists, HGet, HGetAll, HIncrBy, HIncrByFloat, HKeys, HLen, HMGet, HRandField, HScan,
+        HSet, HSetNx, HStrLen, HVals,
+    },
+    hyperloglog::{PfAdd, PfCount, PfMerge},
+    info::Info,
+    key::{
+        Copy, DbSize, Del, Exists, Expire, ExpireAt, ExpireTime, FlushAll, FlushDb, Keys, PExpire,
+        PExpireAt, PExpireTime, PTtl, Persist, Rename, RenameNx, Scan, Ttl, Type, Unlink,
+    },
+    list::{
+        BLMove, BLPop, BRPop, LIndex, LInsert, LLen, LMove, LPop, LPush, LPushX, LRange, LRem,
+        LSet, LTrim, RPop, RPush, RPushX,
+    },
+    map::{
+        Append, Decr, DecrBy, Get, GetDel, GetEx, GetRange, GetSet, Incr, IncrBy, IncrByFloat,
+        MGet, MSet, MSetNx, Set, SetRange, StrLen,
+    },
+    ping::Ping,
+    pubsub::{
+        message_reply, PSubscribe, PUnsubscribe, PubSub, Publish, SPublish, SSubscribe,
+        SUnsubscribe, Subscribe, Unsubscribe,
+    },
+    set::{
+        SAdd, SCard, SDiff, SDiffStore, SInter, SInterCard, SInterStore, SIsMember, SMIsMember,
+        SMembers, SMove, SPop, SRandMember, SRem, SScan, SUnion, SUnionStore,
+    },
+    stream::{
+        XAck, XAdd, XAutoClaim, XClaim, XDel, XGroup, XInfo, XLen, XPending, XRange, XRead,
+        XReadGroup, XRevRange, XTrim,
+    },
+    transaction::{Discard, Exec, Multi, Transaction, Unwatch, Watch},
     unrecognized::Unrecognized,
+    zset::{ZAdd, ZCard, ZIncrBy, ZPopMax, ZPopMin, ZRange, ZRangeByScore, ZRank, ZRem, ZScore},
 };
 
 #[enum_dispatch]
@@ -37,40 +93,67 @@ pub trait CommandExecutor {
 // https://blog.rust-lang.org/2024/07/25/Rust-1.80.0.html
 pub static RESP_OK: LazyLock<RespFrame> = LazyLock::new(|| SimpleString::new("OK").into());
 
-pub fn validate_command(
-    value: &RespArray,
-    names: &[&'static str],
-    n_args: usize,
-) -> Result<(), CommandError> {
-    if n_args != usize::MAX && value.len() != n_args + names.len() {
-        return Err(CommandError::InvalidArgument(format!(
-            "{} command must have exactly {} argument",
-            names.join(" "),
-            n_args
-        )));
+pub fn extract_args(value: RespArray, start: usize) -> Result<Vec<RespFrame>, CommandError> {
+    Ok(value.0.into_iter().skip(start).collect::<Vec<RespFrame>>())
+}
+
+pub fn extract_string(frame: RespFrame) -> Result<String, CommandError> {
+    match frame {
+        RespFrame::BulkString(s) => Ok(String::from_utf8(s.0)?),
+        _ => Err(CommandError::InvalidArgument(
+            "Argument must be a BulkString".to_string(),
+        )),
     }
+}
 
-    for (i, name) in names.iter().enumerate() {
-        match value[i] {
-            RespFrame::BulkString(ref cmd) => {
-                if cmd.as_ref().to_ascii_lowercase() != name.as_bytes() {
-                    return Err(CommandError::InvalidCommand(format!(
-                        "Invalid command: expected {}, got {}",
-                        name,
-                        String::from_utf8_lossy(cmd.as_ref())
-                    )));
-                }
-            }
-            _ => {
-                return Err(CommandError::InvalidCommand(
-                    "Command must have a BulkString as the first argument".to_string(),
-                ))
-            }
-        }
+/// The raw bytes of a binary safe argument, e.g. a string value.
+pub fn extract_bytes(frame: RespFrame) -> Result<Vec<u8>, CommandError> {
+    match frame {
+        RespFrame::BulkString(s) => Ok(s.0),
+        _ => Err(CommandError::InvalidArgument(
+            "Argument must be a BulkString".to_string(),
+        )),
     }
-    Ok(())
 }
 
-pub fn extract_args(value: RespArray, start: usize) -> Result<Vec<RespFrame>, CommandError> {
-    Ok(value.0.into_iter().skip(start).collect::<Vec<RespFrame>>())
+pub fn extract_integer(frame: RespFrame) -> Result<i64, CommandError> {
+    extract_string(frame)?.parse().map_err(|_| {
+        CommandError::InvalidArgument("value is not an integer or out of range".to_string())
+    })
+}
+
+/// The count of SRANDMEMBER / HRANDFIELD, like redis its absolute value must fit in an i64.
+pub fn parse_random_count(count: i64) -> Result<i64, CommandError> {
+    match count {
+        i64::MIN => Err(CommandError::InvalidArgument(format!(
+            "value is out of range, must be between {} and {}",
+            -i64::MAX,
+            i64::MAX
+        ))),
+        count => Ok(count),
+    }
+}
+
+/// Parse a float argument, `inf` / `+inf` / `-inf` are accepted but not NaN.
+pub fn extract_float(frame: RespFrame) -> Result<f64, CommandError> {
+    extract_string(frame)?
+        .parse::<f64>()
+        .ok()
+        .filter(|f| !f.is_nan())
+        .ok_or_else(|| CommandError::InvalidArgument("value is not a valid float".to_string()))
+}
+
+/// The reply of a command which changes the state of the connection, e.g. SUBSCRIBE,
+/// when it is executed without one.
+pub fn connection_only(name: &str) -> RespFrame {
+    SimpleError::new(format!(
+        "ERR {} is only allowed on a client connection",
+        name
+    ))
+    .into()
+}
+
+/// Reply a float as a bulk string, like redis does for INCRBYFLOAT in both protocols.
+pub fn float_reply(value: f64) -> RespFrame {
+    BulkString::new(value.to_string()).into()
 }
@@ -0,0 +1,70 @@
+use crate::{
+    cmd::{wrong_arity, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, SimpleString,
+};
+
+// ping: https://redis.io/docs/latest/commands/ping/
+// PING [message]
+
+#[derive(Debug)]
+pub struct Ping {
+    message: Option<Vec<u8>>,
+}
+
+impl CommandExecutor for Ping {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        match self.message {
+            Some(message) => BulkString::new(message).into(),
+            None => SimpleString::new("PONG").into(),
+        }
+    }
+}
+
+impl Ping {
+    /// A subscribed RESP2 connection replies `pong message` like a pushed message.
+    pub fn execute_subscriber(self) -> RespArray {
+        RespArray::new([
+            BulkString::new("pong").into(),
+            BulkString::new(self.message.unwrap_or_default()).into(),
+        ])
+    }
+}
+
+impl TryFrom<RespArray> for Ping {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        if value.len() > 2 {
+            return Err(wrong_arity("ping"));
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let message = match args.is_empty() {
+            true => None,
+            false => Some(args.next_bytes()?),
+        };
+        Ok(Ping { message })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_ping_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n$4\r\nping\r\n*2\r\n$4\r\nping\r\n$5\r\nhello\r\n");
+
+        let backend = Backend::new();
+        let result: Ping = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(result.execute(&backend), SimpleString::new("PONG").into());
+        let result: Ping = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(result.execute(&backend), BulkString::new("hello").into());
+
+        Ok(())
+    }
+}
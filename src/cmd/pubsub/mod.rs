@@ -0,0 +1,48 @@
+mod publish;
+mod pubsub_info;
+mod subscribe;
+
+pub(crate) use publish::{Publish, SPublish};
+pub(crate) use pubsub_info::PubSub;
+pub(crate) use subscribe::{
+    PSubscribe, PUnsubscribe, SSubscribe, SUnsubscribe, Subscribe, Unsubscribe,
+};
+
+use crate::{BulkString, PubSubMessage, RespArray, RespFrame, RespNull};
+
+/// Reply a published message, `message channel payload`, `pmessage pattern channel payload`
+/// or `smessage channel payload`.
+pub fn message_reply(message: PubSubMessage) -> RespArray {
+    match message {
+        PubSubMessage::Message { channel, payload } => RespArray::new([
+            BulkString::new("message").into(),
+            BulkString::new(channel).into(),
+            BulkString::new(payload).into(),
+        ]),
+        PubSubMessage::PMessage {
+            pattern,
+            channel,
+            payload,
+        } => RespArray::new([
+            BulkString::new("pmessage").into(),
+            BulkString::new(pattern).into(),
+            BulkString::new(channel).into(),
+            BulkString::new(payload).into(),
+        ]),
+        PubSubMessage::SMessage { channel, payload } => RespArray::new([
+            BulkString::new("smessage").into(),
+            BulkString::new(channel).into(),
+            BulkString::new(payload).into(),
+        ]),
+    }
+}
+
+/// Reply a (un)subscription as `kind channel count`, the channel is nil when
+/// unsubscribing from everything without any subscription.
+fn subscription_reply(kind: &str, channel: Option<String>, count: usize) -> RespArray {
+    let channel = match channel {
+        Some(channel) => BulkString::new(channel).into(),
+        None => RespFrame::Null(RespNull),
+    };
+    RespArray::new([BulkString::new(kind).into(), channel, (count as i64).into()])
+}
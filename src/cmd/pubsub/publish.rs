@@ -0,0 +1,90 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// publish: https://redis.io/docs/latest/commands/publish/
+// PUBLISH channel message
+// spublish: https://redis.io/docs/latest/commands/spublish/
+// SPUBLISH shardchannel message
+
+#[derive(Debug)]
+pub struct Publish {
+    channel: String,
+    message: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct SPublish {
+    channel: String,
+    message: Vec<u8>,
+}
+
+impl CommandExecutor for Publish {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.publish(&self.channel, &self.message).into()
+    }
+}
+
+impl TryFrom<RespArray> for Publish {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(Publish {
+            channel: args.next_string()?,
+            message: args.next_bytes()?,
+        })
+    }
+}
+
+impl CommandExecutor for SPublish {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.spublish(&self.channel, &self.message).into()
+    }
+}
+
+impl TryFrom<RespArray> for SPublish {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SPublish {
+            channel: args.next_string()?,
+            message: args.next_bytes()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PubSubMessage, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[tokio::test]
+    async fn test_publish() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\npublish\r\n$4\r\nnews\r\n$5\r\nhello\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: Publish = frame.try_into()?;
+        assert_eq!(result.channel, "news");
+        assert_eq!(result.message, b"hello");
+
+        let backend = Backend::new();
+        let mut subscriber = backend.subscriber();
+        subscriber.subscribe("news");
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(
+            subscriber.recv().await,
+            Some(PubSubMessage::Message {
+                channel: "news".to_string(),
+                payload: b"hello".to_vec()
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// pubsub: https://redis.io/docs/latest/commands/pubsub/
+// PUBSUB CHANNELS [pattern]
+// PUBSUB NUMSUB [channel [channel ...]]
+// PUBSUB NUMPAT
+// PUBSUB SHARDCHANNELS [pattern]
+// PUBSUB SHARDNUMSUB [shardchannel [shardchannel ...]]
+
+#[derive(Debug, PartialEq)]
+enum PubSubOp {
+    Channels(Option<String>),
+    NumSub(Vec<String>),
+    NumPat,
+    ShardChannels(Option<String>),
+    ShardNumSub(Vec<String>),
+}
+
+#[derive(Debug)]
+pub struct PubSub {
+    op: PubSubOp,
+}
+
+fn channels_reply(channels: Vec<String>) -> RespFrame {
+    let frames = channels
+        .into_iter()
+        .map(|channel| BulkString::new(channel).into())
+        .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+}
+
+/// Reply the channels and their numbers of subscribers as a flat array.
+fn numsub_reply(numsub: Vec<(String, i64)>) -> RespFrame {
+    let frames = numsub
+        .into_iter()
+        .flat_map(|(channel, count)| [BulkString::new(channel).into(), count.into()])
+        .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+}
+
+impl CommandExecutor for PubSub {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match self.op {
+            PubSubOp::Channels(pattern) => {
+                channels_reply(backend.pubsub_channels(pattern.as_deref()))
+            }
+            PubSubOp::NumSub(channels) => numsub_reply(backend.pubsub_numsub(&channels)),
+            PubSubOp::NumPat => backend.pubsub_numpat().into(),
+            PubSubOp::ShardChannels(pattern) => {
+                channels_reply(backend.pubsub_shardchannels(pattern.as_deref()))
+            }
+            PubSubOp::ShardNumSub(channels) => numsub_reply(backend.pubsub_shardnumsub(&channels)),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for PubSub {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let subcommand = args.next_option()?.unwrap_or_default();
+        let op = match (subcommand.as_str(), args.len()) {
+            ("channels", 0) => PubSubOp::Channels(None),
+            ("channels", 1) => PubSubOp::Channels(Some(args.next_string()?)),
+            ("numsub", _) => PubSubOp::NumSub(args.rest_strings()?),
+            ("numpat", 0) => PubSubOp::NumPat,
+            ("shardchannels", 0) => PubSubOp::ShardChannels(None),
+            ("shardchannels", 1) => PubSubOp::ShardChannels(Some(args.next_string()?)),
+            ("shardnumsub", _) => PubSubOp::ShardNumSub(args.rest_strings()?),
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand or wrong number of arguments for '{}'. Try PUBSUB HELP.",
+                    subcommand
+                )))
+            }
+        };
+        Ok(PubSub { op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_pubsub_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*4\r\n$6\r\npubsub\r\n$6\r\nNUMSUB\r\n$4\r\nnews\r\n$4\r\nnone\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: PubSub = frame.try_into()?;
+        assert_eq!(
+            result.op,
+            PubSubOp::NumSub(vec!["news".to_string(), "none".to_string()])
+        );
+
+        let backend = Backend::new();
+        let mut subscriber = backend.subscriber();
+        subscriber.subscribe("news");
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([
+                BulkString::new("news").into(),
+                1.into(),
+                BulkString::new("none").into(),
+                0.into()
+            ])
+            .into()
+        );
+
+        Ok(())
+    }
+}
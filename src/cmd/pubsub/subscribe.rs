@@ -0,0 +1,304 @@
+use crate::{
+    cmd::{connection_only, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, Subscriber,
+};
+
+use super::subscription_reply;
+
+// subscribe: https://redis.io/docs/latest/commands/subscribe/
+// SUBSCRIBE channel [channel ...]
+// unsubscribe: https://redis.io/docs/latest/commands/unsubscribe/
+// UNSUBSCRIBE [channel [channel ...]]
+// psubscribe: https://redis.io/docs/latest/commands/psubscribe/
+// PSUBSCRIBE pattern [pattern ...]
+// punsubscribe: https://redis.io/docs/latest/commands/punsubscribe/
+// PUNSUBSCRIBE [pattern [pattern ...]]
+// ssubscribe: https://redis.io/docs/latest/commands/ssubscribe/
+// SSUBSCRIBE shardchannel [shardchannel ...]
+// sunsubscribe: https://redis.io/docs/latest/commands/sunsubscribe/
+// SUNSUBSCRIBE [shardchannel [shardchannel ...]]
+
+// The subscriptions belong to the connection, so these commands are executed
+// against its subscriber, replying one confirmation per channel / pattern.
+
+#[derive(Debug)]
+pub struct Subscribe {
+    channels: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Unsubscribe {
+    channels: Vec<String>, // empty for all the channels
+}
+
+#[derive(Debug)]
+pub struct PSubscribe {
+    patterns: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct PUnsubscribe {
+    patterns: Vec<String>, // empty for all the patterns
+}
+
+#[derive(Debug)]
+pub struct SSubscribe {
+    channels: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SUnsubscribe {
+    channels: Vec<String>, // empty for all the shard channels
+}
+
+impl Subscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        self.channels
+            .into_iter()
+            .map(|channel| {
+                let count = subscriber.subscribe(&channel);
+                subscription_reply("subscribe", Some(channel), count)
+            })
+            .collect()
+    }
+}
+
+impl Unsubscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        let channels = match self.channels.is_empty() {
+            true => subscriber.channels(),
+            false => self.channels,
+        };
+        if channels.is_empty() {
+            return vec![subscription_reply("unsubscribe", None, subscriber.count())];
+        }
+        channels
+            .into_iter()
+            .map(|channel| {
+                let count = subscriber.unsubscribe(&channel);
+                subscription_reply("unsubscribe", Some(channel), count)
+            })
+            .collect()
+    }
+}
+
+impl PSubscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        self.patterns
+            .into_iter()
+            .map(|pattern| {
+                let count = subscriber.psubscribe(&pattern);
+                subscription_reply("psubscribe", Some(pattern), count)
+            })
+            .collect()
+    }
+}
+
+impl PUnsubscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        let patterns = match self.patterns.is_empty() {
+            true => subscriber.patterns(),
+            false => self.patterns,
+        };
+        if patterns.is_empty() {
+            return vec![subscription_reply("punsubscribe", None, subscriber.count())];
+        }
+        patterns
+            .into_iter()
+            .map(|pattern| {
+                let count = subscriber.punsubscribe(&pattern);
+                subscription_reply("punsubscribe", Some(pattern), count)
+            })
+            .collect()
+    }
+}
+
+impl SSubscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        self.channels
+            .into_iter()
+            .map(|channel| {
+                let count = subscriber.ssubscribe(&channel);
+                subscription_reply("ssubscribe", Some(channel), count)
+            })
+            .collect()
+    }
+}
+
+impl SUnsubscribe {
+    pub fn execute_subscriber(self, subscriber: &mut Subscriber) -> Vec<RespArray> {
+        let channels = match self.channels.is_empty() {
+            true => subscriber.shard_channels(),
+            false => self.channels,
+        };
+        if channels.is_empty() {
+            return vec![subscription_reply("sunsubscribe", None, 0)];
+        }
+        channels
+            .into_iter()
+            .map(|channel| {
+                let count = subscriber.sunsubscribe(&channel);
+                subscription_reply("sunsubscribe", Some(channel), count)
+            })
+            .collect()
+    }
+}
+
+impl CommandExecutor for Subscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("SUBSCRIBE")
+    }
+}
+
+impl CommandExecutor for Unsubscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("UNSUBSCRIBE")
+    }
+}
+
+impl CommandExecutor for PSubscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("PSUBSCRIBE")
+    }
+}
+
+impl CommandExecutor for PUnsubscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("PUNSUBSCRIBE")
+    }
+}
+
+impl CommandExecutor for SSubscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("SSUBSCRIBE")
+    }
+}
+
+impl CommandExecutor for SUnsubscribe {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("SUNSUBSCRIBE")
+    }
+}
+
+/// The channels / patterns after the command name.
+fn parse_names(value: RespArray) -> Result<Vec<String>, CommandError> {
+    CommandArgs::new(value, 1)?.rest_strings()
+}
+
+impl TryFrom<RespArray> for Subscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let channels = parse_names(value)?;
+        Ok(Subscribe { channels })
+    }
+}
+
+impl TryFrom<RespArray> for Unsubscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let channels = parse_names(value)?;
+        Ok(Unsubscribe { channels })
+    }
+}
+
+impl TryFrom<RespArray> for PSubscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let patterns = parse_names(value)?;
+        Ok(PSubscribe { patterns })
+    }
+}
+
+impl TryFrom<RespArray> for PUnsubscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let patterns = parse_names(value)?;
+        Ok(PUnsubscribe { patterns })
+    }
+}
+
+impl TryFrom<RespArray> for SSubscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let channels = parse_names(value)?;
+        Ok(SSubscribe { channels })
+    }
+}
+
+impl TryFrom<RespArray> for SUnsubscribe {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let channels = parse_names(value)?;
+        Ok(SUnsubscribe { channels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, RespNull};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_subscribe_unsubscribe() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$9\r\nsubscribe\r\n$4\r\nnews\r\n$7\r\nweather\r\n");
+        let subscribe: Subscribe = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(subscribe.channels, ["news", "weather"]);
+
+        let backend = Backend::new();
+        let mut subscriber = backend.subscriber();
+        let replies = subscribe.execute_subscriber(&mut subscriber);
+        assert_eq!(
+            replies[1],
+            RespArray::new([
+                BulkString::new("subscribe").into(),
+                BulkString::new("weather").into(),
+                2.into()
+            ])
+        );
+
+        buf.extend_from_slice(b"*1\r\n$11\r\nunsubscribe\r\n");
+        let unsubscribe: Unsubscribe = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(unsubscribe.execute_subscriber(&mut subscriber).len(), 2);
+        assert_eq!(subscriber.count(), 0);
+
+        let replies = Unsubscribe { channels: vec![] }.execute_subscriber(&mut subscriber);
+        assert_eq!(
+            replies,
+            [RespArray::new([
+                BulkString::new("unsubscribe").into(),
+                RespFrame::Null(RespNull),
+                0.into()
+            ])]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ssubscribe() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$10\r\nssubscribe\r\n$4\r\nnews\r\n");
+        let ssubscribe: SSubscribe = RespArray::decode(&mut buf)?.try_into()?;
+
+        let backend = Backend::new();
+        let mut subscriber = backend.subscriber();
+        subscriber.subscribe("weather");
+        let replies = ssubscribe.execute_subscriber(&mut subscriber);
+        // the count is of the shard channels only
+        assert_eq!(
+            replies,
+            [RespArray::new([
+                BulkString::new("ssubscribe").into(),
+                BulkString::new("news").into(),
+                1.into()
+            ])]
+        );
+        assert_eq!(backend.pubsub_shardchannels(None), ["news"]);
+
+        Ok(())
+    }
+}
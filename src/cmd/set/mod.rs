@@ -1,5 +1,44 @@
 mod sadd;
+mod scard;
+mod setop;
+mod sintercard;
 mod sismember;
+mod smembers;
+mod smismember;
+mod smove;
+mod spop;
+mod srandmember;
+mod srem;
+mod sscan;
 
 pub(crate) use sadd::SAdd;
+pub(crate) use scard::SCard;
+pub(crate) use setop::{SDiff, SDiffStore, SInter, SInterStore, SUnion, SUnionStore};
+pub(crate) use sintercard::SInterCard;
 pub(crate) use sismember::SIsMember;
+pub(crate) use smembers::SMembers;
+pub(crate) use smismember::SMIsMember;
+pub(crate) use smove::SMove;
+pub(crate) use spop::SPop;
+pub(crate) use srandmember::SRandMember;
+pub(crate) use srem::SRem;
+pub(crate) use sscan::SScan;
+
+use crate::{BulkString, RespArray, RespFrame, RespSet};
+
+/// Reply the members as an array of bulk strings, they may repeat.
+fn members_reply(members: Vec<String>) -> RespFrame {
+    RespArray::new(bulk_strings(members)).into()
+}
+
+/// Reply distinct members as a set, an array for a RESP2 connection.
+fn set_reply(members: Vec<String>) -> RespFrame {
+    RespSet::new(bulk_strings(members)).into()
+}
+
+fn bulk_strings(members: Vec<String>) -> Vec<RespFrame> {
+    members
+        .into_iter()
+        .map(|m| BulkString::new(m).into())
+        .collect()
+}
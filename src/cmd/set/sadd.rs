@@ -1,5 +1,5 @@
 use crate::{
-    cmd::{command::CommandError, extract_args, validate_command, CommandExecutor},
+    cmd::{command::CommandError, extract_args, CommandExecutor},
     RespArray, RespFrame,
 };
 
@@ -12,17 +12,18 @@ pub struct SAdd {
 impl CommandExecutor for SAdd {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
         let (key, members) = (self.key, self.members);
-        let cnt = backend.sadd(key, members);
-        // RespFrame::Integer(cnt)
-        // RespFrame::BulkString(format!("{}(integer)", cnt).into())
-        cnt.into()
+        match backend.sadd(&key, members) {
+            // RespFrame::Integer(cnt)
+            // RespFrame::BulkString(format!("{}(integer)", cnt).into())
+            Ok(cnt) => cnt.into(),
+            Err(e) => e.into(),
+        }
     }
 }
 
 impl TryFrom<RespArray> for SAdd {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["sadd"], usize::MAX)?;
         let args = extract_args(value, 1)?.into_iter();
         let mut data = Vec::with_capacity(args.len());
         for arg in args {
@@ -0,0 +1,57 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// scard: https://redis.io/docs/latest/commands/scard/
+// SCARD key
+
+#[derive(Debug)]
+pub struct SCard {
+    key: String,
+}
+
+impl CommandExecutor for SCard {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.scard(&self.key) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SCard {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SCard {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_scard_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$5\r\nscard\r\n$3\r\nset\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SCard = frame.try_into()?;
+        assert_eq!(result.key, "set");
+
+        let backend = Backend::new();
+        backend.sadd("set", vec!["a".to_string(), "b".to_string()])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(2));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, SetOp,
+};
+
+use super::set_reply;
+
+// sinter: https://redis.io/docs/latest/commands/sinter/
+// SINTER key [key ...]
+// SUNION key [key ...]
+// SDIFF key [key ...]
+// SINTERSTORE destination key [key ...]
+// SUNIONSTORE destination key [key ...]
+// SDIFFSTORE destination key [key ...]
+
+#[derive(Debug)]
+struct SetOpArgs {
+    destination: Option<String>,
+    keys: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SInter(SetOpArgs);
+
+#[derive(Debug)]
+pub struct SUnion(SetOpArgs);
+
+#[derive(Debug)]
+pub struct SDiff(SetOpArgs);
+
+#[derive(Debug)]
+pub struct SInterStore(SetOpArgs);
+
+#[derive(Debug)]
+pub struct SUnionStore(SetOpArgs);
+
+#[derive(Debug)]
+pub struct SDiffStore(SetOpArgs);
+
+impl SetOpArgs {
+    fn execute(self, backend: &Backend, op: SetOp) -> RespFrame {
+        match self.destination {
+            Some(destination) => match backend.set_op_store(&destination, &self.keys, op) {
+                Ok(n) => n.into(),
+                Err(e) => e.into(),
+            },
+            None => match backend.set_op(&self.keys, op) {
+                Ok(set) => set_reply(set.into_iter().collect()),
+                Err(e) => e.into(),
+            },
+        }
+    }
+}
+
+fn parse_set_op_args(value: RespArray, store: bool) -> Result<SetOpArgs, CommandError> {
+    let mut args = CommandArgs::new(value, 1)?;
+    let destination = match store {
+        true => Some(args.next_string()?),
+        false => None,
+    };
+    Ok(SetOpArgs {
+        destination,
+        keys: args.rest_strings()?,
+    })
+}
+
+macro_rules! set_op_command {
+    ($name:ident, $op:expr, $store:literal) => {
+        impl CommandExecutor for $name {
+            fn execute(self, backend: &Backend) -> RespFrame {
+                self.0.execute(backend, $op)
+            }
+        }
+
+        impl TryFrom<RespArray> for $name {
+            type Error = CommandError;
+            fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+                Ok($name(parse_set_op_args(value, $store)?))
+            }
+        }
+    };
+}
+
+set_op_command!(SInter, SetOp::Inter, false);
+set_op_command!(SUnion, SetOp::Union, false);
+set_op_command!(SDiff, SetOp::Diff, false);
+set_op_command!(SInterStore, SetOp::Inter, true);
+set_op_command!(SUnionStore, SetOp::Union, true);
+set_op_command!(SDiffStore, SetOp::Diff, true);
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_sdiffstore_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$10\r\nsdiffstore\r\n$3\r\ndst\r\n$2\r\ns1\r\n$2\r\ns2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SDiffStore = frame.try_into()?;
+        assert_eq!(result.0.destination, Some("dst".to_string()));
+        assert_eq!(result.0.keys, vec!["s1", "s2"]);
+
+        let backend = Backend::new();
+        backend.sadd("s1", vec!["a".to_string(), "b".to_string()])?;
+        backend.sadd("s2", vec!["b".to_string()])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert_eq!(backend.smembers("dst")?, vec!["a"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sinter_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$6\r\nsinter\r\n$2\r\ns1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SInter = frame.try_into()?;
+        assert_eq!(result.0.destination, None);
+        assert_eq!(result.0.keys, vec!["s1"]);
+
+        Ok(())
+    }
+}
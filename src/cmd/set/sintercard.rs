@@ -0,0 +1,86 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// sintercard: https://redis.io/docs/latest/commands/sintercard/
+// SINTERCARD numkeys key [key ...] [LIMIT limit]
+
+#[derive(Debug)]
+pub struct SInterCard {
+    keys: Vec<String>,
+    limit: usize,
+}
+
+impl CommandExecutor for SInterCard {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.sintercard(&self.keys, self.limit) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SInterCard {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let numkeys = args.next_integer()?;
+        if numkeys <= 0 {
+            return Err(CommandError::InvalidArgument(
+                "numkeys should be greater than 0".to_string(),
+            ));
+        }
+        if numkeys as usize > args.len() {
+            return Err(CommandError::InvalidArgument(
+                "Number of keys can't be greater than number of args".to_string(),
+            ));
+        }
+        let keys = (0..numkeys)
+            .map(|_| args.next_string())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut limit = 0;
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "limit" => {
+                    limit = match args.next_integer()? {
+                        limit if limit >= 0 => limit as usize,
+                        _ => {
+                            return Err(CommandError::InvalidArgument(
+                                "LIMIT can't be negative".to_string(),
+                            ))
+                        }
+                    }
+                }
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok(SInterCard { keys, limit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_sintercard_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$10\r\nsintercard\r\n$1\r\n2\r\n$2\r\ns1\r\n$2\r\ns2\r\n$5\r\nLIMIT\r\n$1\r\n3\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SInterCard = frame.try_into()?;
+        assert_eq!(result.keys, vec!["s1", "s2"]);
+        assert_eq!(result.limit, 3);
+
+        Ok(())
+    }
+}
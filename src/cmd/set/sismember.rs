@@ -1,5 +1,5 @@
 use crate::{
-    cmd::{command::CommandError, extract_args, validate_command, CommandExecutor},
+    cmd::{command::CommandError, extract_args, CommandExecutor},
     RespArray, RespFrame,
 };
 
@@ -12,16 +12,16 @@ pub struct SIsMember {
 impl CommandExecutor for SIsMember {
     fn execute(self, backend: &crate::Backend) -> RespFrame {
         let (key, member) = (self.key, self.member);
-        let res = backend.sismember(&key, &member);
-        (res as i64).into()
+        match backend.sismember(&key, &member) {
+            Ok(res) => (res as i64).into(),
+            Err(e) => e.into(),
+        }
     }
 }
 
 impl TryFrom<RespArray> for SIsMember {
     type Error = CommandError;
     fn try_from(value: RespArray) -> Result<Self, Self::Error> {
-        validate_command(&value, &["sismember"], 2)?;
-
         let mut args = extract_args(value, 1)?.into_iter();
         match (args.next(), args.next()) {
             (Some(RespFrame::BulkString(key)), Some(RespFrame::BulkString(member))) => {
@@ -0,0 +1,55 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+use super::set_reply;
+
+// smembers: https://redis.io/docs/latest/commands/smembers/
+// SMEMBERS key
+
+#[derive(Debug)]
+pub struct SMembers {
+    key: String,
+}
+
+impl CommandExecutor for SMembers {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.smembers(&self.key) {
+            Ok(members) => set_reply(members),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SMembers {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SMembers {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_smembers_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$8\r\nsmembers\r\n$3\r\nset\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SMembers = frame.try_into()?;
+        assert_eq!(result.key, "set");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// smismember: https://redis.io/docs/latest/commands/smismember/
+// SMISMEMBER key member [member ...]
+
+#[derive(Debug)]
+pub struct SMIsMember {
+    key: String,
+    members: Vec<String>,
+}
+
+impl CommandExecutor for SMIsMember {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.smismember(&self.key, &self.members) {
+            Ok(res) => {
+                let frames = res.into_iter().map(RespFrame::Boolean).collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SMIsMember {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SMIsMember {
+            key: args.next_string()?,
+            members: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_smismember_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$10\r\nsmismember\r\n$3\r\nset\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SMIsMember = frame.try_into()?;
+        assert_eq!(result.key, "set");
+        assert_eq!(result.members, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.sadd("set", vec!["b".to_string()])?;
+        let expected = RespArray::new([RespFrame::Boolean(false), RespFrame::Boolean(true)]);
+        assert_eq!(result.execute(&backend), expected.into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// smove: https://redis.io/docs/latest/commands/smove/
+// SMOVE source destination member
+
+#[derive(Debug)]
+pub struct SMove {
+    source: String,
+    destination: String,
+    member: String,
+}
+
+impl CommandExecutor for SMove {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.smove(&self.source, &self.destination, &self.member) {
+            Ok(moved) => (moved as i64).into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SMove {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SMove {
+            source: args.next_string()?,
+            destination: args.next_string()?,
+            member: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_smove_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nsmove\r\n$3\r\nsrc\r\n$3\r\ndst\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SMove = frame.try_into()?;
+        assert_eq!(result.source, "src");
+        assert_eq!(result.destination, "dst");
+        assert_eq!(result.member, "a");
+
+        let backend = Backend::new();
+        backend.sadd("src", vec!["a".to_string()])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+        assert!(!backend.exists("src"));
+        assert!(backend.sismember("dst", "a")?);
+
+        Ok(())
+    }
+}
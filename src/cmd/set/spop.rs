@@ -0,0 +1,87 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+use super::set_reply;
+
+// spop: https://redis.io/docs/latest/commands/spop/
+// SPOP key [count]
+
+#[derive(Debug)]
+pub struct SPop {
+    key: String,
+    count: Option<usize>,
+}
+
+impl CommandExecutor for SPop {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let members = match backend.spop(&self.key, self.count.unwrap_or(1)) {
+            Ok(members) => members,
+            Err(e) => return e.into(),
+        };
+        match self.count {
+            Some(_) => set_reply(members),
+            // without count, reply the member itself
+            None => match members.into_iter().next() {
+                Some(member) => BulkString::new(member).into(),
+                None => RespFrame::Null(RespNull),
+            },
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SPop {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        if value.len() > 3 {
+            return Err(syntax_error());
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let count = match args.is_empty() {
+            true => None,
+            false => match args.next_integer()? {
+                count if count >= 0 => Some(count as usize),
+                _ => {
+                    return Err(CommandError::InvalidArgument(
+                        "value is out of range, must be positive".to_string(),
+                    ))
+                }
+            },
+        };
+        Ok(SPop { key, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_spop_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$4\r\nspop\r\n$3\r\nset\r\n$2\r\n-1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(SPop::try_from(frame).is_err());
+
+        buf.extend_from_slice(b"*2\r\n$4\r\nspop\r\n$3\r\nset\r\n");
+        let frame = RespArray::decode(&mut buf)?;
+        let result: SPop = frame.try_into()?;
+        assert_eq!(result.key, "set");
+        assert_eq!(result.count, None);
+
+        let backend = Backend::new();
+        backend.sadd("set", vec!["a".to_string()])?;
+        assert_eq!(result.execute(&backend), BulkString::new("a").into());
+        assert!(!backend.exists("set"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    cmd::{parse_random_count, syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull,
+};
+
+use super::members_reply;
+
+// srandmember: https://redis.io/docs/latest/commands/srandmember/
+// SRANDMEMBER key [count]
+// a positive count returns distinct members, a negative count allows the same member
+// to be returned multiple times
+
+#[derive(Debug)]
+pub struct SRandMember {
+    key: String,
+    count: Option<i64>,
+}
+
+impl CommandExecutor for SRandMember {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let members = match backend.srandmember(&self.key, self.count.unwrap_or(1)) {
+            Ok(members) => members,
+            Err(e) => return e.into(),
+        };
+        match self.count {
+            Some(_) => members_reply(members),
+            None => match members.into_iter().next() {
+                Some(member) => BulkString::new(member).into(),
+                None => RespFrame::Null(RespNull),
+            },
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SRandMember {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        if value.len() > 3 {
+            return Err(syntax_error());
+        }
+
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let count = match args.is_empty() {
+            true => None,
+            false => Some(parse_random_count(args.next_integer()?)?),
+        };
+        Ok(SRandMember { key, count })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_srandmember_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$11\r\nsrandmember\r\n$3\r\nset\r\n$2\r\n-3\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SRandMember = frame.try_into()?;
+        assert_eq!(result.key, "set");
+        assert_eq!(result.count, Some(-3));
+
+        let backend = Backend::new();
+        backend.sadd("set", vec!["a".to_string()])?;
+        let a: RespFrame = BulkString::new("a").into();
+        let expected = RespArray::new([a.clone(), a.clone(), a]);
+        assert_eq!(result.execute(&backend), expected.into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_srandmember_count_out_of_range() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*3\r\n$11\r\nsrandmember\r\n$3\r\nset\r\n$20\r\n-9223372036854775808\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+        let result: Result<SRandMember, _> = frame.try_into();
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// srem: https://redis.io/docs/latest/commands/srem/
+// SREM key member [member ...]
+
+#[derive(Debug)]
+pub struct SRem {
+    key: String,
+    members: Vec<String>,
+}
+
+impl CommandExecutor for SRem {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.srem(&self.key, &self.members) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SRem {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(SRem {
+            key: args.next_string()?,
+            members: args.rest_strings()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_srem_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nsrem\r\n$3\r\nset\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SRem = frame.try_into()?;
+        assert_eq!(result.key, "set");
+        assert_eq!(result.members, vec!["a", "b"]);
+
+        let backend = Backend::new();
+        backend.sadd("set", vec!["a".to_string(), "c".to_string()])?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
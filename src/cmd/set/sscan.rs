@@ -0,0 +1,91 @@
+use crate::{
+    cmd::{
+        key::{scan_reply, ScanOptions},
+        CommandArgs, CommandError, CommandExecutor,
+    },
+    Backend, BulkString, RespArray, RespFrame,
+};
+
+// sscan: https://redis.io/docs/latest/commands/sscan/
+// SSCAN key cursor [MATCH pattern] [COUNT count]
+
+#[derive(Debug)]
+pub struct SScan {
+    key: String,
+    cursor: u64,
+    options: ScanOptions,
+}
+
+impl CommandExecutor for SScan {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let options = self.options;
+        let ret = backend.sscan(
+            &self.key,
+            self.cursor,
+            options.pattern.as_deref(),
+            options.count,
+        );
+        match ret {
+            Ok((cursor, members)) => {
+                let items = members
+                    .into_iter()
+                    .map(|m| BulkString::from(m).into())
+                    .collect();
+                scan_reply(cursor, items)
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for SScan {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let (cursor, options) = ScanOptions::parse(&mut args, "sscan")?;
+        Ok(SScan {
+            key,
+            cursor,
+            options,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_sscan_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$5\r\nsscan\r\n$3\r\nset\r\n$2\r\n42\r\n$5\r\ncount\r\n$2\r\n20\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: SScan = frame.try_into()?;
+        assert_eq!(result.key, "set");
+        assert_eq!(result.cursor, 42);
+        assert_eq!(result.options.count, 20);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sscan_type_option_not_allowed() {
+        let result = SScan::try_from(RespArray::new([
+            BulkString::new("sscan").into(),
+            BulkString::new("set").into(),
+            BulkString::new("0").into(),
+            BulkString::new("type").into(),
+            BulkString::new("string").into(),
+        ]));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,123 @@
+mod xack;
+mod xadd;
+mod xautoclaim;
+mod xclaim;
+mod xdel;
+mod xgroup;
+mod xinfo;
+mod xlen;
+mod xpending;
+mod xrange;
+mod xread;
+mod xreadgroup;
+mod xtrim;
+
+pub(crate) use xack::XAck;
+pub(crate) use xadd::XAdd;
+pub(crate) use xautoclaim::XAutoClaim;
+pub(crate) use xclaim::XClaim;
+pub(crate) use xdel::XDel;
+pub(crate) use xgroup::XGroup;
+pub(crate) use xinfo::XInfo;
+pub(crate) use xlen::XLen;
+pub(crate) use xpending::XPending;
+pub(crate) use xrange::{XRange, XRevRange};
+pub(crate) use xread::XRead;
+pub(crate) use xreadgroup::XReadGroup;
+pub(crate) use xtrim::XTrim;
+
+use std::ops::Bound;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError},
+    BulkString, RespArray, RespFrame, StreamEntry, StreamId, StreamTrim, TrimStrategy,
+};
+
+fn invalid_id() -> CommandError {
+    CommandError::InvalidArgument(
+        "Invalid stream ID specified as stream command argument".to_string(),
+    )
+}
+
+/// Parse `ms-seq`, or `ms` with the given sequence.
+fn parse_id(id: &str, missing_seq: u64) -> Result<StreamId, CommandError> {
+    let (ms, seq) = match id.split_once('-') {
+        Some((ms, seq)) => (ms, seq.parse().map_err(|_| invalid_id())?),
+        None => (id, missing_seq),
+    };
+    Ok(StreamId::new(ms.parse().map_err(|_| invalid_id())?, seq))
+}
+
+/// Parse a bound of XRANGE, `-` and `+` are the smallest and the greatest IDs,
+/// `(` makes it exclusive.
+fn parse_bound(bound: &str, missing_seq: u64) -> Result<Bound<StreamId>, CommandError> {
+    Ok(match bound {
+        "-" => Bound::Included(StreamId::MIN),
+        "+" => Bound::Included(StreamId::MAX),
+        _ => match bound.strip_prefix('(') {
+            Some(id) => Bound::Excluded(parse_id(id, missing_seq)?),
+            None => Bound::Included(parse_id(bound, missing_seq)?),
+        },
+    })
+}
+
+/// Parse the rest of the MAXLEN / MINID option: [= | ~] threshold [LIMIT count]
+fn parse_trim(strategy: &str, args: &mut CommandArgs) -> Result<StreamTrim, CommandError> {
+    let approx = match args.peek_option().as_deref() {
+        Some(op @ ("~" | "=")) => {
+            let approx = op == "~";
+            args.next_frame()?;
+            approx
+        }
+        _ => false,
+    };
+
+    let strategy = match strategy {
+        "maxlen" => match args.next_integer()? {
+            len if len >= 0 => TrimStrategy::MaxLen(len as usize),
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "The MAXLEN argument must be >= 0.".to_string(),
+                ))
+            }
+        },
+        _ => TrimStrategy::MinId(parse_id(&args.next_string()?, 0)?),
+    };
+
+    let mut limit = None;
+    if args.peek_option().as_deref() == Some("limit") {
+        args.next_frame()?;
+        if !approx {
+            return Err(CommandError::InvalidArgument(
+                "syntax error, LIMIT cannot be used without the special ~ option".to_string(),
+            ));
+        }
+        limit = match args.next_integer()? {
+            limit if limit >= 0 => Some(limit as usize),
+            _ => return Err(syntax_error()),
+        };
+    }
+    Ok(StreamTrim {
+        strategy,
+        approx,
+        limit,
+    })
+}
+
+/// Reply an entry as an array of its ID and the array of its field-value pairs.
+fn entry_reply((id, fields): StreamEntry) -> RespFrame {
+    let fields = fields
+        .into_iter()
+        .flat_map(|(field, value)| [BulkString::new(field).into(), BulkString::new(value).into()])
+        .collect::<Vec<RespFrame>>();
+    RespArray::new([
+        BulkString::new(id.to_string()).into(),
+        RespArray::new(fields).into(),
+    ])
+    .into()
+}
+
+fn entries_reply(entries: Vec<StreamEntry>) -> RespFrame {
+    let frames = entries.into_iter().map(entry_reply).collect::<Vec<_>>();
+    RespArray::new(frames).into()
+}
@@ -0,0 +1,72 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, StreamId,
+};
+
+use super::parse_id;
+
+// xack: https://redis.io/docs/latest/commands/xack/
+// XACK key group id [id ...]
+
+#[derive(Debug)]
+pub struct XAck {
+    key: String,
+    group: String,
+    ids: Vec<StreamId>,
+}
+
+impl CommandExecutor for XAck {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xack(&self.key, &self.group, &self.ids) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XAck {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (key, group) = (args.next_string()?, args.next_string()?);
+        let ids = args
+            .rest_strings()?
+            .iter()
+            .map(|id| parse_id(id, 0))
+            .collect::<Result<_, _>>()?;
+        Ok(XAck { key, group, ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xack_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*5\r\n$4\r\nxack\r\n$6\r\nstream\r\n$1\r\ng\r\n$3\r\n1-0\r\n$1\r\n2\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XAck = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(result.group, "g");
+        assert_eq!(result.ids, vec![StreamId::new(1, 0), StreamId::new(2, 0)]);
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        backend.xgroup_create("stream", "g", Some(StreamId::MIN), false)?;
+        let keys = ["stream".to_string()];
+        backend.xreadgroup("g", "alice", &keys, &[None], None, false)?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    cmd::{wrong_arity, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNull, StreamFields, StreamTrim, XAddId,
+};
+
+use super::{invalid_id, parse_id, parse_trim};
+
+// xadd: https://redis.io/docs/latest/commands/xadd/
+// XADD key [NOMKSTREAM] [<MAXLEN | MINID> [= | ~] threshold [LIMIT count]]
+//   <* | id> field value [field value ...]
+
+#[derive(Debug)]
+pub struct XAdd {
+    key: String,
+    nomkstream: bool,
+    trim: Option<StreamTrim>,
+    id: XAddId,
+    fields: StreamFields,
+}
+
+impl CommandExecutor for XAdd {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let trim = self.trim.as_ref();
+        match backend.xadd(&self.key, self.id, self.fields, self.nomkstream, trim) {
+            Ok(Some(id)) => BulkString::new(id.to_string()).into(),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XAdd {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let (mut nomkstream, mut trim) = (false, None);
+        while let Some(option) = args.peek_option() {
+            match option.as_str() {
+                "nomkstream" => {
+                    args.next_frame()?;
+                    nomkstream = true;
+                }
+                "maxlen" | "minid" => {
+                    args.next_frame()?;
+                    trim = Some(parse_trim(&option, &mut args)?);
+                }
+                _ => break,
+            }
+        }
+
+        let id = args.next_string()?;
+        let id = match id.as_str() {
+            "*" => XAddId::Auto,
+            _ => match id.strip_suffix("-*") {
+                Some(ms) => XAddId::AutoSeq(ms.parse().map_err(|_| invalid_id())?),
+                None => XAddId::Explicit(parse_id(&id, 0)?),
+            },
+        };
+
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(wrong_arity("xadd"));
+        }
+        let mut fields = Vec::with_capacity(args.len() / 2);
+        while !args.is_empty() {
+            fields.push((args.next_bytes()?, args.next_bytes()?));
+        }
+        Ok(XAdd {
+            key,
+            nomkstream,
+            trim,
+            id,
+            fields,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, StreamId, TrimStrategy};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xadd_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*9\r\n$4\r\nxadd\r\n$6\r\nstream\r\n$6\r\nMAXLEN\r\n$1\r\n~\r\n$2\r\n10\r\n\
+              $3\r\n1-1\r\n$4\r\nname\r\n$3\r\nbob\r\n$1\r\nx\r\n",
+        );
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(XAdd::try_from(frame).is_err());
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*10\r\n$4\r\nxadd\r\n$6\r\nstream\r\n$6\r\nMAXLEN\r\n$1\r\n~\r\n$2\r\n10\r\n\
+              $3\r\n1-1\r\n$4\r\nname\r\n$3\r\nbob\r\n$3\r\nage\r\n$2\r\n42\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XAdd = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(result.id, XAddId::Explicit(StreamId::new(1, 1)));
+        assert_eq!(
+            result.trim,
+            Some(StreamTrim {
+                strategy: TrimStrategy::MaxLen(10),
+                approx: true,
+                limit: None,
+            })
+        );
+        assert_eq!(result.fields.len(), 2);
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), BulkString::new("1-1").into());
+        assert_eq!(backend.xlen("stream")?, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::Bound;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, StreamId,
+};
+
+use super::{entries_reply, parse_bound};
+
+// xautoclaim: https://redis.io/docs/latest/commands/xautoclaim/
+// XAUTOCLAIM key group consumer min-idle-time start [COUNT count] [JUSTID]
+
+#[derive(Debug)]
+pub struct XAutoClaim {
+    key: String,
+    group: String,
+    consumer: String,
+    min_idle: i64,
+    start: StreamId,
+    count: usize,
+    just_id: bool,
+}
+
+fn ids_reply(ids: impl IntoIterator<Item = StreamId>) -> RespFrame {
+    let frames = ids
+        .into_iter()
+        .map(|id| BulkString::new(id.to_string()).into())
+        .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+}
+
+impl CommandExecutor for XAutoClaim {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xautoclaim(
+            &self.key,
+            &self.group,
+            &self.consumer,
+            self.min_idle,
+            self.start,
+            self.count,
+            self.just_id,
+        ) {
+            Ok((next, entries, deleted)) => {
+                let claimed = match self.just_id {
+                    true => ids_reply(entries.into_iter().map(|(id, _)| id)),
+                    false => entries_reply(entries),
+                };
+                RespArray::new([
+                    BulkString::new(next.to_string()).into(),
+                    claimed,
+                    ids_reply(deleted),
+                ])
+                .into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XAutoClaim {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (key, group, consumer) = (
+            args.next_string()?,
+            args.next_string()?,
+            args.next_string()?,
+        );
+        let min_idle = match args.next_integer()? {
+            n if n >= 0 => n,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "Invalid min-idle-time argument for XAUTOCLAIM".to_string(),
+                ))
+            }
+        };
+        // the start is inclusive, `(` starts after the ID
+        let start = match parse_bound(&args.next_string()?, 0)? {
+            Bound::Excluded(id) => id.next().unwrap_or(StreamId::MAX),
+            Bound::Included(id) => id,
+            Bound::Unbounded => StreamId::MIN,
+        };
+
+        let (mut count, mut just_id) = (100, false);
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "count" => {
+                    count = match args.next_integer()? {
+                        n if n > 0 && n <= i64::MAX / 10 => n as usize,
+                        _ => {
+                            return Err(CommandError::InvalidArgument(
+                                "COUNT must be > 0".to_string(),
+                            ))
+                        }
+                    }
+                }
+                "justid" => just_id = true,
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok(XAutoClaim {
+            key,
+            group,
+            consumer,
+            min_idle,
+            start,
+            count,
+            just_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xautoclaim_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*9\r\n$10\r\nxautoclaim\r\n$6\r\nstream\r\n$1\r\ng\r\n$3\r\nbob\r\n$1\r\n0\r\n\
+              $1\r\n0\r\n$5\r\nCOUNT\r\n$1\r\n1\r\n$6\r\nJUSTID\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XAutoClaim = frame.try_into()?;
+        assert_eq!(result.start, StreamId::MIN);
+        assert_eq!(result.count, 1);
+        assert!(result.just_id);
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        backend.xgroup_create("stream", "g", Some(StreamId::MIN), false)?;
+        let keys = ["stream".to_string()];
+        backend.xreadgroup("g", "alice", &keys, &[None], None, false)?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([
+                BulkString::new("0-0").into(),
+                RespArray::new([BulkString::new("1-0").into()]).into(),
+                RespArray::new([]).into(),
+            ])
+            .into()
+        );
+
+        Ok(())
+    }
+}
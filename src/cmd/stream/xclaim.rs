@@ -0,0 +1,142 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    now_ms, Backend, BulkString, ClaimOptions, RespArray, RespFrame, StreamId,
+};
+
+use super::{entries_reply, parse_id};
+
+// xclaim: https://redis.io/docs/latest/commands/xclaim/
+// XCLAIM key group consumer min-idle-time id [id ...] [IDLE ms]
+//   [TIME unix-time-milliseconds] [RETRYCOUNT count] [FORCE] [JUSTID] [LASTID lastid]
+
+#[derive(Debug)]
+pub struct XClaim {
+    key: String,
+    group: String,
+    consumer: String,
+    min_idle: i64,
+    ids: Vec<StreamId>,
+    opts: ClaimOptions,
+}
+
+impl CommandExecutor for XClaim {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xclaim(
+            &self.key,
+            &self.group,
+            &self.consumer,
+            self.min_idle,
+            &self.ids,
+            &self.opts,
+        ) {
+            Ok(entries) if self.opts.just_id => {
+                let ids = entries
+                    .into_iter()
+                    .map(|(id, _)| BulkString::new(id.to_string()).into())
+                    .collect::<Vec<RespFrame>>();
+                RespArray::new(ids).into()
+            }
+            Ok(entries) => entries_reply(entries),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+fn next_non_negative(args: &mut CommandArgs, name: &str) -> Result<i64, CommandError> {
+    match args.next_integer()? {
+        n if n >= 0 => Ok(n),
+        _ => Err(CommandError::InvalidArgument(format!(
+            "Invalid {} argument for XCLAIM",
+            name
+        ))),
+    }
+}
+
+impl TryFrom<RespArray> for XClaim {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (key, group, consumer) = (
+            args.next_string()?,
+            args.next_string()?,
+            args.next_string()?,
+        );
+        let min_idle = next_non_negative(&mut args, "min-idle-time")?;
+
+        // the IDs go on until the first option
+        let mut ids = Vec::new();
+        while let Some(arg) = args.peek_option() {
+            match parse_id(&arg, 0) {
+                Ok(id) => ids.push(id),
+                Err(_) if !ids.is_empty() => break,
+                Err(e) => return Err(e),
+            }
+            args.next_frame()?;
+        }
+
+        let mut opts = ClaimOptions::default();
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "idle" => opts.idle = Some(next_non_negative(&mut args, "IDLE")?),
+                "time" => opts.time = Some(next_non_negative(&mut args, "TIME")?),
+                "retrycount" => {
+                    opts.retry_count = Some(next_non_negative(&mut args, "RETRYCOUNT")? as u64)
+                }
+                "force" => opts.force = true,
+                "justid" => opts.just_id = true,
+                "lastid" => opts.last_id = Some(parse_id(&args.next_string()?, 0)?),
+                _ => return Err(syntax_error()),
+            }
+        }
+        // a delivery time in the future is the current time
+        opts.time = opts.time.map(|time| time.min(now_ms()));
+        Ok(XClaim {
+            key,
+            group,
+            consumer,
+            min_idle,
+            ids,
+            opts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xclaim_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*9\r\n$6\r\nxclaim\r\n$6\r\nstream\r\n$1\r\ng\r\n$3\r\nbob\r\n$1\r\n0\r\n\
+              $3\r\n1-0\r\n$1\r\n2\r\n$10\r\nRETRYCOUNT\r\n$1\r\n5\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XClaim = frame.try_into()?;
+        assert_eq!(result.consumer, "bob");
+        assert_eq!(result.min_idle, 0);
+        assert_eq!(result.ids, vec![StreamId::new(1, 0), StreamId::new(2, 0)]);
+        assert_eq!(result.opts.retry_count, Some(5));
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        backend.xgroup_create("stream", "g", Some(StreamId::MIN), false)?;
+        let keys = ["stream".to_string()];
+        backend.xreadgroup("g", "alice", &keys, &[None], None, false)?;
+        let RespFrame::Array(reply) = result.execute(&backend) else {
+            panic!("expected an array");
+        };
+        assert_eq!(reply.len(), 1);
+        let pending = backend.xpending_summary("stream", "g")?;
+        assert_eq!(pending.consumers, [("bob".to_string(), 1)]);
+
+        Ok(())
+    }
+}
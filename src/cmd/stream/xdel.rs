@@ -0,0 +1,65 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, StreamId,
+};
+
+use super::parse_id;
+
+// xdel: https://redis.io/docs/latest/commands/xdel/
+// XDEL key id [id ...]
+
+#[derive(Debug)]
+pub struct XDel {
+    key: String,
+    ids: Vec<StreamId>,
+}
+
+impl CommandExecutor for XDel {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xdel(&self.key, &self.ids) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XDel {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let ids = args
+            .rest_strings()?
+            .iter()
+            .map(|id| parse_id(id, 0))
+            .collect::<Result<_, _>>()?;
+        Ok(XDel { key, ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xdel_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$4\r\nxdel\r\n$6\r\nstream\r\n$3\r\n1-0\r\n$1\r\n2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XDel = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(result.ids, vec![StreamId::new(1, 0), StreamId::new(2, 0)]);
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame, StreamId,
+};
+
+use super::parse_id;
+
+// xgroup: https://redis.io/docs/latest/commands/xgroup/
+// XGROUP CREATE key group <id | $> [MKSTREAM] [ENTRIESREAD entries-read]
+// XGROUP SETID key group <id | $> [ENTRIESREAD entries-read]
+// XGROUP DESTROY key group
+// XGROUP CREATECONSUMER key group consumer
+// XGROUP DELCONSUMER key group consumer
+
+#[derive(Debug, PartialEq)]
+enum XGroupOp {
+    Create {
+        id: Option<StreamId>, // None is `$`, the last ID of the stream
+        mkstream: bool,
+    },
+    SetId(Option<StreamId>),
+    Destroy,
+    CreateConsumer(String),
+    DelConsumer(String),
+}
+
+#[derive(Debug)]
+pub struct XGroup {
+    key: String,
+    group: String,
+    op: XGroupOp,
+}
+
+impl CommandExecutor for XGroup {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let (key, group) = (&self.key, &self.group);
+        let ret = match self.op {
+            XGroupOp::Create { id, mkstream } => backend
+                .xgroup_create(key, group, id, mkstream)
+                .map(|_| RESP_OK.clone()),
+            XGroupOp::SetId(id) => backend
+                .xgroup_setid(key, group, id)
+                .map(|_| RESP_OK.clone()),
+            XGroupOp::Destroy => backend
+                .xgroup_destroy(key, group)
+                .map(|destroyed| (destroyed as i64).into()),
+            XGroupOp::CreateConsumer(consumer) => backend
+                .xgroup_createconsumer(key, group, &consumer)
+                .map(|created| (created as i64).into()),
+            XGroupOp::DelConsumer(consumer) => backend
+                .xgroup_delconsumer(key, group, &consumer)
+                .map(|pending| pending.into()),
+        };
+        ret.unwrap_or_else(|e| e.into())
+    }
+}
+
+/// Parse `<id | $>`, followed by the options of CREATE / SETID.
+fn parse_group_id(
+    args: &mut CommandArgs,
+    allow_mkstream: bool,
+) -> Result<(Option<StreamId>, bool), CommandError> {
+    let id = match args.next_string()?.as_str() {
+        "$" => None,
+        id => Some(parse_id(id, 0)?),
+    };
+    let mut mkstream = false;
+    while let Some(option) = args.next_option()? {
+        match option.as_str() {
+            "mkstream" if allow_mkstream => mkstream = true,
+            // the read counter is derived from the stream, so the hint is ignored
+            "entriesread" => {
+                if args.next_integer()? < -1 {
+                    return Err(CommandError::InvalidArgument(
+                        "value for ENTRIESREAD must be positive or -1".to_string(),
+                    ));
+                }
+            }
+            _ => return Err(syntax_error()),
+        }
+    }
+    Ok((id, mkstream))
+}
+
+impl TryFrom<RespArray> for XGroup {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let subcommand = args.next_option()?.unwrap_or_default();
+        let (key, group) = (args.next_string()?, args.next_string()?);
+        let op = match subcommand.as_str() {
+            "create" => {
+                let (id, mkstream) = parse_group_id(&mut args, true)?;
+                XGroupOp::Create { id, mkstream }
+            }
+            "setid" => XGroupOp::SetId(parse_group_id(&mut args, false)?.0),
+            "destroy" => XGroupOp::Destroy,
+            "createconsumer" => XGroupOp::CreateConsumer(args.next_string()?),
+            "delconsumer" => XGroupOp::DelConsumer(args.next_string()?),
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand '{}'. Try XGROUP HELP.",
+                    subcommand
+                )))
+            }
+        };
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        Ok(XGroup { key, group, op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xgroup_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$6\r\nxgroup\r\n$6\r\nCREATE\r\n$6\r\nstream\r\n$5\r\ngroup\r\n$1\r\n$\r\n$8\r\nMKSTREAM\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XGroup = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(result.group, "group");
+        assert_eq!(
+            result.op,
+            XGroupOp::Create {
+                id: None,
+                mkstream: true
+            }
+        );
+
+        let backend = Backend::new();
+        assert_eq!(result.execute(&backend), RESP_OK.clone());
+        assert_eq!(backend.xinfo_groups("stream")?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, ConsumerInfo, GroupInfo, RespArray, RespFrame, RespMap, RespNull,
+    StreamInfo,
+};
+
+use super::entry_reply;
+
+// xinfo: https://redis.io/docs/latest/commands/xinfo/
+// XINFO STREAM key
+// XINFO GROUPS key
+// XINFO CONSUMERS key group
+
+#[derive(Debug, PartialEq)]
+enum XInfoOp {
+    Stream(String),
+    Groups(String),
+    Consumers(String, String),
+}
+
+#[derive(Debug)]
+pub struct XInfo {
+    op: XInfoOp,
+}
+
+/// Reply the name-value pairs as a map, a flat array for a RESP2 connection.
+fn info_reply<const N: usize>(pairs: [(&str, RespFrame); N]) -> RespFrame {
+    let map = pairs
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    RespMap(map).into()
+}
+
+fn id_frame(id: impl ToString) -> RespFrame {
+    BulkString::new(id.to_string()).into()
+}
+
+fn stream_reply(info: StreamInfo) -> RespFrame {
+    let entry = |entry| match entry {
+        Some(entry) => entry_reply(entry),
+        None => RespFrame::Null(RespNull),
+    };
+    info_reply([
+        ("length", (info.length as i64).into()),
+        ("last-generated-id", id_frame(info.last_generated_id)),
+        ("max-deleted-entry-id", id_frame(info.max_deleted_id)),
+        ("entries-added", (info.entries_added as i64).into()),
+        ("recorded-first-entry-id", id_frame(info.first_id)),
+        ("groups", (info.groups as i64).into()),
+        ("first-entry", entry(info.first_entry)),
+        ("last-entry", entry(info.last_entry)),
+    ])
+}
+
+fn group_reply(info: GroupInfo) -> RespFrame {
+    info_reply([
+        ("name", BulkString::new(info.name).into()),
+        ("consumers", (info.consumers as i64).into()),
+        ("pending", (info.pending as i64).into()),
+        ("last-delivered-id", id_frame(info.last_delivered_id)),
+        ("entries-read", (info.entries_read as i64).into()),
+        ("lag", (info.lag as i64).into()),
+    ])
+}
+
+fn consumer_reply(info: ConsumerInfo) -> RespFrame {
+    info_reply([
+        ("name", BulkString::new(info.name).into()),
+        ("pending", (info.pending as i64).into()),
+        ("idle", info.idle.into()),
+        ("inactive", info.inactive.unwrap_or(-1).into()),
+    ])
+}
+
+impl CommandExecutor for XInfo {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let ret = match self.op {
+            XInfoOp::Stream(key) => backend.xinfo_stream(&key).map(stream_reply),
+            XInfoOp::Groups(key) => backend.xinfo_groups(&key).map(|groups| {
+                RespArray::new(groups.into_iter().map(group_reply).collect::<Vec<_>>()).into()
+            }),
+            XInfoOp::Consumers(key, group) => {
+                backend.xinfo_consumers(&key, &group).map(|consumers| {
+                    let frames = consumers.into_iter().map(consumer_reply);
+                    RespArray::new(frames.collect::<Vec<_>>()).into()
+                })
+            }
+        };
+        ret.unwrap_or_else(|e| e.into())
+    }
+}
+
+impl TryFrom<RespArray> for XInfo {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let subcommand = args.next_option()?.unwrap_or_default();
+        let op = match (subcommand.as_str(), args.len()) {
+            ("stream", 1) => XInfoOp::Stream(args.next_string()?),
+            ("groups", 1) => XInfoOp::Groups(args.next_string()?),
+            ("consumers", 2) => XInfoOp::Consumers(args.next_string()?, args.next_string()?),
+            _ => {
+                return Err(CommandError::InvalidArgument(format!(
+                    "unknown subcommand or wrong number of arguments for '{}'. Try XINFO HELP.",
+                    subcommand
+                )))
+            }
+        };
+        Ok(XInfo { op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, StreamId, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xinfo_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$5\r\nxinfo\r\n$6\r\nGROUPS\r\n$6\r\nstream\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XInfo = frame.try_into()?;
+        assert_eq!(result.op, XInfoOp::Groups("stream".to_string()));
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        backend.xgroup_create("stream", "g", Some(StreamId::MIN), false)?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([info_reply([
+                ("name", BulkString::new("g").into()),
+                ("consumers", 0.into()),
+                ("pending", 0.into()),
+                ("last-delivered-id", id_frame("0-0")),
+                ("entries-read", 0.into()),
+                ("lag", 1.into()),
+            ])])
+            .into()
+        );
+
+        Ok(())
+    }
+}
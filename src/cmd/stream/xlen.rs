@@ -0,0 +1,57 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// xlen: https://redis.io/docs/latest/commands/xlen/
+// XLEN key
+
+#[derive(Debug)]
+pub struct XLen {
+    key: String,
+}
+
+impl CommandExecutor for XLen {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xlen(&self.key) {
+            Ok(len) => len.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XLen {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(XLen {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xlen_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$4\r\nxlen\r\n$6\r\nstream\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XLen = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::Auto, vec![], false, None)?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, PendingRange, RespArray, RespFrame, RespNull,
+};
+
+use super::parse_bound;
+
+// xpending: https://redis.io/docs/latest/commands/xpending/
+// XPENDING key group [[IDLE min-idle-time] start end count [consumer]]
+
+#[derive(Debug)]
+pub struct XPending {
+    key: String,
+    group: String,
+    range: Option<PendingRange>, // None for the summary form
+}
+
+impl CommandExecutor for XPending {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        let Some(range) = self.range else {
+            return match backend.xpending_summary(&self.key, &self.group) {
+                Ok(summary) => {
+                    let (min, max) = match summary.range {
+                        Some((min, max)) => (
+                            BulkString::new(min.to_string()).into(),
+                            BulkString::new(max.to_string()).into(),
+                        ),
+                        None => (RespFrame::Null(RespNull), RespFrame::Null(RespNull)),
+                    };
+                    let consumers = match summary.consumers.is_empty() {
+                        true => RespFrame::Null(RespNull),
+                        false => {
+                            let frames = summary
+                                .consumers
+                                .into_iter()
+                                .map(|(name, count)| {
+                                    RespArray::new([
+                                        BulkString::new(name).into(),
+                                        BulkString::new(count.to_string()).into(),
+                                    ])
+                                    .into()
+                                })
+                                .collect::<Vec<RespFrame>>();
+                            RespArray::new(frames).into()
+                        }
+                    };
+                    RespArray::new([(summary.count as i64).into(), min, max, consumers]).into()
+                }
+                Err(e) => e.into(),
+            };
+        };
+
+        match backend.xpending(&self.key, &self.group, &range) {
+            Ok(pending) => {
+                let frames = pending
+                    .into_iter()
+                    .map(|p| {
+                        RespArray::new([
+                            BulkString::new(p.id.to_string()).into(),
+                            BulkString::new(p.consumer).into(),
+                            p.idle.into(),
+                            (p.delivery_count as i64).into(),
+                        ])
+                        .into()
+                    })
+                    .collect::<Vec<RespFrame>>();
+                RespArray::new(frames).into()
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XPending {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (key, group) = (args.next_string()?, args.next_string()?);
+        if args.is_empty() {
+            return Ok(XPending {
+                key,
+                group,
+                range: None,
+            });
+        }
+
+        let mut min_idle = None;
+        if args.peek_option().as_deref() == Some("idle") {
+            args.next_frame()?;
+            min_idle = Some(args.next_integer()?.max(0));
+        }
+        let start = parse_bound(&args.next_string()?, 0)?;
+        let end = parse_bound(&args.next_string()?, u64::MAX)?;
+        let count = args.next_integer()?.max(0) as usize;
+        let consumer = match args.is_empty() {
+            true => None,
+            false => Some(args.next_string()?),
+        };
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        Ok(XPending {
+            key,
+            group,
+            range: Some(PendingRange {
+                start,
+                end,
+                count,
+                consumer,
+                min_idle,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use crate::{RespDecode, StreamId, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xpending_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$8\r\nxpending\r\n$6\r\nstream\r\n$1\r\ng\r\n$1\r\n-\r\n$1\r\n+\r\n$2\r\n10\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XPending = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(
+            result.range,
+            Some(PendingRange {
+                start: Bound::Included(StreamId::MIN),
+                end: Bound::Included(StreamId::MAX),
+                count: 10,
+                consumer: None,
+                min_idle: None,
+            })
+        );
+
+        let backend = Backend::new();
+        backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        backend.xgroup_create("stream", "g", Some(StreamId::MIN), false)?;
+        let keys = ["stream".to_string()];
+        backend.xreadgroup("g", "alice", &keys, &[None], None, false)?;
+        let RespFrame::Array(reply) = result.execute(&backend) else {
+            panic!("expected an array");
+        };
+        assert_eq!(reply.len(), 1);
+
+        Ok(())
+    }
+}
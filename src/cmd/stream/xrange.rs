@@ -0,0 +1,141 @@
+use std::ops::Bound;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, StreamId,
+};
+
+use super::{entries_reply, parse_bound};
+
+// xrange: https://redis.io/docs/latest/commands/xrange/
+// XRANGE key start end [COUNT count]
+// XREVRANGE key end start [COUNT count]
+
+#[derive(Debug)]
+struct XRangeArgs {
+    key: String,
+    start: Bound<StreamId>,
+    end: Bound<StreamId>,
+    count: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct XRange(XRangeArgs);
+
+#[derive(Debug)]
+pub struct XRevRange(XRangeArgs);
+
+impl XRangeArgs {
+    fn execute(self, backend: &Backend, rev: bool) -> RespFrame {
+        match backend.xrange(&self.key, self.start, self.end, self.count, rev) {
+            Ok(entries) => entries_reply(entries),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for XRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, false)
+    }
+}
+
+impl CommandExecutor for XRevRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, true)
+    }
+}
+
+fn parse_xrange_args(value: RespArray, rev: bool) -> Result<XRangeArgs, CommandError> {
+    if !matches!(value.len(), 4 | 6) {
+        return Err(syntax_error());
+    }
+
+    let mut args = CommandArgs::new(value, 1)?;
+    let key = args.next_string()?;
+    let (first, second) = (args.next_string()?, args.next_string()?);
+    let (start, end) = match rev {
+        true => (second, first),
+        false => (first, second),
+    };
+    let count = match args.next_option()?.as_deref() {
+        None => None,
+        // a negative count returns nothing
+        Some("count") => Some(args.next_integer()?.max(0) as usize),
+        Some(_) => return Err(syntax_error()),
+    };
+    Ok(XRangeArgs {
+        key,
+        start: parse_bound(&start, 0)?,
+        end: parse_bound(&end, u64::MAX)?,
+        count,
+    })
+}
+
+impl TryFrom<RespArray> for XRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(XRange(parse_xrange_args(value, false)?))
+    }
+}
+
+impl TryFrom<RespArray> for XRevRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(XRevRange(parse_xrange_args(value, true)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$6\r\nxrange\r\n$6\r\nstream\r\n$1\r\n-\r\n$2\r\n(2\r\n$5\r\nCOUNT\r\n$1\r\n5\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XRange = frame.try_into()?;
+        assert_eq!(result.0.key, "stream");
+        assert_eq!(result.0.start, Bound::Included(StreamId::MIN));
+        assert_eq!(result.0.end, Bound::Excluded(StreamId::new(2, u64::MAX)));
+        assert_eq!(result.0.count, Some(5));
+
+        let backend = Backend::new();
+        let fields = vec![(b"a".to_vec(), b"1".to_vec())];
+        backend.xadd("stream", XAddId::AutoSeq(1), fields, false, None)?;
+        assert_eq!(
+            result.execute(&backend),
+            RespArray::new([RespArray::new([
+                BulkString::new("1-0").into(),
+                RespArray::new([BulkString::new("a").into(), BulkString::new("1").into()]).into(),
+            ])
+            .into()])
+            .into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_xrevrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$9\r\nxrevrange\r\n$6\r\nstream\r\n$1\r\n+\r\n$3\r\n1-1\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XRevRange = frame.try_into()?;
+        assert_eq!(result.0.start, Bound::Included(StreamId::new(1, 1)));
+        assert_eq!(result.0.end, Bound::Included(StreamId::MAX));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, RespArray, RespFrame, RespNullArray, StreamEntry, XReadId,
+};
+
+use super::{entries_reply, parse_id};
+
+// xread: https://redis.io/docs/latest/commands/xread/
+// XREAD [COUNT count] [BLOCK milliseconds] STREAMS key [key ...] id [id ...]
+
+#[derive(Debug)]
+pub struct XRead {
+    count: Option<usize>,
+    block: bool,
+    timeout: Option<Duration>, // None blocks forever
+    keys: Vec<String>,
+    ids: Vec<XReadId>,
+}
+
+/// Reply the entries of each stream as an array of the key and its entries.
+fn streams_reply(streams: Vec<(String, Vec<StreamEntry>)>) -> RespFrame {
+    if streams.is_empty() {
+        return RespFrame::NullArray(RespNullArray);
+    }
+    let frames = streams
+        .into_iter()
+        .map(|(key, entries)| {
+            RespArray::new([BulkString::new(key).into(), entries_reply(entries)]).into()
+        })
+        .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+}
+
+/// Without blocking, e.g. inside a transaction, it returns nil if there is nothing to read
+impl CommandExecutor for XRead {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xread(&self.keys, &self.ids, self.count) {
+            Ok(streams) => streams_reply(streams),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl XRead {
+    pub async fn execute_blocking(self, backend: &Backend) -> RespFrame {
+        if !self.block {
+            return self.execute(backend);
+        }
+        match backend
+            .xread_block(&self.keys, &self.ids, self.count, self.timeout)
+            .await
+        {
+            Ok(Some(streams)) => streams_reply(streams),
+            Ok(None) => RespFrame::NullArray(RespNullArray),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XRead {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let (mut count, mut block, mut timeout) = (None, false, None);
+        loop {
+            match args.next_option()?.as_deref() {
+                // COUNT 0 means no limit
+                Some("count") => {
+                    count = match args.next_integer()? {
+                        n if n > 0 => Some(n as usize),
+                        _ => None,
+                    }
+                }
+                Some("block") => {
+                    let ms = args.next_integer()?;
+                    if ms < 0 {
+                        return Err(CommandError::InvalidArgument(
+                            "timeout is negative".to_string(),
+                        ));
+                    }
+                    block = true;
+                    timeout = (ms > 0).then(|| Duration::from_millis(ms as u64));
+                }
+                Some("streams") => break,
+                _ => return Err(syntax_error()),
+            }
+        }
+
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(CommandError::InvalidArgument(
+                "Unbalanced 'xread' list of streams: for each stream key an ID or '$' must be specified."
+                    .to_string(),
+            ));
+        }
+        let rest = args.rest_strings()?;
+        let (keys, ids) = rest.split_at(rest.len() / 2);
+        let ids = ids
+            .iter()
+            .map(|id| match id.as_str() {
+                "$" => Ok(XReadId::New),
+                "+" => Ok(XReadId::LastEntry),
+                _ => Ok(XReadId::After(parse_id(id, 0)?)),
+            })
+            .collect::<Result<_, CommandError>>()?;
+        Ok(XRead {
+            count,
+            block,
+            timeout,
+            keys: keys.to_vec(),
+            ids,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, StreamId, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xread_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*8\r\n$5\r\nxread\r\n$5\r\nCOUNT\r\n$1\r\n2\r\n$7\r\nSTREAMS\r\n\
+              $2\r\ns1\r\n$2\r\ns2\r\n$1\r\n0\r\n$1\r\n$\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XRead = frame.try_into()?;
+        assert_eq!(result.count, Some(2));
+        assert!(!result.block);
+        assert_eq!(result.keys, vec!["s1", "s2"]);
+        assert_eq!(
+            result.ids,
+            vec![XReadId::After(StreamId::MIN), XReadId::New]
+        );
+
+        let backend = Backend::new();
+        assert_eq!(
+            result.execute(&backend),
+            RespFrame::NullArray(RespNullArray)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_xread_block() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*6\r\n$5\r\nxread\r\n$5\r\nBLOCK\r\n$1\r\n0\r\n$7\r\nSTREAMS\r\n$2\r\ns1\r\n$1\r\n$\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XRead = frame.try_into()?;
+        assert!(result.block);
+        assert_eq!(result.timeout, None);
+
+        let backend = Backend::new();
+        let cloned = backend.clone();
+        let handle = tokio::spawn(async move { result.execute_blocking(&cloned).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let fields = vec![(b"a".to_vec(), b"1".to_vec())];
+        backend.xadd("s1", XAddId::AutoSeq(1), fields, false, None)?;
+        let RespFrame::Array(reply) = handle.await? else {
+            panic!("expected an array");
+        };
+        assert_eq!(reply.len(), 1);
+
+        Ok(())
+    }
+}
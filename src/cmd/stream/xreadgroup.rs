@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, BulkString, GroupEntry, RespArray, RespFrame, RespNullArray, StreamId,
+};
+
+use super::{entry_reply, parse_id};
+
+// xreadgroup: https://redis.io/docs/latest/commands/xreadgroup/
+// XREADGROUP GROUP group consumer [COUNT count] [BLOCK milliseconds] [NOACK]
+//   STREAMS key [key ...] id [id ...]
+
+#[derive(Debug)]
+pub struct XReadGroup {
+    group: String,
+    consumer: String,
+    count: Option<usize>,
+    block: bool,
+    timeout: Option<Duration>, // None blocks forever
+    noack: bool,
+    keys: Vec<String>,
+    ids: Vec<Option<StreamId>>, // None is `>`, the entries never delivered to the group
+}
+
+/// Like XREAD, but a deleted entry in the history of the consumer is replied with nil fields.
+fn streams_reply(streams: Vec<(String, Vec<GroupEntry>)>) -> RespFrame {
+    if streams.is_empty() {
+        return RespFrame::NullArray(RespNullArray);
+    }
+    let frames = streams
+        .into_iter()
+        .map(|(key, entries)| {
+            let entries = entries
+                .into_iter()
+                .map(|(id, fields)| match fields {
+                    Some(fields) => entry_reply((id, fields)),
+                    None => RespArray::new([
+                        BulkString::new(id.to_string()).into(),
+                        RespFrame::NullArray(RespNullArray),
+                    ])
+                    .into(),
+                })
+                .collect::<Vec<_>>();
+            RespArray::new([BulkString::new(key).into(), RespArray::new(entries).into()]).into()
+        })
+        .collect::<Vec<RespFrame>>();
+    RespArray::new(frames).into()
+}
+
+impl CommandExecutor for XReadGroup {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xreadgroup(
+            &self.group,
+            &self.consumer,
+            &self.keys,
+            &self.ids,
+            self.count,
+            self.noack,
+        ) {
+            Ok(streams) => streams_reply(streams),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl XReadGroup {
+    /// Only a read of new entries blocks, the history is replied immediately.
+    pub async fn execute_blocking(self, backend: &Backend) -> RespFrame {
+        if !self.block || self.ids.iter().any(Option::is_some) {
+            return self.execute(backend);
+        }
+        match backend
+            .xreadgroup_block(
+                &self.group,
+                &self.consumer,
+                &self.keys,
+                &self.ids,
+                self.count,
+                self.noack,
+                self.timeout,
+            )
+            .await
+        {
+            Ok(Some(streams)) => streams_reply(streams),
+            Ok(None) => RespFrame::NullArray(RespNullArray),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XReadGroup {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        if args.next_option()?.as_deref() != Some("group") {
+            return Err(syntax_error());
+        }
+        let (group, consumer) = (args.next_string()?, args.next_string()?);
+        let (mut count, mut block, mut timeout, mut noack) = (None, false, None, false);
+        loop {
+            match args.next_option()?.as_deref() {
+                // COUNT 0 means no limit
+                Some("count") => {
+                    count = match args.next_integer()? {
+                        n if n > 0 => Some(n as usize),
+                        _ => None,
+                    }
+                }
+                Some("block") => {
+                    let ms = args.next_integer()?;
+                    if ms < 0 {
+                        return Err(CommandError::InvalidArgument(
+                            "timeout is negative".to_string(),
+                        ));
+                    }
+                    block = true;
+                    timeout = (ms > 0).then(|| Duration::from_millis(ms as u64));
+                }
+                Some("noack") => noack = true,
+                Some("streams") => break,
+                _ => return Err(syntax_error()),
+            }
+        }
+
+        if args.is_empty() || !args.len().is_multiple_of(2) {
+            return Err(CommandError::InvalidArgument(
+                "Unbalanced 'xreadgroup' list of streams: for each stream key an ID or '>' must be specified."
+                    .to_string(),
+            ));
+        }
+        let rest = args.rest_strings()?;
+        let (keys, ids) = rest.split_at(rest.len() / 2);
+        let ids = ids
+            .iter()
+            .map(|id| match id.as_str() {
+                ">" => Ok(None),
+                _ => Ok(Some(parse_id(id, 0)?)),
+            })
+            .collect::<Result<_, CommandError>>()?;
+        Ok(XReadGroup {
+            group,
+            consumer,
+            count,
+            block,
+            timeout,
+            noack,
+            keys: keys.to_vec(),
+            ids,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[tokio::test]
+    async fn test_xreadgroup_block() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(
+            b"*9\r\n$10\r\nxreadgroup\r\n$5\r\nGROUP\r\n$1\r\ng\r\n$5\r\nalice\r\n\
+              $5\r\nBLOCK\r\n$1\r\n0\r\n$7\r\nSTREAMS\r\n$2\r\ns1\r\n$1\r\n>\r\n",
+        );
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XReadGroup = frame.try_into()?;
+        assert_eq!(result.group, "g");
+        assert_eq!(result.consumer, "alice");
+        assert!(result.block);
+        assert_eq!(result.ids, vec![None]);
+
+        let backend = Backend::new();
+        backend.xgroup_create("s1", "g", None, true)?;
+        let cloned = backend.clone();
+        let handle = tokio::spawn(async move { result.execute_blocking(&cloned).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let fields = vec![(b"a".to_vec(), b"1".to_vec())];
+        backend.xadd("s1", XAddId::AutoSeq(1), fields, false, None)?;
+        let RespFrame::Array(reply) = handle.await? else {
+            panic!("expected an array");
+        };
+        assert_eq!(reply.len(), 1);
+        assert_eq!(backend.xpending_summary("s1", "g")?.count, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, StreamTrim,
+};
+
+use super::parse_trim;
+
+// xtrim: https://redis.io/docs/latest/commands/xtrim/
+// XTRIM key <MAXLEN | MINID> [= | ~] threshold [LIMIT count]
+
+#[derive(Debug)]
+pub struct XTrim {
+    key: String,
+    trim: StreamTrim,
+}
+
+impl CommandExecutor for XTrim {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.xtrim(&self.key, &self.trim) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for XTrim {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let trim = match args.next_option()?.as_deref() {
+            Some(strategy @ ("maxlen" | "minid")) => parse_trim(strategy, &mut args)?,
+            _ => return Err(syntax_error()),
+        };
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        Ok(XTrim { key, trim })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, StreamId, TrimStrategy, XAddId};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_xtrim_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nxtrim\r\n$6\r\nstream\r\n$5\r\nMINID\r\n$1\r\n2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: XTrim = frame.try_into()?;
+        assert_eq!(result.key, "stream");
+        assert_eq!(
+            result.trim.strategy,
+            TrimStrategy::MinId(StreamId::new(2, 0))
+        );
+
+        let backend = Backend::new();
+        for _ in 0..3 {
+            backend.xadd("stream", XAddId::AutoSeq(1), vec![], false, None)?;
+        }
+        backend.xadd("stream", XAddId::AutoSeq(2), vec![], false, None)?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(3));
+        assert_eq!(backend.xlen("stream")?, 1);
+
+        Ok(())
+    }
+}
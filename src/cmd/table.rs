@@ -0,0 +1,1290 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::{RespArray, RespFrame};
+
+use super::{
+    bitmap::{BitCount, BitField, BitOp, BitPos, GetBit, SetBit},
+    client::Client,
+    command::{Command, CommandError},
+    commands::Commands,
+    echo::Echo,
+    geo::{GeoAdd, GeoDist, GeoHash, GeoPos, GeoSearch, GeoSearchStore},
+    hello::Hello,
+    hmap::{
+        HDel, HExists, HGet, HGetAll, HIncrBy, HIncrByFloat, HKeys, HLen, HMGet, HRandField, HScan,
+        HSet, HSetNx, HStrLen, HVals,
+    },
+    hyperloglog::{PfAdd, PfCount, PfMerge},
+    info::Info,
+    key::{
+        Copy, DbSize, Del, Exists, Expire, ExpireAt, ExpireTime, FlushAll, FlushDb, Keys, PExpire,
+        PExpireAt, PExpireTime, PTtl, Persist, Rename, RenameNx, Scan, Ttl, Type, Unlink,
+    },
+    list::{
+        BLMove, BLPop, BRPop, LIndex, LInsert, LLen, LMove, LPop, LPush, LPushX, LRange, LRem,
+        LSet, LTrim, RPop, RPush, RPushX,
+    },
+    map::{
+        Append, Decr, DecrBy, Get, GetDel, GetEx, GetRange, GetSet, Incr, IncrBy, IncrByFloat,
+        MGet, MSet, MSetNx, Set, SetRange, StrLen,
+    },
+    ping::Ping,
+    pubsub::{
+        PSubscribe, PUnsubscribe, PubSub, Publish, SPublish, SSubscribe, SUnsubscribe, Subscribe,
+        Unsubscribe,
+    },
+    set::{
+        SAdd, SCard, SDiff, SDiffStore, SInter, SInterCard, SInterStore, SIsMember, SMIsMember,
+        SMembers, SMove, SPop, SRandMember, SRem, SScan, SUnion, SUnionStore,
+    },
+    stream::{
+        XAck, XAdd, XAutoClaim, XClaim, XDel, XGroup, XInfo, XLen, XPending, XRange, XRead,
+        XReadGroup, XRevRange, XTrim,
+    },
+    transaction::{Discard, Exec, Multi, Unwatch, Watch},
+    wrong_arity,
+    zset::{ZAdd, ZCard, ZIncrBy, ZPopMax, ZPopMin, ZRange, ZRangeByScore, ZRank, ZRem, ZScore},
+};
+
+// The command table, like the one of redis: every command is dispatched by its
+// entry, and COMMAND reports the entries to the clients.
+
+/// The flags of a command, see https://redis.io/docs/latest/commands/command/#flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandFlag {
+    Write,       // may modify the keyspace
+    ReadOnly,    // only reads the keys
+    DenyOom,     // may use more memory, rejected when out of memory
+    Admin,       // an administrative command
+    PubSub,      // a pub/sub related command
+    NoScript,    // not allowed in scripts
+    Loading,     // allowed while loading the database
+    Stale,       // allowed on a replica with stale data
+    Fast,        // O(1) or O(log(N)), it never blocks the server
+    Blocking,    // may block the client
+    MovableKeys, // the keys are not at fixed positions, e.g. after a numkeys
+}
+
+// the ACL categories in the order redis reports them
+const ACL_CATEGORIES: [&str; 20] = [
+    "keyspace",
+    "read",
+    "write",
+    "set",
+    "sortedset",
+    "list",
+    "hash",
+    "string",
+    "bitmap",
+    "hyperloglog",
+    "geo",
+    "stream",
+    "pubsub",
+    "admin",
+    "fast",
+    "slow",
+    "blocking",
+    "dangerous",
+    "connection",
+    "transaction",
+];
+
+/// An entry of the command table.
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub arity: i64, // the number of arguments with the name, negative for at least -arity
+    pub flags: &'static [CommandFlag],
+    pub first_key: i64,
+    pub last_key: i64, // negative counts from the end, e.g. -1 for the last argument
+    pub step: i64,
+    categories: &'static [&'static str], // the ACL categories not implied by the flags
+    key_spec: Option<KeySpec>,           // for the movable keys, the others are in the range
+    pub subcommands: &'static [CommandSpec], // named like client|kill
+    parse: fn(RespArray) -> Result<Command, CommandError>,
+}
+
+/// Where the search of the keys begins, see https://redis.io/docs/latest/develop/reference/key-specs/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeginSearch {
+    Index(i64),
+    // after the keyword, searched from `start_from`, backwards if it is negative
+    Keyword {
+        keyword: &'static str,
+        start_from: i64,
+    },
+}
+
+/// How the keys are found from where the search begins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindKeys {
+    // `last_key` is relative to the beginning, or to the end if negative, in
+    // which case a `limit` of N only takes the first 1/N of the arguments
+    Range {
+        last_key: i64,
+        key_step: i64,
+        limit: i64,
+    },
+    // the number of keys is the argument at `key_num_idx`
+    KeyNum {
+        key_num_idx: i64,
+        first_key: i64,
+        key_step: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySpec {
+    pub begin_search: BeginSearch,
+    pub find_keys: FindKeys,
+}
+
+macro_rules! command {
+    ($name:literal, $cmd:ident, $arity:expr, [$($flag:ident),*], ($first:expr, $last:expr, $step:expr), [$($category:literal),*]) => {
+        command!(@spec $name, $cmd, $arity, [$($flag),*], ($first, $last, $step), [$($category),*], None, &[])
+    };
+    ($name:literal, $cmd:ident, $arity:expr, [$($flag:ident),*], ($first:expr, $last:expr, $step:expr), [$($category:literal),*], keys: $key_spec:expr) => {
+        command!(@spec $name, $cmd, $arity, [$($flag),*], ($first, $last, $step), [$($category),*], Some($key_spec), &[])
+    };
+    ($name:literal, $cmd:ident, $arity:expr, [$($flag:ident),*], ($first:expr, $last:expr, $step:expr), [$($category:literal),*], subcommands: $subcommands:expr) => {
+        command!(@spec $name, $cmd, $arity, [$($flag),*], ($first, $last, $step), [$($category),*], None, $subcommands)
+    };
+    (@spec $name:literal, $cmd:ident, $arity:expr, [$($flag:ident),*], ($first:expr, $last:expr, $step:expr), [$($category:literal),*], $key_spec:expr, $subcommands:expr) => {
+        CommandSpec {
+            name: $name,
+            arity: $arity,
+            flags: &[$(CommandFlag::$flag),*],
+            first_key: $first,
+            last_key: $last,
+            step: $step,
+            categories: &[$($category),*],
+            key_spec: $key_spec,
+            subcommands: $subcommands,
+            parse: |value| Ok($cmd::try_from(value)?.into()),
+        }
+    };
+}
+
+// the keys of XREAD and XREADGROUP, the first half of the arguments after STREAMS
+const STREAMS_KEYS: KeySpec = KeySpec {
+    begin_search: BeginSearch::Keyword {
+        keyword: "STREAMS",
+        start_from: 1,
+    },
+    find_keys: FindKeys::Range {
+        last_key: -1,
+        key_step: 1,
+        limit: 2,
+    },
+};
+
+const XGROUP_SUBCOMMANDS: &[CommandSpec] = &[
+    command!(
+        "xgroup|create",
+        XGroup,
+        -5,
+        [Write, DenyOom],
+        (2, 2, 1),
+        ["stream"]
+    ),
+    command!("xgroup|setid", XGroup, -5, [Write], (2, 2, 1), ["stream"]),
+    command!("xgroup|destroy", XGroup, 4, [Write], (2, 2, 1), ["stream"]),
+    command!(
+        "xgroup|createconsumer",
+        XGroup,
+        5,
+        [Write, DenyOom],
+        (2, 2, 1),
+        ["stream"]
+    ),
+    command!(
+        "xgroup|delconsumer",
+        XGroup,
+        5,
+        [Write],
+        (2, 2, 1),
+        ["stream"]
+    ),
+];
+
+const XINFO_SUBCOMMANDS: &[CommandSpec] = &[
+    command!("xinfo|stream", XInfo, -3, [ReadOnly], (2, 2, 1), ["stream"]),
+    command!("xinfo|groups", XInfo, 3, [ReadOnly], (2, 2, 1), ["stream"]),
+    command!(
+        "xinfo|consumers",
+        XInfo,
+        4,
+        [ReadOnly],
+        (2, 2, 1),
+        ["stream"]
+    ),
+];
+
+const PUBSUB_SUBCOMMANDS: &[CommandSpec] = &[
+    command!(
+        "pubsub|channels",
+        PubSub,
+        -2,
+        [PubSub, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "pubsub|numsub",
+        PubSub,
+        -2,
+        [PubSub, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "pubsub|numpat",
+        PubSub,
+        2,
+        [PubSub, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "pubsub|shardchannels",
+        PubSub,
+        -2,
+        [PubSub, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "pubsub|shardnumsub",
+        PubSub,
+        -2,
+        [PubSub, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+];
+
+const CLIENT_SUBCOMMANDS: &[CommandSpec] = &[
+    command!(
+        "client|id",
+        Client,
+        2,
+        [NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|setname",
+        Client,
+        3,
+        [NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|getname",
+        Client,
+        2,
+        [NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|list",
+        Client,
+        -2,
+        [Admin, NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|info",
+        Client,
+        2,
+        [NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|kill",
+        Client,
+        -3,
+        [Admin, NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "client|no-evict",
+        Client,
+        3,
+        [Admin, NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+];
+
+const COMMAND_SUBCOMMANDS: &[CommandSpec] = &[
+    command!(
+        "command|count",
+        Commands,
+        2,
+        [Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "command|info",
+        Commands,
+        -2,
+        [Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "command|docs",
+        Commands,
+        -2,
+        [Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "command|getkeys",
+        Commands,
+        -3,
+        [Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!(
+        "command|list",
+        Commands,
+        -2,
+        [Loading, Stale],
+        (0, 0, 0),
+        ["connection"]
+    ),
+];
+
+pub static COMMAND_TABLE: &[CommandSpec] = &[
+    // keyspace
+    command!("expire", Expire, -3, [Write, Fast], (1, 1, 1), ["keyspace"]),
+    command!(
+        "pexpire",
+        PExpire,
+        -3,
+        [Write, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!(
+        "expireat",
+        ExpireAt,
+        -3,
+        [Write, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!(
+        "pexpireat",
+        PExpireAt,
+        -3,
+        [Write, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!("ttl", Ttl, 2, [ReadOnly, Fast], (1, 1, 1), ["keyspace"]),
+    command!("pttl", PTtl, 2, [ReadOnly, Fast], (1, 1, 1), ["keyspace"]),
+    command!(
+        "expiretime",
+        ExpireTime,
+        2,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!(
+        "pexpiretime",
+        PExpireTime,
+        2,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!(
+        "persist",
+        Persist,
+        2,
+        [Write, Fast],
+        (1, 1, 1),
+        ["keyspace"]
+    ),
+    command!("type", Type, 2, [ReadOnly, Fast], (1, 1, 1), ["keyspace"]),
+    command!("del", Del, -2, [Write], (1, -1, 1), ["keyspace"]),
+    command!(
+        "unlink",
+        Unlink,
+        -2,
+        [Write, Fast],
+        (1, -1, 1),
+        ["keyspace"]
+    ),
+    command!(
+        "exists",
+        Exists,
+        -2,
+        [ReadOnly, Fast],
+        (1, -1, 1),
+        ["keyspace"]
+    ),
+    command!("rename", Rename, 3, [Write], (1, 2, 1), ["keyspace"]),
+    command!(
+        "renamenx",
+        RenameNx,
+        3,
+        [Write, Fast],
+        (1, 2, 1),
+        ["keyspace"]
+    ),
+    command!("copy", Copy, -3, [Write, DenyOom], (1, 2, 1), ["keyspace"]),
+    command!(
+        "dbsize",
+        DbSize,
+        1,
+        [ReadOnly, Fast],
+        (0, 0, 0),
+        ["keyspace"]
+    ),
+    command!(
+        "flushdb",
+        FlushDb,
+        -1,
+        [Write],
+        (0, 0, 0),
+        ["keyspace", "dangerous"]
+    ),
+    command!(
+        "flushall",
+        FlushAll,
+        -1,
+        [Write],
+        (0, 0, 0),
+        ["keyspace", "dangerous"]
+    ),
+    command!("scan", Scan, -2, [ReadOnly], (0, 0, 0), ["keyspace"]),
+    command!(
+        "keys",
+        Keys,
+        2,
+        [ReadOnly],
+        (0, 0, 0),
+        ["keyspace", "dangerous"]
+    ),
+    // strings
+    command!("get", Get, 2, [ReadOnly, Fast], (1, 1, 1), ["string"]),
+    command!("set", Set, -3, [Write, DenyOom], (1, 1, 1), ["string"]),
+    command!(
+        "append",
+        Append,
+        3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!("getdel", GetDel, 2, [Write, Fast], (1, 1, 1), ["string"]),
+    command!("getex", GetEx, -2, [Write, Fast], (1, 1, 1), ["string"]),
+    command!("getrange", GetRange, 4, [ReadOnly], (1, 1, 1), ["string"]),
+    command!(
+        "getset",
+        GetSet,
+        3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!(
+        "incr",
+        Incr,
+        2,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!(
+        "decr",
+        Decr,
+        2,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!(
+        "incrby",
+        IncrBy,
+        3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!(
+        "decrby",
+        DecrBy,
+        3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!(
+        "incrbyfloat",
+        IncrByFloat,
+        3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!("mget", MGet, -2, [ReadOnly, Fast], (1, -1, 1), ["string"]),
+    command!("mset", MSet, -3, [Write, DenyOom], (1, -1, 2), ["string"]),
+    command!(
+        "msetnx",
+        MSetNx,
+        -3,
+        [Write, DenyOom],
+        (1, -1, 2),
+        ["string"]
+    ),
+    command!(
+        "setrange",
+        SetRange,
+        4,
+        [Write, DenyOom],
+        (1, 1, 1),
+        ["string"]
+    ),
+    command!("strlen", StrLen, 2, [ReadOnly, Fast], (1, 1, 1), ["string"]),
+    // bitmaps
+    command!("setbit", SetBit, 4, [Write, DenyOom], (1, 1, 1), ["bitmap"]),
+    command!("getbit", GetBit, 3, [ReadOnly, Fast], (1, 1, 1), ["bitmap"]),
+    command!("bitcount", BitCount, -2, [ReadOnly], (1, 1, 1), ["bitmap"]),
+    command!("bitpos", BitPos, -3, [ReadOnly], (1, 1, 1), ["bitmap"]),
+    command!("bitop", BitOp, -4, [Write, DenyOom], (2, -1, 1), ["bitmap"]),
+    command!(
+        "bitfield",
+        BitField,
+        -2,
+        [Write, DenyOom],
+        (1, 1, 1),
+        ["bitmap"]
+    ),
+    // hashes
+    command!("hget", HGet, 3, [ReadOnly, Fast], (1, 1, 1), ["hash"]),
+    command!(
+        "hset",
+        HSet,
+        -4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["hash"]
+    ),
+    command!("hgetall", HGetAll, 2, [ReadOnly], (1, 1, 1), ["hash"]),
+    command!("hmget", HMGet, -3, [ReadOnly, Fast], (1, 1, 1), ["hash"]),
+    command!("hscan", HScan, -3, [ReadOnly], (1, 1, 1), ["hash"]),
+    command!("hdel", HDel, -3, [Write, Fast], (1, 1, 1), ["hash"]),
+    command!("hexists", HExists, 3, [ReadOnly, Fast], (1, 1, 1), ["hash"]),
+    command!(
+        "hincrby",
+        HIncrBy,
+        4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["hash"]
+    ),
+    command!(
+        "hincrbyfloat",
+        HIncrByFloat,
+        4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["hash"]
+    ),
+    command!("hkeys", HKeys, 2, [ReadOnly], (1, 1, 1), ["hash"]),
+    command!("hvals", HVals, 2, [ReadOnly], (1, 1, 1), ["hash"]),
+    command!("hlen", HLen, 2, [ReadOnly, Fast], (1, 1, 1), ["hash"]),
+    command!(
+        "hrandfield",
+        HRandField,
+        -2,
+        [ReadOnly],
+        (1, 1, 1),
+        ["hash"]
+    ),
+    command!(
+        "hsetnx",
+        HSetNx,
+        4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["hash"]
+    ),
+    command!("hstrlen", HStrLen, 3, [ReadOnly, Fast], (1, 1, 1), ["hash"]),
+    // lists
+    command!(
+        "lpush",
+        LPush,
+        -3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["list"]
+    ),
+    command!(
+        "rpush",
+        RPush,
+        -3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["list"]
+    ),
+    command!(
+        "lpushx",
+        LPushX,
+        -3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["list"]
+    ),
+    command!(
+        "rpushx",
+        RPushX,
+        -3,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["list"]
+    ),
+    command!("lpop", LPop, -2, [Write, Fast], (1, 1, 1), ["list"]),
+    command!("rpop", RPop, -2, [Write, Fast], (1, 1, 1), ["list"]),
+    command!("lrange", LRange, 4, [ReadOnly], (1, 1, 1), ["list"]),
+    command!("lindex", LIndex, 3, [ReadOnly], (1, 1, 1), ["list"]),
+    command!("lset", LSet, 4, [Write, DenyOom], (1, 1, 1), ["list"]),
+    command!("lrem", LRem, 4, [Write], (1, 1, 1), ["list"]),
+    command!("ltrim", LTrim, 4, [Write], (1, 1, 1), ["list"]),
+    command!("linsert", LInsert, 5, [Write, DenyOom], (1, 1, 1), ["list"]),
+    command!("llen", LLen, 2, [ReadOnly, Fast], (1, 1, 1), ["list"]),
+    command!("lmove", LMove, 5, [Write, DenyOom], (1, 2, 1), ["list"]),
+    command!("blpop", BLPop, -3, [Write, Blocking], (1, -2, 1), ["list"]),
+    command!("brpop", BRPop, -3, [Write, Blocking], (1, -2, 1), ["list"]),
+    command!(
+        "blmove",
+        BLMove,
+        6,
+        [Write, DenyOom, Blocking],
+        (1, 2, 1),
+        ["list"]
+    ),
+    // sets
+    command!("sadd", SAdd, -3, [Write, DenyOom, Fast], (1, 1, 1), ["set"]),
+    command!(
+        "sismember",
+        SIsMember,
+        3,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["set"]
+    ),
+    command!("sscan", SScan, -3, [ReadOnly], (1, 1, 1), ["set"]),
+    command!("scard", SCard, 2, [ReadOnly, Fast], (1, 1, 1), ["set"]),
+    command!("sdiff", SDiff, -2, [ReadOnly], (1, -1, 1), ["set"]),
+    command!(
+        "sdiffstore",
+        SDiffStore,
+        -3,
+        [Write, DenyOom],
+        (1, -1, 1),
+        ["set"]
+    ),
+    command!("sinter", SInter, -2, [ReadOnly], (1, -1, 1), ["set"]),
+    command!(
+        "sinterstore",
+        SInterStore,
+        -3,
+        [Write, DenyOom],
+        (1, -1, 1),
+        ["set"]
+    ),
+    command!("sunion", SUnion, -2, [ReadOnly], (1, -1, 1), ["set"]),
+    command!(
+        "sunionstore",
+        SUnionStore,
+        -3,
+        [Write, DenyOom],
+        (1, -1, 1),
+        ["set"]
+    ),
+    command!("sintercard", SInterCard, -3, [ReadOnly, MovableKeys], (0, 0, 0), ["set"], keys: KeySpec {
+        begin_search: BeginSearch::Index(1),
+        find_keys: FindKeys::KeyNum { key_num_idx: 0, first_key: 1, key_step: 1 },
+    }),
+    command!("smembers", SMembers, 2, [ReadOnly], (1, 1, 1), ["set"]),
+    command!(
+        "smismember",
+        SMIsMember,
+        -3,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["set"]
+    ),
+    command!("smove", SMove, 4, [Write, Fast], (1, 2, 1), ["set"]),
+    command!("spop", SPop, -2, [Write, Fast], (1, 1, 1), ["set"]),
+    command!(
+        "srandmember",
+        SRandMember,
+        -2,
+        [ReadOnly],
+        (1, 1, 1),
+        ["set"]
+    ),
+    command!("srem", SRem, -3, [Write, Fast], (1, 1, 1), ["set"]),
+    // sorted sets
+    command!(
+        "zadd",
+        ZAdd,
+        -4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!(
+        "zcard",
+        ZCard,
+        2,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!(
+        "zincrby",
+        ZIncrBy,
+        4,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!(
+        "zpopmin",
+        ZPopMin,
+        -2,
+        [Write, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!(
+        "zpopmax",
+        ZPopMax,
+        -2,
+        [Write, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!("zrange", ZRange, -4, [ReadOnly], (1, 1, 1), ["sortedset"]),
+    command!(
+        "zrangebyscore",
+        ZRangeByScore,
+        -4,
+        [ReadOnly],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!(
+        "zrank",
+        ZRank,
+        -3,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    command!("zrem", ZRem, -3, [Write, Fast], (1, 1, 1), ["sortedset"]),
+    command!(
+        "zscore",
+        ZScore,
+        3,
+        [ReadOnly, Fast],
+        (1, 1, 1),
+        ["sortedset"]
+    ),
+    // hyperloglogs
+    command!(
+        "pfadd",
+        PfAdd,
+        -2,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["hyperloglog"]
+    ),
+    command!(
+        "pfcount",
+        PfCount,
+        -2,
+        [ReadOnly],
+        (1, -1, 1),
+        ["hyperloglog"]
+    ),
+    command!(
+        "pfmerge",
+        PfMerge,
+        -2,
+        [Write, DenyOom],
+        (1, -1, 1),
+        ["hyperloglog"]
+    ),
+    // geospatial indices
+    command!("geoadd", GeoAdd, -5, [Write, DenyOom], (1, 1, 1), ["geo"]),
+    command!("geodist", GeoDist, -4, [ReadOnly], (1, 1, 1), ["geo"]),
+    command!("geohash", GeoHash, -2, [ReadOnly], (1, 1, 1), ["geo"]),
+    command!("geopos", GeoPos, -2, [ReadOnly], (1, 1, 1), ["geo"]),
+    command!("geosearch", GeoSearch, -7, [ReadOnly], (1, 1, 1), ["geo"]),
+    command!(
+        "geosearchstore",
+        GeoSearchStore,
+        -8,
+        [Write, DenyOom],
+        (1, 2, 1),
+        ["geo"]
+    ),
+    // streams
+    command!(
+        "xadd",
+        XAdd,
+        -5,
+        [Write, DenyOom, Fast],
+        (1, 1, 1),
+        ["stream"]
+    ),
+    command!("xdel", XDel, -3, [Write, Fast], (1, 1, 1), ["stream"]),
+    command!("xlen", XLen, 2, [ReadOnly, Fast], (1, 1, 1), ["stream"]),
+    command!("xrange", XRange, -4, [ReadOnly], (1, 1, 1), ["stream"]),
+    command!(
+        "xrevrange",
+        XRevRange,
+        -4,
+        [ReadOnly],
+        (1, 1, 1),
+        ["stream"]
+    ),
+    command!("xread", XRead, -4, [ReadOnly, Blocking, MovableKeys], (0, 0, 0), ["stream"], keys: STREAMS_KEYS),
+    command!("xtrim", XTrim, -4, [Write], (1, 1, 1), ["stream"]),
+    command!("xgroup", XGroup, -2, [], (0, 0, 0), [], subcommands: XGROUP_SUBCOMMANDS),
+    command!("xreadgroup", XReadGroup, -7, [Write, Blocking, MovableKeys], (0, 0, 0), ["stream"], keys: STREAMS_KEYS),
+    command!("xack", XAck, -4, [Write, Fast], (1, 1, 1), ["stream"]),
+    command!("xpending", XPending, -3, [ReadOnly], (1, 1, 1), ["stream"]),
+    command!("xclaim", XClaim, -6, [Write, Fast], (1, 1, 1), ["stream"]),
+    command!(
+        "xautoclaim",
+        XAutoClaim,
+        -6,
+        [Write, Fast],
+        (1, 1, 1),
+        ["stream"]
+    ),
+    command!("xinfo", XInfo, -2, [], (0, 0, 0), [], subcommands: XINFO_SUBCOMMANDS),
+    // pub/sub
+    command!(
+        "subscribe",
+        Subscribe,
+        -2,
+        [PubSub, NoScript, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "unsubscribe",
+        Unsubscribe,
+        -1,
+        [PubSub, NoScript, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "psubscribe",
+        PSubscribe,
+        -2,
+        [PubSub, NoScript, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "punsubscribe",
+        PUnsubscribe,
+        -1,
+        [PubSub, NoScript, Loading, Stale],
+        (0, 0, 0),
+        []
+    ),
+    command!(
+        "publish",
+        Publish,
+        3,
+        [PubSub, Loading, Stale, Fast],
+        (0, 0, 0),
+        []
+    ),
+    command!("pubsub", PubSub, -2, [], (0, 0, 0), [], subcommands: PUBSUB_SUBCOMMANDS),
+    command!(
+        "ssubscribe",
+        SSubscribe,
+        -2,
+        [PubSub, NoScript, Loading, Stale],
+        (1, -1, 1),
+        []
+    ),
+    command!(
+        "sunsubscribe",
+        SUnsubscribe,
+        -1,
+        [PubSub, NoScript, Loading, Stale],
+        (1, -1, 1),
+        []
+    ),
+    command!(
+        "spublish",
+        SPublish,
+        3,
+        [PubSub, Loading, Stale, Fast],
+        (1, 1, 1),
+        []
+    ),
+    // transactions
+    command!(
+        "multi",
+        Multi,
+        1,
+        [NoScript, Loading, Stale, Fast],
+        (0, 0, 0),
+        ["transaction"]
+    ),
+    command!(
+        "exec",
+        Exec,
+        1,
+        [NoScript, Loading, Stale],
+        (0, 0, 0),
+        ["transaction"]
+    ),
+    command!(
+        "discard",
+        Discard,
+        1,
+        [NoScript, Loading, Stale, Fast],
+        (0, 0, 0),
+        ["transaction"]
+    ),
+    command!(
+        "watch",
+        Watch,
+        -2,
+        [NoScript, Loading, Stale, Fast],
+        (1, -1, 1),
+        ["transaction"]
+    ),
+    command!(
+        "unwatch",
+        Unwatch,
+        1,
+        [NoScript, Loading, Stale, Fast],
+        (0, 0, 0),
+        ["transaction"]
+    ),
+    // connections
+    command!("echo", Echo, 2, [Fast], (0, 0, 0), ["connection"]),
+    command!("ping", Ping, -1, [Fast], (0, 0, 0), ["connection"]),
+    command!(
+        "hello",
+        Hello,
+        -1,
+        [NoScript, Loading, Stale, Fast],
+        (0, 0, 0),
+        ["connection"]
+    ),
+    command!("client", Client, -2, [], (0, 0, 0), [], subcommands: CLIENT_SUBCOMMANDS),
+    command!("command", Commands, -1, [Loading, Stale], (0, 0, 0), ["connection"], subcommands: COMMAND_SUBCOMMANDS),
+    // server
+    command!("info", Info, -1, [Loading, Stale], (0, 0, 0), ["dangerous"]),
+];
+
+static COMMANDS_BY_NAME: LazyLock<HashMap<&'static str, &'static CommandSpec>> =
+    LazyLock::new(|| COMMAND_TABLE.iter().map(|spec| (spec.name, spec)).collect());
+
+/// Find the entry of a command, the name is case insensitive.
+pub fn lookup_command(name: &[u8]) -> Option<&'static CommandSpec> {
+    let name = String::from_utf8_lossy(name).to_ascii_lowercase();
+    COMMANDS_BY_NAME.get(name.as_str()).copied()
+}
+
+/// Find an entry by its full name, e.g. client|kill for a subcommand.
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    match name.split_once('|') {
+        Some((name, subcommand)) => {
+            let spec = lookup_command(name.as_bytes())?;
+            spec.subcommand(&subcommand.to_ascii_lowercase())
+        }
+        None => lookup_command(name.as_bytes()),
+    }
+}
+
+impl CommandFlag {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CommandFlag::Write => "write",
+            CommandFlag::ReadOnly => "readonly",
+            CommandFlag::DenyOom => "denyoom",
+            CommandFlag::Admin => "admin",
+            CommandFlag::PubSub => "pubsub",
+            CommandFlag::NoScript => "noscript",
+            CommandFlag::Loading => "loading",
+            CommandFlag::Stale => "stale",
+            CommandFlag::Fast => "fast",
+            CommandFlag::Blocking => "blocking",
+            CommandFlag::MovableKeys => "movablekeys",
+        }
+    }
+}
+
+impl CommandSpec {
+    pub fn has_flag(&self, flag: CommandFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+
+    /// The ACL categories, including the ones implied by the flags like redis does.
+    pub fn acl_categories(&self) -> Vec<&'static str> {
+        let implied = |category: &str| match category {
+            "read" => self.has_flag(CommandFlag::ReadOnly),
+            "write" => self.has_flag(CommandFlag::Write),
+            "pubsub" => self.has_flag(CommandFlag::PubSub),
+            "admin" | "dangerous" => self.has_flag(CommandFlag::Admin),
+            "fast" => self.has_flag(CommandFlag::Fast),
+            "slow" => !self.has_flag(CommandFlag::Fast),
+            "blocking" => self.has_flag(CommandFlag::Blocking),
+            _ => false,
+        };
+        ACL_CATEGORIES
+            .into_iter()
+            .filter(|category| self.categories.contains(category) || implied(category))
+            .collect()
+    }
+
+    /// The group of the command in the documentation, e.g. sorted-set.
+    pub fn group(&self) -> &'static str {
+        let category = |c: &str| self.categories.contains(&c);
+        match self.name.split('|').next().unwrap_or_default() {
+            _ if category("keyspace") => "generic",
+            _ if category("sortedset") => "sorted-set",
+            _ if category("transaction") => "transactions",
+            _ if self.has_flag(CommandFlag::PubSub) => "pubsub",
+            "pubsub" => "pubsub",
+            "xgroup" | "xinfo" => "stream",
+            "client" | "command" => "connection",
+            _ => [
+                "string",
+                "bitmap",
+                "hash",
+                "list",
+                "set",
+                "hyperloglog",
+                "geo",
+                "stream",
+                "connection",
+            ]
+            .into_iter()
+            .find(|c| category(c))
+            .unwrap_or("server"),
+        }
+    }
+
+    /// Find a subcommand by its lowercase name, without the container name.
+    pub fn subcommand(&self, name: &str) -> Option<&'static CommandSpec> {
+        self.subcommands
+            .iter()
+            .find(|spec| spec.name.split_once('|').map(|(_, sub)| sub) == Some(name))
+    }
+
+    /// The entry which handles the arguments, the command name included:
+    /// the subcommand of a container like CLIENT, or the command itself.
+    /// The number of arguments is checked against its arity.
+    pub fn resolve(
+        &'static self,
+        args: &[RespFrame],
+    ) -> Result<&'static CommandSpec, CommandError> {
+        self.check_arity(args.len())?;
+        let name = match args.get(1) {
+            Some(RespFrame::BulkString(name)) if !self.subcommands.is_empty() => {
+                String::from_utf8_lossy(name).into_owned()
+            }
+            _ => return Ok(self),
+        };
+        let spec = self.subcommand(&name.to_ascii_lowercase()).ok_or_else(|| {
+            CommandError::InvalidArgument(format!(
+                "unknown subcommand '{}'. Try {} HELP.",
+                name,
+                self.name.to_ascii_uppercase()
+            ))
+        })?;
+        spec.check_arity(args.len())?;
+        Ok(spec)
+    }
+
+    /// Resolve the entry of the arguments, then parse the command.
+    pub fn parse(&'static self, value: RespArray) -> Result<Command, CommandError> {
+        let spec = self.resolve(&value)?;
+        (spec.parse)(value)
+    }
+
+    /// The key specs, a command with its keys at fixed positions has a range.
+    pub fn key_specs(&self) -> Vec<KeySpec> {
+        match self.key_spec {
+            Some(spec) => vec![spec],
+            None if self.first_key > 0 => vec![KeySpec {
+                begin_search: BeginSearch::Index(self.first_key),
+                find_keys: FindKeys::Range {
+                    last_key: match self.last_key {
+                        last if last < 0 => last,
+                        last => last - self.first_key,
+                    },
+                    key_step: self.step,
+                    limit: 0,
+                },
+            }],
+            None => vec![],
+        }
+    }
+
+    /// The positions of the keys in the arguments, the command name included.
+    pub fn key_positions(&self, args: &[Vec<u8>]) -> Vec<usize> {
+        self.key_specs()
+            .iter()
+            .flat_map(|spec| spec.key_positions(args))
+            .collect()
+    }
+
+    fn check_arity(&self, n: usize) -> Result<(), CommandError> {
+        let n = n as i64;
+        if (self.arity >= 0 && n != self.arity) || n < -self.arity {
+            return Err(wrong_arity(self.name));
+        }
+        Ok(())
+    }
+}
+
+impl KeySpec {
+    /// The positions of the keys in the arguments, like redis the positions
+    /// beyond the arguments are ignored.
+    pub fn key_positions(&self, args: &[Vec<u8>]) -> Vec<usize> {
+        let argc = args.len() as i64;
+        let start = match self.begin_search {
+            BeginSearch::Index(index) => index,
+            BeginSearch::Keyword {
+                keyword,
+                start_from,
+            } => {
+                let found = |i: &i64| args[*i as usize].eq_ignore_ascii_case(keyword.as_bytes());
+                let pos = match start_from {
+                    from if from >= 0 => (from..argc).find(found),
+                    from => (1..=argc + from).rev().find(found),
+                };
+                match pos {
+                    Some(pos) => pos + 1,
+                    None => return vec![],
+                }
+            }
+        };
+        let (first, last, step) = match self.find_keys {
+            FindKeys::Range {
+                last_key,
+                key_step,
+                limit,
+            } => {
+                let last = match last_key {
+                    last if last >= 0 => start + last,
+                    last if limit <= 1 => argc + last,
+                    last => start + (argc - start) / limit + last,
+                };
+                (start, last, key_step)
+            }
+            FindKeys::KeyNum {
+                key_num_idx,
+                first_key,
+                key_step,
+            } => {
+                // the number of keys comes from the client, there can't be more keys than arguments
+                let numkeys = args
+                    .get((start + key_num_idx) as usize)
+                    .and_then(|n| String::from_utf8_lossy(n).parse::<i64>().ok())
+                    .unwrap_or(0)
+                    .clamp(0, argc);
+                let first = start + first_key;
+                (first, first + (numkeys - 1) * key_step, key_step)
+            }
+        };
+        (first.max(1)..=last.min(argc - 1))
+            .step_by(step.max(1) as usize)
+            .map(|i| i as usize)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BulkString;
+
+    #[test]
+    fn test_lookup_command() {
+        let spec = lookup_command(b"GeT").unwrap();
+        assert_eq!(spec.name, "get");
+        assert_eq!(
+            (spec.arity, spec.first_key, spec.last_key, spec.step),
+            (2, 1, 1, 1)
+        );
+        assert_eq!(spec.acl_categories(), vec!["read", "string", "fast"]);
+        assert!(lookup_command(b"foo").is_none());
+
+        let spec = find_command("client|kill").unwrap();
+        assert_eq!(
+            spec.acl_categories(),
+            vec!["admin", "slow", "dangerous", "connection"]
+        );
+
+        let get = lookup_command(b"get").unwrap();
+        let value = RespArray::new([BulkString::new("GET").into()]);
+        assert_eq!(
+            get.parse(value).unwrap_err().to_string(),
+            "Invalid argument: wrong number of arguments for 'get' command"
+        );
+        let value = RespArray::new([BulkString::new("GET").into(), BulkString::new("a").into()]);
+        assert!(matches!(get.parse(value), Ok(Command::Get(_))));
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let client = lookup_command(b"CLIENT").unwrap();
+        let args = [
+            BulkString::new("client").into(),
+            BulkString::new("Id").into(),
+        ];
+        assert_eq!(client.resolve(&args).unwrap().name, "client|id");
+
+        let args = [
+            BulkString::new("client").into(),
+            BulkString::new("foo").into(),
+        ];
+        assert_eq!(
+            client.resolve(&args).unwrap_err().to_string(),
+            "Invalid argument: unknown subcommand 'foo'. Try CLIENT HELP."
+        );
+        assert_eq!(find_command("client|KILL").unwrap().name, "client|kill");
+        assert_eq!(find_command("client|kill").unwrap().group(), "connection");
+
+        let sintercard = lookup_command(b"sintercard").unwrap();
+        let args = ["sintercard", "2", "a", "b", "LIMIT", "1"].map(|s| s.as_bytes().to_vec());
+        assert_eq!(sintercard.key_positions(&args), vec![2, 3]);
+        let args = ["sintercard", "9223372036854775807", "a"].map(|s| s.as_bytes().to_vec());
+        assert_eq!(sintercard.key_positions(&args), vec![2]);
+        let args = ["sintercard", "-9223372036854775808", "a"].map(|s| s.as_bytes().to_vec());
+        assert!(sintercard.key_positions(&args).is_empty());
+    }
+}
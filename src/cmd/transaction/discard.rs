@@ -0,0 +1,65 @@
+use crate::{
+    cmd::{connection_only, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame, SimpleError,
+};
+
+use super::Transaction;
+
+// discard: https://redis.io/docs/latest/commands/discard/
+// DISCARD
+
+#[derive(Debug)]
+pub struct Discard;
+
+impl Discard {
+    /// Drop the queued commands and unwatch all the keys.
+    pub fn execute_transaction(self, txn: &mut Transaction) -> RespFrame {
+        match txn.reset() {
+            Some(_) => RESP_OK.clone(),
+            None => SimpleError::new("ERR DISCARD without MULTI".to_string()).into(),
+        }
+    }
+}
+
+impl CommandExecutor for Discard {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("DISCARD")
+    }
+}
+
+impl TryFrom<RespArray> for Discard {
+    type Error = CommandError;
+    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Discard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd::Command, RespDecode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_discard() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n$7\r\ndiscard\r\n*2\r\n$4\r\necho\r\n$5\r\nhello\r\n");
+
+        let discard: Discard = RespArray::decode(&mut buf)?.try_into()?;
+        let mut txn = Transaction {
+            queued: Some(Vec::new()),
+            ..Default::default()
+        };
+        txn.queue(Command::try_from(RespArray::decode(&mut buf)?)?);
+        assert_eq!(discard.execute_transaction(&mut txn), RESP_OK.clone());
+        assert!(!txn.in_multi());
+        assert_eq!(
+            Discard.execute_transaction(&mut txn),
+            SimpleError::new("ERR DISCARD without MULTI".to_string()).into()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    cmd::{connection_only, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNullArray, SimpleError,
+};
+
+use super::Transaction;
+
+// exec: https://redis.io/docs/latest/commands/exec/
+// EXEC
+
+#[derive(Debug)]
+pub struct Exec;
+
+impl Exec {
+    /// Execute the queued commands atomically, replying an array of their replies,
+    /// or nil if a watched key has been modified.
+    pub fn execute_transaction(self, txn: &mut Transaction, backend: &Backend) -> RespFrame {
+        let failed = txn.failed;
+        let watched = std::mem::take(&mut txn.watched);
+        let Some(queued) = txn.reset() else {
+            return SimpleError::new("ERR EXEC without MULTI".to_string()).into();
+        };
+        if failed {
+            return SimpleError::new(
+                "EXECABORT Transaction discarded because of previous errors.".to_string(),
+            )
+            .into();
+        }
+
+        let _guard = backend.lock_exclusive();
+        if watched.iter().any(|key| backend.is_modified(key)) {
+            return RespFrame::NullArray(RespNullArray);
+        }
+        // the blocking commands don't block inside a transaction
+        let replies = queued
+            .into_iter()
+            .map(|cmd| cmd.execute(backend))
+            .collect::<Vec<_>>();
+        RespArray::new(replies).into()
+    }
+}
+
+impl CommandExecutor for Exec {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("EXEC")
+    }
+}
+
+impl TryFrom<RespArray> for Exec {
+    type Error = CommandError;
+    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Exec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmd::Command, BulkString, RespDecode, RespEncode};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn queued(frames: &[u8]) -> Result<Transaction> {
+        let mut buf = BytesMut::from(frames);
+        let mut txn = Transaction {
+            queued: Some(Vec::new()),
+            ..Default::default()
+        };
+        while !buf.is_empty() {
+            txn.queue(Command::try_from(RespArray::decode(&mut buf)?)?);
+        }
+        Ok(txn)
+    }
+
+    #[test]
+    fn test_exec() -> Result<()> {
+        let backend = Backend::new();
+        let mut txn = queued(
+            b"*3\r\n$3\r\nset\r\n$5\r\nhello\r\n$5\r\nworld\r\n*2\r\n$4\r\nincr\r\n$5\r\nhello\r\n\
+              *2\r\n$3\r\nget\r\n$5\r\nhello\r\n",
+        )?;
+        let RespFrame::Array(replies) = Exec.execute_transaction(&mut txn, &backend) else {
+            panic!("expected an array");
+        };
+        // a command failing at runtime doesn't abort the others
+        assert_eq!(replies.len(), 3);
+        assert!(matches!(replies[1], RespFrame::Error(_)));
+        assert_eq!(replies[2], BulkString::new("world").into());
+        assert!(!txn.in_multi());
+
+        let mut txn = queued(b"")?;
+        txn.fail();
+        let reply = Exec.execute_transaction(&mut txn, &backend);
+        assert_eq!(
+            reply,
+            SimpleError::new(
+                "EXECABORT Transaction discarded because of previous errors.".to_string()
+            )
+            .into()
+        );
+
+        // nothing queued is an empty array, not the nil of an aborted transaction
+        let mut txn = queued(b"")?;
+        assert_eq!(
+            Exec.execute_transaction(&mut txn, &backend).encode(),
+            b"*0\r\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_watch() -> Result<()> {
+        let backend = Backend::new();
+        let mut txn = queued(b"*2\r\n$3\r\nget\r\n$5\r\nhello\r\n")?;
+        txn.watched.push(backend.watch("hello"));
+        backend.set("hello".to_string(), "world");
+        assert_eq!(
+            Exec.execute_transaction(&mut txn, &backend),
+            RespFrame::NullArray(RespNullArray)
+        );
+        assert!(txn.watched.is_empty());
+
+        Ok(())
+    }
+}
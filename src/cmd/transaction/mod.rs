@@ -0,0 +1,56 @@
+mod discard;
+mod exec;
+mod multi;
+mod watch;
+
+pub(crate) use discard::Discard;
+pub(crate) use exec::Exec;
+pub(crate) use multi::Multi;
+pub(crate) use watch::{Unwatch, Watch};
+
+use crate::{RespFrame, SimpleString, WatchedKey};
+
+use super::Command;
+
+/// The transaction state of a connection.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    queued: Option<Vec<Command>>, // Some after MULTI
+    failed: bool,                 // a command failed to be queued, EXEC aborts
+    watched: Vec<WatchedKey>,
+}
+
+impl Transaction {
+    /// Whether the commands are queued instead of executed.
+    pub fn in_multi(&self) -> bool {
+        self.queued.is_some()
+    }
+
+    /// The number of the queued commands, None outside MULTI.
+    pub fn queued_len(&self) -> Option<usize> {
+        self.queued.as_ref().map(Vec::len)
+    }
+
+    pub fn watched_len(&self) -> usize {
+        self.watched.len()
+    }
+
+    pub fn queue(&mut self, cmd: Command) -> RespFrame {
+        if let Some(queued) = self.queued.as_mut() {
+            queued.push(cmd);
+        }
+        SimpleString::new("QUEUED").into()
+    }
+
+    /// A command can't be queued, e.g. for a syntax error, so the transaction is discarded by EXEC.
+    pub fn fail(&mut self) {
+        self.failed = true;
+    }
+
+    /// Leave MULTI and forget the watched keys.
+    fn reset(&mut self) -> Option<Vec<Command>> {
+        self.failed = false;
+        self.watched.clear();
+        self.queued.take()
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    cmd::{connection_only, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame, SimpleError,
+};
+
+use super::Transaction;
+
+// multi: https://redis.io/docs/latest/commands/multi/
+// MULTI
+
+#[derive(Debug)]
+pub struct Multi;
+
+impl Multi {
+    pub fn execute_transaction(self, txn: &mut Transaction) -> RespFrame {
+        if txn.in_multi() {
+            return SimpleError::new("ERR MULTI calls can not be nested".to_string()).into();
+        }
+        txn.queued = Some(Vec::new());
+        RESP_OK.clone()
+    }
+}
+
+impl CommandExecutor for Multi {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        connection_only("MULTI")
+    }
+}
+
+impl TryFrom<RespArray> for Multi {
+    type Error = CommandError;
+    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Multi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_multi() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n$5\r\nmulti\r\n*1\r\n$5\r\nmulti\r\n");
+
+        let mut txn = Transaction::default();
+        let multi: Multi = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(multi.execute_transaction(&mut txn), RESP_OK.clone());
+        assert!(txn.in_multi());
+        let multi: Multi = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(
+            multi.execute_transaction(&mut txn),
+            SimpleError::new("ERR MULTI calls can not be nested".to_string()).into()
+        );
+
+        Ok(())
+    }
+}
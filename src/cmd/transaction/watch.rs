@@ -0,0 +1,97 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor, RESP_OK},
+    Backend, RespArray, RespFrame, SimpleError,
+};
+
+use super::Transaction;
+
+// watch: https://redis.io/docs/latest/commands/watch/
+// WATCH key [key ...]
+// unwatch: https://redis.io/docs/latest/commands/unwatch/
+// UNWATCH
+
+#[derive(Debug)]
+pub struct Watch {
+    keys: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Unwatch;
+
+impl Watch {
+    /// Make the next EXEC fail if any of the keys is modified in the meantime.
+    pub fn execute_transaction(self, txn: &mut Transaction, backend: &Backend) -> RespFrame {
+        if txn.in_multi() {
+            return SimpleError::new("ERR WATCH inside MULTI is not allowed".to_string()).into();
+        }
+        txn.watched
+            .extend(self.keys.iter().map(|key| backend.watch(key)));
+        RESP_OK.clone()
+    }
+}
+
+/// WATCH needs the state of a connection, nothing is watched without one.
+impl CommandExecutor for Watch {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        RESP_OK.clone()
+    }
+}
+
+impl Unwatch {
+    pub fn execute_transaction(self, txn: &mut Transaction) -> RespFrame {
+        txn.watched.clear();
+        RESP_OK.clone()
+    }
+}
+
+/// Inside a transaction UNWATCH is queued, EXEC unwatches all the keys anyway.
+impl CommandExecutor for Unwatch {
+    fn execute(self, _backend: &Backend) -> RespFrame {
+        RESP_OK.clone()
+    }
+}
+
+impl TryFrom<RespArray> for Watch {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let keys = CommandArgs::new(value, 1)?.rest_strings()?;
+        Ok(Watch { keys })
+    }
+}
+
+impl TryFrom<RespArray> for Unwatch {
+    type Error = CommandError;
+    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
+        Ok(Unwatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_watch_unwatch() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$5\r\nwatch\r\n$5\r\nhello\r\n$5\r\nworld\r\n");
+
+        let watch: Watch = RespArray::decode(&mut buf)?.try_into()?;
+        assert_eq!(watch.keys, ["hello", "world"]);
+
+        let backend = Backend::new();
+        let mut txn = Transaction::default();
+        assert_eq!(
+            watch.execute_transaction(&mut txn, &backend),
+            RESP_OK.clone()
+        );
+        assert_eq!(txn.watched.len(), 2);
+        assert_eq!(Unwatch.execute_transaction(&mut txn), RESP_OK.clone());
+        assert!(txn.watched.is_empty());
+
+        Ok(())
+    }
+}
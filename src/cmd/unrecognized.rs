@@ -1,20 +1,56 @@
-use crate::{Backend, RespArray, RespFrame};
+use crate::{Backend, RespArray, RespFrame, SimpleError};
 
-use super::{command::CommandError, CommandExecutor, RESP_OK};
+use super::{command::CommandError, CommandExecutor};
 
+// redis truncates the command name and the arguments in the error reply
+const MAX_REPLY_ARGS_LEN: usize = 128;
+
+/// A command which is not implemented, replied as an error like redis.
 #[derive(Debug)]
-pub struct Unrecognized;
+pub struct Unrecognized {
+    name: String,
+    args: Vec<String>,
+}
+
+/// At most `n` chars of `s`, a newline would break the error reply.
+fn sanitize(s: &str, n: usize) -> String {
+    s.chars()
+        .take(n)
+        .map(|c| if c == '\r' || c == '\n' { ' ' } else { c })
+        .collect()
+}
 
 impl CommandExecutor for Unrecognized {
-    fn execute(self, _: &Backend) -> RespFrame {
-        RESP_OK.clone()
+    fn execute(self, backend: &Backend) -> RespFrame {
+        backend.incr_unknown_commands();
+        let mut args = String::new();
+        for arg in &self.args {
+            if args.len() >= MAX_REPLY_ARGS_LEN {
+                break;
+            }
+            let arg = sanitize(arg, MAX_REPLY_ARGS_LEN - args.len());
+            args.push_str(&format!("'{}' ", arg));
+        }
+        SimpleError::new(format!(
+            "ERR unknown command '{}', with args beginning with: {}",
+            sanitize(&self.name, MAX_REPLY_ARGS_LEN),
+            args
+        ))
+        .into()
     }
 }
 
 impl TryFrom<RespArray> for Unrecognized {
     type Error = CommandError;
-    fn try_from(_value: RespArray) -> Result<Self, Self::Error> {
-        Ok(Unrecognized)
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = value.0.into_iter().map(|frame| match frame {
+            RespFrame::BulkString(s) => String::from_utf8_lossy(&s).into_owned(),
+            _ => String::new(),
+        });
+        Ok(Unrecognized {
+            name: args.next().unwrap_or_default(),
+            args: args.collect(),
+        })
     }
 }
 
@@ -28,12 +64,18 @@ mod tests {
     #[test]
     fn test_unrecognized_command() -> Result<()> {
         let mut buf = BytesMut::new();
-        buf.extend_from_slice(b"*1\r\n$3\r\nfoo\r\n");
+        buf.extend_from_slice(b"*3\r\n$3\r\nfoo\r\n$3\r\nbar\r\n$3\r\nbaz\r\n");
 
         let frame = RespArray::decode(&mut buf)?;
 
+        let backend = Backend::new();
         let result: Unrecognized = frame.try_into()?;
-        assert_eq!(result.execute(&Backend::new()), RESP_OK.clone());
+        assert_eq!(
+            result.execute(&backend),
+            SimpleError::new("ERR unknown command 'foo', with args beginning with: 'bar' 'baz' ")
+                .into()
+        );
+        assert_eq!(backend.stats().unknown_commands, 1);
 
         Ok(())
     }
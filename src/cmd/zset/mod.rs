@@ -0,0 +1,32 @@
+mod zadd;
+mod zcard;
+mod zincrby;
+mod zpop;
+mod zrange;
+mod zrank;
+mod zrem;
+mod zscore;
+
+pub(crate) use zadd::ZAdd;
+pub(crate) use zcard::ZCard;
+pub(crate) use zincrby::ZIncrBy;
+pub(crate) use zpop::{ZPopMax, ZPopMin};
+pub(crate) use zrange::{ZRange, ZRangeByScore};
+pub(crate) use zrank::ZRank;
+pub(crate) use zrem::ZRem;
+pub(crate) use zscore::ZScore;
+
+use crate::{BulkString, RespArray, RespFrame};
+
+/// Reply the elements as a flat array of the members, followed by their scores
+/// if `with_scores`.
+fn elements_reply(elements: Vec<(String, f64)>, with_scores: bool) -> RespFrame {
+    let mut frames = Vec::with_capacity(elements.len() * if with_scores { 2 } else { 1 });
+    for (member, score) in elements {
+        frames.push(BulkString::new(member).into());
+        if with_scores {
+            frames.push(RespFrame::Double(score));
+        }
+    }
+    RespArray::new(frames).into()
+}
@@ -0,0 +1,131 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull, ScoreCompare, SetCondition, ZAddFlags,
+};
+
+// zadd: https://redis.io/docs/latest/commands/zadd/
+// ZADD key [NX | XX] [GT | LT] [CH] [INCR] score member [score member ...]
+
+#[derive(Debug)]
+pub struct ZAdd {
+    key: String,
+    flags: ZAddFlags,
+    incr: bool,
+    elements: Vec<(f64, String)>,
+}
+
+impl CommandExecutor for ZAdd {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        if self.incr {
+            let (increment, member) = self.elements.into_iter().next().unwrap_or_default();
+            return match backend.zincrby(&self.key, member, increment, self.flags) {
+                Ok(Some(score)) => RespFrame::Double(score),
+                // the operation was aborted because of the NX / XX / GT / LT options
+                Ok(None) => RespFrame::Null(RespNull),
+                Err(e) => e.into(),
+            };
+        }
+        match backend.zadd(&self.key, self.elements, self.flags) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for ZAdd {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let (mut nx, mut xx, mut gt, mut lt, mut ch, mut incr) =
+            (false, false, false, false, false, false);
+        while let Some(option) = args.peek_option() {
+            match option.as_str() {
+                "nx" => nx = true,
+                "xx" => xx = true,
+                "gt" => gt = true,
+                "lt" => lt = true,
+                "ch" => ch = true,
+                "incr" => incr = true,
+                _ => break,
+            }
+            args.next_frame()?;
+        }
+
+        if nx && xx {
+            return Err(CommandError::InvalidArgument(
+                "XX and NX options at the same time are not compatible".to_string(),
+            ));
+        }
+        if (gt && lt) || (nx && (gt || lt)) {
+            return Err(CommandError::InvalidArgument(
+                "GT, LT, and/or NX options at the same time are not compatible".to_string(),
+            ));
+        }
+        let flags = ZAddFlags {
+            condition: match (nx, xx) {
+                (true, _) => SetCondition::Nx,
+                (_, true) => SetCondition::Xx,
+                _ => SetCondition::Always,
+            },
+            compare: match (gt, lt) {
+                (true, _) => Some(ScoreCompare::Gt),
+                (_, true) => Some(ScoreCompare::Lt),
+                _ => None,
+            },
+            ch,
+        };
+
+        if args.is_empty() || args.len() % 2 != 0 {
+            return Err(syntax_error());
+        }
+        if incr && args.len() > 2 {
+            return Err(CommandError::InvalidArgument(
+                "INCR option supports a single increment-element pair".to_string(),
+            ));
+        }
+
+        let mut elements = Vec::with_capacity(args.len() / 2);
+        while !args.is_empty() {
+            elements.push((args.next_float()?, args.next_string()?));
+        }
+        Ok(ZAdd {
+            key,
+            flags,
+            incr,
+            elements,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zadd_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*7\r\n$4\r\nzadd\r\n$1\r\nz\r\n$2\r\nXX\r\n$2\r\nch\r\n$1\r\n1\r\n$1\r\na\r\n$4\r\n-inf\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(ZAdd::try_from(frame).is_err());
+
+        buf.extend_from_slice(b"*8\r\n$4\r\nzadd\r\n$1\r\nz\r\n$2\r\nXX\r\n$2\r\nch\r\n$1\r\n1\r\n$1\r\na\r\n$4\r\n-inf\r\n$1\r\nb\r\n");
+        let frame = RespArray::decode(&mut buf)?;
+        let result: ZAdd = frame.try_into()?;
+        assert_eq!(result.key, "z");
+        assert_eq!(result.flags.condition, SetCondition::Xx);
+        assert!(result.flags.ch);
+        assert!(!result.incr);
+        assert_eq!(
+            result.elements,
+            vec![(1.0, "a".to_string()), (f64::NEG_INFINITY, "b".to_string())]
+        );
+
+        Ok(())
+    }
+}
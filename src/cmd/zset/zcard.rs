@@ -0,0 +1,57 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+// zcard: https://redis.io/docs/latest/commands/zcard/
+// ZCARD key
+
+#[derive(Debug)]
+pub struct ZCard {
+    key: String,
+}
+
+impl CommandExecutor for ZCard {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.zcard(&self.key) {
+            Ok(n) => n.into(),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for ZCard {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(ZCard {
+            key: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zcard_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$5\r\nzcard\r\n$1\r\nz\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZCard = frame.try_into()?;
+        assert_eq!(result.key, "z");
+
+        let backend = Backend::new();
+        backend.zadd("z", vec![(1.0, "a".to_string())], ZAddFlags::default())?;
+        assert_eq!(result.execute(&backend), RespFrame::Integer(1));
+
+        Ok(())
+    }
+}
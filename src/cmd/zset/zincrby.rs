@@ -0,0 +1,60 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull, ZAddFlags,
+};
+
+// zincrby: https://redis.io/docs/latest/commands/zincrby/
+// ZINCRBY key increment member
+
+#[derive(Debug)]
+pub struct ZIncrBy {
+    key: String,
+    increment: f64,
+    member: String,
+}
+
+impl CommandExecutor for ZIncrBy {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.zincrby(&self.key, self.member, self.increment, ZAddFlags::default()) {
+            Ok(Some(score)) => RespFrame::Double(score),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for ZIncrBy {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(ZIncrBy {
+            key: args.next_string()?,
+            increment: args.next_float()?,
+            member: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zincrby_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$7\r\nzincrby\r\n$1\r\nz\r\n$3\r\n2.5\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZIncrBy = frame.try_into()?;
+        assert_eq!(result.key, "z");
+        assert_eq!(result.increment, 2.5);
+        assert_eq!(result.member, "a");
+
+        Ok(())
+    }
+}
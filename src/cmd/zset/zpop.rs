@@ -0,0 +1,122 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame,
+};
+
+use super::elements_reply;
+
+// zpopmin: https://redis.io/docs/latest/commands/zpopmin/
+// ZPOPMIN key [count]
+// ZPOPMAX key [count]
+
+#[derive(Debug)]
+struct ZPopArgs {
+    key: String,
+    count: usize,
+}
+
+#[derive(Debug)]
+pub struct ZPopMin(ZPopArgs);
+
+#[derive(Debug)]
+pub struct ZPopMax(ZPopArgs);
+
+impl ZPopArgs {
+    fn execute(self, backend: &Backend, max: bool) -> RespFrame {
+        match backend.zpop(&self.key, self.count, max) {
+            Ok(elements) => elements_reply(elements, true),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for ZPopMin {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, false)
+    }
+}
+
+impl CommandExecutor for ZPopMax {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend, true)
+    }
+}
+
+fn parse_zpop_args(value: RespArray) -> Result<ZPopArgs, CommandError> {
+    if value.len() > 3 {
+        return Err(syntax_error());
+    }
+
+    let mut args = CommandArgs::new(value, 1)?;
+    let key = args.next_string()?;
+    let count = match args.is_empty() {
+        true => 1,
+        false => match args.next_integer()? {
+            count if count >= 0 => count as usize,
+            _ => {
+                return Err(CommandError::InvalidArgument(
+                    "value is out of range, must be positive".to_string(),
+                ))
+            }
+        },
+    };
+    Ok(ZPopArgs { key, count })
+}
+
+impl TryFrom<RespArray> for ZPopMin {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(ZPopMin(parse_zpop_args(value)?))
+    }
+}
+
+impl TryFrom<RespArray> for ZPopMax {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        Ok(ZPopMax(parse_zpop_args(value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zpopmax_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$7\r\nzpopmax\r\n$1\r\nz\r\n$1\r\n2\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZPopMax = frame.try_into()?;
+        assert_eq!(result.0.key, "z");
+        assert_eq!(result.0.count, 2);
+
+        let backend = Backend::new();
+        let elements = vec![(1.0, "a".to_string()), (2.0, "b".to_string())];
+        backend.zadd("z", elements, ZAddFlags::default())?;
+        let expected = RespArray::new([
+            BulkString::new("b").into(),
+            RespFrame::Double(2.0),
+            BulkString::new("a").into(),
+            RespFrame::Double(1.0),
+        ]);
+        let reply = result.execute(&backend);
+        assert_eq!(reply, expected.into());
+        // the scores are bulk strings for a RESP2 connection
+        let expected = RespArray::new([
+            BulkString::new("b").into(),
+            BulkString::new("2").into(),
+            BulkString::new("a").into(),
+            BulkString::new("1").into(),
+        ]);
+        assert_eq!(reply.into_resp2(), expected.into());
+        assert!(!backend.exists("z"));
+
+        Ok(())
+    }
+}
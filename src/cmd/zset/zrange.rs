@@ -0,0 +1,226 @@
+use crate::{
+    cmd::{extract_string, syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, LexBound, RespArray, RespFrame, ScoreBound, ZRangeBy,
+};
+
+use super::elements_reply;
+
+// zrange: https://redis.io/docs/latest/commands/zrange/
+// ZRANGE key start stop [BYSCORE | BYLEX] [REV] [LIMIT offset count] [WITHSCORES]
+// zrangebyscore: https://redis.io/docs/latest/commands/zrangebyscore/
+// ZRANGEBYSCORE key min max [WITHSCORES] [LIMIT offset count]
+
+#[derive(Debug)]
+struct ZRangeArgs {
+    key: String,
+    range: ZRangeBy,
+    rev: bool,
+    limit: Option<(i64, i64)>,
+    with_scores: bool,
+}
+
+#[derive(Debug)]
+pub struct ZRange(ZRangeArgs);
+
+#[derive(Debug)]
+pub struct ZRangeByScore(ZRangeArgs);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeType {
+    Rank,
+    Score,
+    Lex,
+}
+
+impl ZRangeArgs {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.zrange(&self.key, &self.range, self.rev, self.limit) {
+            Ok(elements) => elements_reply(elements, self.with_scores),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl CommandExecutor for ZRange {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend)
+    }
+}
+
+impl CommandExecutor for ZRangeByScore {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        self.0.execute(backend)
+    }
+}
+
+/// Parse a score bound, `(` makes it exclusive.
+fn parse_score_bound(s: &str) -> Result<ScoreBound, CommandError> {
+    let (s, exclusive) = match s.strip_prefix('(') {
+        Some(s) => (s, true),
+        None => (s, false),
+    };
+    match s.parse::<f64>() {
+        Ok(value) if !value.is_nan() => Ok(ScoreBound { value, exclusive }),
+        _ => Err(CommandError::InvalidArgument(
+            "min or max is not a float".to_string(),
+        )),
+    }
+}
+
+/// Parse a lex bound, `[` is inclusive, `(` is exclusive, `-` / `+` are the infinities.
+fn parse_lex_bound(s: String) -> Result<LexBound, CommandError> {
+    match s.as_bytes().first() {
+        Some(b'-') if s.len() == 1 => Ok(LexBound::Min),
+        Some(b'+') if s.len() == 1 => Ok(LexBound::Max),
+        Some(b'[') => Ok(LexBound::Inclusive(s[1..].to_string())),
+        Some(b'(') => Ok(LexBound::Exclusive(s[1..].to_string())),
+        _ => Err(CommandError::InvalidArgument(
+            "min or max not valid string range item".to_string(),
+        )),
+    }
+}
+
+/// Build the range from the arguments, `min` / `max` are already swapped for REV.
+fn parse_range(range_type: RangeType, min: String, max: String) -> Result<ZRangeBy, CommandError> {
+    Ok(match range_type {
+        RangeType::Rank => {
+            let parse = |s: String| {
+                s.parse::<i64>().map_err(|_| {
+                    CommandError::InvalidArgument(
+                        "value is not an integer or out of range".to_string(),
+                    )
+                })
+            };
+            ZRangeBy::Rank(parse(min)?, parse(max)?)
+        }
+        RangeType::Score => ZRangeBy::Score(parse_score_bound(&min)?, parse_score_bound(&max)?),
+        RangeType::Lex => ZRangeBy::Lex(parse_lex_bound(min)?, parse_lex_bound(max)?),
+    })
+}
+
+fn parse_limit(args: &mut CommandArgs) -> Result<(i64, i64), CommandError> {
+    Ok((args.next_integer()?, args.next_integer()?))
+}
+
+impl TryFrom<RespArray> for ZRange {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let start = extract_string(args.next_frame()?)?;
+        let stop = extract_string(args.next_frame()?)?;
+
+        let (mut range_type, mut rev, mut limit, mut with_scores) =
+            (RangeType::Rank, false, None, false);
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "byscore" if range_type == RangeType::Rank => range_type = RangeType::Score,
+                "bylex" if range_type == RangeType::Rank => range_type = RangeType::Lex,
+                "rev" => rev = true,
+                "limit" => limit = Some(parse_limit(&mut args)?),
+                "withscores" => with_scores = true,
+                _ => return Err(syntax_error()),
+            }
+        }
+
+        if limit.is_some() && range_type == RangeType::Rank {
+            return Err(CommandError::InvalidArgument(
+                "syntax error, LIMIT is only supported in combination with either BYSCORE or BYLEX"
+                    .to_string(),
+            ));
+        }
+        if with_scores && range_type == RangeType::Lex {
+            return Err(CommandError::InvalidArgument(
+                "syntax error, WITHSCORES not supported in combination with BYLEX".to_string(),
+            ));
+        }
+
+        // with REV, the score / lex ranges are given as max min
+        let (min, max) = match (rev, range_type) {
+            (true, RangeType::Score | RangeType::Lex) => (stop, start),
+            _ => (start, stop),
+        };
+        Ok(ZRange(ZRangeArgs {
+            key,
+            range: parse_range(range_type, min, max)?,
+            rev,
+            limit,
+            with_scores,
+        }))
+    }
+}
+
+impl TryFrom<RespArray> for ZRangeByScore {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let min = extract_string(args.next_frame()?)?;
+        let max = extract_string(args.next_frame()?)?;
+
+        let (mut limit, mut with_scores) = (None, false);
+        while let Some(option) = args.next_option()? {
+            match option.as_str() {
+                "limit" => limit = Some(parse_limit(&mut args)?),
+                "withscores" => with_scores = true,
+                _ => return Err(syntax_error()),
+            }
+        }
+        Ok(ZRangeByScore(ZRangeArgs {
+            key,
+            range: parse_range(RangeType::Score, min, max)?,
+            rev: false,
+            limit,
+            with_scores,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zrange_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*10\r\n$6\r\nzrange\r\n$1\r\nz\r\n$4\r\n+inf\r\n$2\r\n(1\r\n$7\r\nBYSCORE\r\n$3\r\nREV\r\n$5\r\nLIMIT\r\n$1\r\n0\r\n$1\r\n5\r\n$10\r\nWITHSCORES\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZRange = frame.try_into()?;
+        assert_eq!(result.0.key, "z");
+        assert_eq!(
+            result.0.range,
+            ZRangeBy::Score(
+                ScoreBound {
+                    value: 1.0,
+                    exclusive: true
+                },
+                ScoreBound {
+                    value: f64::INFINITY,
+                    exclusive: false
+                }
+            )
+        );
+        assert!(result.0.rev);
+        assert_eq!(result.0.limit, Some((0, 5)));
+        assert!(result.0.with_scores);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zrange_bylex_withscores() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*6\r\n$6\r\nzrange\r\n$1\r\nz\r\n$1\r\n-\r\n$2\r\n[c\r\n$5\r\nBYLEX\r\n$10\r\nWITHSCORES\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+        assert!(ZRange::try_from(frame).is_err());
+
+        Ok(())
+    }
+}
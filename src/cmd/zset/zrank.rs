@@ -0,0 +1,74 @@
+use crate::{
+    cmd::{syntax_error, CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull, RespNullArray,
+};
+
+// zrank: https://redis.io/docs/latest/commands/zrank/
+// ZRANK key member [WITHSCORE]
+
+#[derive(Debug)]
+pub struct ZRank {
+    key: String,
+    member: String,
+    with_score: bool,
+}
+
+impl CommandExecutor for ZRank {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.zrank(&self.key, &self.member, false) {
+            Ok(Some((rank, score))) if self.with_score => {
+                RespArray::new([(rank as i64).into(), RespFrame::Double(score)]).into()
+            }
+            Ok(Some((rank, _))) => (rank as i64).into(),
+            Ok(None) if self.with_score => RespFrame::NullArray(RespNullArray),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for ZRank {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        let key = args.next_string()?;
+        let member = args.next_string()?;
+        let with_score = match args.next_option()?.as_deref() {
+            None => false,
+            Some("withscore") => true,
+            Some(_) => return Err(syntax_error()),
+        };
+        if !args.is_empty() {
+            return Err(syntax_error());
+        }
+        Ok(ZRank {
+            key,
+            member,
+            with_score,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespDecode;
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zrank_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*4\r\n$5\r\nzrank\r\n$1\r\nz\r\n$1\r\na\r\n$9\r\nWITHSCORE\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZRank = frame.try_into()?;
+        assert_eq!(result.key, "z");
+        assert_eq!(result.member, "a");
+        assert!(result.with_score);
+
+        Ok(())
+    }
+}
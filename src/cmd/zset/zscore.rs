@@ -0,0 +1,63 @@
+use crate::{
+    cmd::{CommandArgs, CommandError, CommandExecutor},
+    Backend, RespArray, RespFrame, RespNull,
+};
+
+// zscore: https://redis.io/docs/latest/commands/zscore/
+// ZSCORE key member
+
+#[derive(Debug)]
+pub struct ZScore {
+    key: String,
+    member: String,
+}
+
+impl CommandExecutor for ZScore {
+    fn execute(self, backend: &Backend) -> RespFrame {
+        match backend.zscore(&self.key, &self.member) {
+            Ok(Some(score)) => RespFrame::Double(score),
+            Ok(None) => RespFrame::Null(RespNull),
+            Err(e) => e.into(),
+        }
+    }
+}
+
+impl TryFrom<RespArray> for ZScore {
+    type Error = CommandError;
+    fn try_from(value: RespArray) -> Result<Self, Self::Error> {
+        let mut args = CommandArgs::new(value, 1)?;
+        Ok(ZScore {
+            key: args.next_string()?,
+            member: args.next_string()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BulkString, RespDecode, ZAddFlags};
+
+    use super::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_zscore_from_resp_array() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*3\r\n$6\r\nzscore\r\n$1\r\nz\r\n$1\r\na\r\n");
+
+        let frame = RespArray::decode(&mut buf)?;
+
+        let result: ZScore = frame.try_into()?;
+        assert_eq!(result.key, "z");
+        assert_eq!(result.member, "a");
+
+        let backend = Backend::new();
+        backend.zadd("z", vec![(1.5, "a".to_string())], ZAddFlags::default())?;
+        let reply = result.execute(&backend);
+        assert_eq!(reply, RespFrame::Double(1.5));
+        assert_eq!(reply.into_resp2(), BulkString::new("1.5").into());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::Result;
 use simple_redis::{network, Backend};
 use tokio::net::TcpListener;
@@ -35,6 +37,8 @@ async fn main() -> Result<()> {
     info!("Simple-Redis-Server is listening on {}", addr);
 
     let backend = Backend::new();
+    // active expiration, remove the expired keys which are never accessed again
+    tokio::spawn(backend.clone().active_expire(Duration::from_millis(100)));
     loop {
         let (stream, remote_addr) = listener.accept().await?;
         info!("Accepted connection from {}", remote_addr);
@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use anyhow::Result;
 use futures::SinkExt;
 use tokio::net::TcpStream;
@@ -6,8 +8,9 @@ use tokio_util::codec::{Decoder, Encoder, Framed};
 use tracing::info;
 
 use crate::{
-    cmd::{Command, CommandExecutor},
-    Backend, RespDecode, RespEncode, RespError, RespFrame,
+    cmd::{message_reply, Command, CommandExecutor, Transaction},
+    Backend, PubSubMessage, RespArray, RespDecode, RespEncode, RespError, RespFrame, RespPush,
+    Session, SimpleError, Subscriber,
 };
 
 #[derive(Debug)]
@@ -18,36 +21,106 @@ struct RedisRequest {
 
 #[derive(Debug)]
 struct RedisResponse {
-    frame: RespFrame,
+    frames: Vec<RespFrame>, // a (un)subscription replies once per channel
 }
 
 #[derive(Debug)]
 struct RespFrameCodec;
 
+/// The state of a client connection.
+#[derive(Debug)]
+struct Connection {
+    session: Session, // registered in the backend for CLIENT LIST / KILL
+    // Some once subscribed to a channel, a pattern or a shard channel, a RESP2
+    // connection in this push mode only accepts the pub/sub commands and PING
+    subscriber: Option<Subscriber>,
+    transaction: Transaction,
+}
+
 pub async fn stream_handler(stream: TcpStream, backend: Backend) -> Result<()> {
     // how to get a frame from the stream?
+    let session = backend.new_session(
+        stream.peer_addr()?.to_string(),
+        stream.local_addr()?.to_string(),
+    );
     let mut framed = Framed::new(stream, RespFrameCodec);
+    let mut conn = Connection::new(session);
+    // the requests read while a blocking command waits, served in order afterwards
+    let mut pending = VecDeque::new();
     loop {
-        match framed.next().await {
+        // the published messages are written out between the replies
+        let frame = match pending.pop_front() {
+            Some(frame) => Some(Ok(frame)),
+            None => tokio::select! {
+                frame = framed.next() => frame,
+                _ = conn.session.killed() => {
+                    info!("Connection {} killed", conn.session.id());
+                    return Ok(());
+                }
+                message = next_message(&mut conn.subscriber) => {
+                    let Some(message) = message else {
+                        info!("Connection {} closed, too many pending messages", conn.session.id());
+                        return Ok(());
+                    };
+                    let frame = conn.push_frame(message_reply(message));
+                    info!("Pushing message: {:?}", frame);
+                    framed.send(frame).await?;
+                    continue;
+                }
+            },
+        };
+        match frame {
             Some(Ok(frame)) => {
                 info!("Received frame: {:?}", frame);
                 let request = RedisRequest {
                     frame,
                     backend: backend.clone(),
                 };
-                let response = request_handler(request).await?;
-                info!("Sending response: {:?}", response.frame);
+                // keep reading while the command blocks, a closed or killed
+                // connection cancels it, so a blocked client doesn't take an
+                // element it can't receive anymore
+                let killed = conn.session.killed();
+                let id = conn.session.id();
+                let response = {
+                    let response = request_handler(request, &mut conn);
+                    tokio::pin!(response, killed);
+                    loop {
+                        tokio::select! {
+                            biased;
+                            response = &mut response => break response?,
+                            _ = &mut killed => {
+                                info!("Connection {} killed", id);
+                                return Ok(());
+                            }
+                            frame = framed.next() => match frame {
+                                Some(Ok(frame)) => pending.push_back(frame),
+                                Some(Err(e)) => return Err(e),
+                                None => return Ok(()),
+                            },
+                        }
+                    }
+                };
+                conn.sync_session();
+                info!("Sending response: {:?}", response.frames);
 
                 // NOTE: When dealing with a large amount of concurrent data,
                 // flushing the sink each time will incur performance overhead.
                 // framed.send(response.frame).await?;
 
                 // 使用 feed 方法添加响应
-                framed.feed(response.frame).await?;
+                for frame in response.frames {
+                    framed.feed(conn.reply_frame(frame)).await?;
+                }
                 // 在合适的时候调用 flush 方法
                 framed.flush().await?;
             }
-            Some(Err(e)) => return Err(e),
+            // the rest of the stream can't be framed, tell the client before closing
+            Some(Err(e)) => {
+                framed
+                    .send(SimpleError::new("ERR Protocol error").into())
+                    .await?;
+                return Err(e);
+            }
             None => return Ok(()),
         }
     }
@@ -57,12 +130,182 @@ pub async fn stream_handler(stream: TcpStream, backend: Backend) -> Result<()> {
 // async fn request_handler(request: RespFrame) -> Result<RespFrame> {
 //     todo!()
 // }
-async fn request_handler(request: RedisRequest) -> Result<RedisResponse> {
+async fn request_handler(request: RedisRequest, conn: &mut Connection) -> Result<RedisResponse> {
     let (frame, backend) = (request.frame, request.backend);
-    let cmd = Command::try_from(frame)?;
+    let name = command_name(&frame);
+    let cmd = match Command::try_from(frame) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            // a command which can't be queued aborts the transaction
+            if conn.transaction.in_multi() {
+                conn.transaction.fail();
+            }
+            return Ok(RedisResponse {
+                frames: vec![e.into()],
+            });
+        }
+    };
     info!("Executing command: {:?}", cmd);
-    let frame = cmd.execute(&backend);
-    Ok(RedisResponse { frame })
+    conn.session.touch(&name);
+    if conn.push_mode() && !allowed_in_push_mode(&cmd) {
+        let frames = vec![SimpleError::new(format!(
+            "ERR Can't execute '{}': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING are allowed in this context",
+            name
+        ))
+        .into()];
+        return Ok(RedisResponse { frames });
+    }
+    if conn.transaction.in_multi() || is_transaction_command(&cmd) {
+        let frames = vec![conn.transaction(cmd, &backend)];
+        return Ok(RedisResponse { frames });
+    }
+    let frames = match cmd {
+        Command::Subscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::Unsubscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::PSubscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::PUnsubscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::SSubscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::SUnsubscribe(cmd) => conn.subscriptions(&backend, |s| cmd.execute_subscriber(s)),
+        Command::Ping(cmd) if conn.push_mode() => vec![cmd.execute_subscriber().into()],
+        Command::Client(cmd) => vec![cmd.execute_session(&conn.session, &backend)],
+        Command::Hello(cmd) => vec![cmd.execute_session(&conn.session)],
+        cmd => vec![cmd.execute_async(&backend).await],
+    };
+    Ok(RedisResponse { frames })
+}
+
+/// The lowercase name of the command in the request, for error replies.
+fn command_name(frame: &RespFrame) -> String {
+    match frame {
+        RespFrame::Array(array) => match array.first() {
+            Some(RespFrame::BulkString(name)) => String::from_utf8_lossy(name).to_lowercase(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+/// The commands a RESP2 connection can execute while subscribed.
+fn allowed_in_push_mode(cmd: &Command) -> bool {
+    matches!(
+        cmd,
+        Command::Subscribe(_)
+            | Command::Unsubscribe(_)
+            | Command::PSubscribe(_)
+            | Command::PUnsubscribe(_)
+            | Command::SSubscribe(_)
+            | Command::SUnsubscribe(_)
+            | Command::Ping(_)
+    )
+}
+
+fn is_transaction_command(cmd: &Command) -> bool {
+    matches!(
+        cmd,
+        Command::Multi(_)
+            | Command::Exec(_)
+            | Command::Discard(_)
+            | Command::Watch(_)
+            | Command::Unwatch(_)
+    )
+}
+
+impl Connection {
+    fn new(session: Session) -> Self {
+        Self {
+            session,
+            subscriber: None,
+            transaction: Transaction::default(),
+        }
+    }
+
+    /// RESP3 replies the pub/sub messages as push frames.
+    fn resp3(&self) -> bool {
+        self.session.state().protocol == 3
+    }
+
+    /// Copy the subscriptions and the transaction state to the session, for CLIENT LIST.
+    fn sync_session(&self) {
+        let subscriber = self.subscriber.as_ref();
+        let count = |f: fn(&Subscriber) -> Vec<String>| subscriber.map_or(0, |s| f(s).len());
+        let (sub, psub, ssub) = (
+            count(Subscriber::channels),
+            count(Subscriber::patterns),
+            count(Subscriber::shard_channels),
+        );
+        self.session.update(|state| {
+            state.sub = sub;
+            state.psub = psub;
+            state.ssub = ssub;
+            state.multi = self.transaction.queued_len();
+            state.watch = self.transaction.watched_len();
+        });
+    }
+
+    /// Execute a transaction command, any other command is queued inside MULTI.
+    fn transaction(&mut self, cmd: Command, backend: &Backend) -> RespFrame {
+        let txn = &mut self.transaction;
+        match cmd {
+            Command::Multi(cmd) => cmd.execute_transaction(txn),
+            Command::Exec(cmd) => cmd.execute_transaction(txn, backend),
+            Command::Discard(cmd) => cmd.execute_transaction(txn),
+            Command::Watch(cmd) => cmd.execute_transaction(txn, backend),
+            Command::Unwatch(cmd) if !txn.in_multi() => cmd.execute_transaction(txn),
+            // an unknown command can't be queued, EXEC aborts like for a syntax error
+            cmd @ Command::Unrecognized(_) => {
+                txn.fail();
+                cmd.execute(backend)
+            }
+            cmd => txn.queue(cmd),
+        }
+    }
+
+    /// Whether only the pub/sub commands can be executed.
+    fn push_mode(&self) -> bool {
+        self.subscriber.is_some() && !self.resp3()
+    }
+
+    /// Run a (un)subscription against the subscriber of the connection, the
+    /// connection leaves the push mode once it has no subscription.
+    fn subscriptions(
+        &mut self,
+        backend: &Backend,
+        f: impl FnOnce(&mut Subscriber) -> Vec<RespArray>,
+    ) -> Vec<RespFrame> {
+        let subscriber = self.subscriber.get_or_insert_with(|| backend.subscriber());
+        let replies = f(subscriber);
+        if subscriber.is_empty() {
+            self.subscriber = None;
+        }
+        replies.into_iter().map(|r| self.push_frame(r)).collect()
+    }
+
+    /// A RESP2 connection can't decode the RESP3 types of the reply, and RESP3
+    /// has a single null type.
+    fn reply_frame(&self, frame: RespFrame) -> RespFrame {
+        match self.resp3() {
+            true => frame.into_resp3(),
+            false => frame.into_resp2(),
+        }
+    }
+
+    /// The pub/sub replies are push frames in RESP3.
+    fn push_frame(&self, reply: RespArray) -> RespFrame {
+        match self.resp3() {
+            true => RespPush::new(reply.0).into(),
+            false => reply.into(),
+        }
+    }
+}
+
+/// Wait for the next published message, forever if not subscribed.
+/// It only borrows the subscriber, the connection also waits to be killed.
+/// None means the subscriber fell too far behind.
+async fn next_message(subscriber: &mut Option<Subscriber>) -> Option<PubSubMessage> {
+    match subscriber.as_mut() {
+        Some(subscriber) => subscriber.recv().await,
+        None => std::future::pending().await,
+    }
 }
 
 impl Encoder<RespFrame> for RespFrameCodec {
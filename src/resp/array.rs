@@ -24,9 +24,6 @@ impl RespArray {
 // - array: "*<number-of-elements>\r\n<element-1>...<element-n>"
 impl RespEncode for RespArray {
     fn encode(self) -> Vec<u8> {
-        if self.is_empty() {
-            return NULL_ARRAY.to_vec();
-        }
         let mut buf = Vec::with_capacity(BUF_CAP);
         buf.extend_from_slice(&format!("*{}\r\n", self.len()).into_bytes());
         for frame in self.0 {
@@ -99,7 +96,7 @@ impl Deref for RespArray {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::BulkString;
+    use crate::{BulkString, RespNullArray};
     use anyhow::Result;
 
     #[test]
@@ -118,9 +115,12 @@ mod tests {
 
     #[test]
     fn test_null_array_encode() {
-        // let frame: RespFrame = RespNullArray.into();
-        let frame: RespFrame = RespArray::new(vec![]).into();
+        let frame: RespFrame = RespNullArray.into();
         assert_eq!(frame.encode(), b"*-1\r\n");
+
+        // an empty array is not a null
+        let frame: RespFrame = RespArray::new(vec![]).into();
+        assert_eq!(frame.encode(), b"*0\r\n");
     }
 
     #[test]
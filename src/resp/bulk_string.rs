@@ -21,9 +21,6 @@ impl BulkString {
 // - bulk string: "$<length>\r\n<data>\r\n"
 impl RespEncode for BulkString {
     fn encode(self) -> Vec<u8> {
-        if self.is_empty() {
-            return NULL_BULK_STRING.to_vec();
-        }
         let mut buf = Vec::with_capacity(self.len() + 16);
         buf.extend_from_slice(&format!("${}\r\n", self.len()).into_bytes());
         buf.extend_from_slice(&self);
@@ -118,7 +115,7 @@ impl<const N: usize> From<&[u8; N]> for BulkString {
 
 #[cfg(test)]
 mod tests {
-    use crate::RespFrame;
+    use crate::{RespFrame, RespNullBulkString};
 
     use super::*;
     use anyhow::Result;
@@ -131,9 +128,12 @@ mod tests {
 
     #[test]
     fn test_null_bulk_string_encode() {
-        // let frame: RespFrame = RespNullBulkString.into();
-        let frame: RespFrame = BulkString::new(vec![]).into();
+        let frame: RespFrame = RespNullBulkString.into();
         assert_eq!(frame.encode(), b"$-1\r\n");
+
+        // an empty bulk string is not the null
+        let frame: RespFrame = BulkString::new(vec![]).into();
+        assert_eq!(frame.encode(), b"$0\r\n\r\n");
     }
 
     #[test]
@@ -6,7 +6,10 @@ use super::{extract_simple_frame_data, RespDecode, RespEncode, RespError, CRLF_L
 impl RespEncode for f64 {
     fn encode(self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(32);
-        let ret = if self.abs() > 1e+8 || self.abs() < 1e-8 {
+        let ret = if !self.is_finite() {
+            // inf, -inf and nan are spelled out like redis does
+            format!(",{}\r\n", self.to_string().to_lowercase())
+        } else if self.abs() > 1e+8 || self.abs() < 1e-8 {
             format!(",{:+e}\r\n", self)
         } else {
             let sign = if self < 0.0 { "" } else { "+" };
@@ -55,6 +58,12 @@ mod tests {
 
         let frame: RespFrame = (-1.23456e-9).into();
         assert_eq!(&frame.encode(), b",-1.23456e-9\r\n");
+
+        let frame: RespFrame = f64::NEG_INFINITY.into();
+        assert_eq!(&frame.encode(), b",-inf\r\n");
+
+        let frame: RespFrame = f64::NAN.into();
+        assert_eq!(&frame.encode(), b",nan\r\n");
     }
 
     #[test]
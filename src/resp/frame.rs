@@ -2,8 +2,8 @@ use bytes::BytesMut;
 use enum_dispatch::enum_dispatch;
 
 use super::{
-    BulkString, RespArray, RespDecode, RespError, RespMap, RespNull, RespSet, SimpleError,
-    SimpleString,
+    BulkString, RespArray, RespDecode, RespError, RespMap, RespNull, RespNullArray,
+    RespNullBulkString, RespPush, RespSet, SimpleError, SimpleString,
 };
 
 // 关于 enum 的知识点
@@ -21,14 +21,16 @@ pub enum RespFrame {
     Error(SimpleError),
     Integer(i64),
     BulkString(BulkString),
-    // NullBulkString(RespNullBulkString),
+    NullBulkString(RespNullBulkString), // the null of a command which otherwise replies a bulk string
     Array(RespArray),
     // NullArray(RespNullArray),
     Null(RespNull),
+    NullArray(RespNullArray), // the null of a command which otherwise replies an array
     Boolean(bool),
     Double(f64),
     Map(RespMap),
     Set(RespSet),
+    Push(RespPush),
 }
 // NOTE: 这里需要 impl RespDecode, RespEncode 不需要是因为使用 enum_dispatch 宏的时候, 会自动实现这些 trait
 // RespDecode 不能使用 enum_dispatch, 因为不支持 trait 中带有 associated type/ const 的情况
@@ -100,6 +102,10 @@ impl RespDecode for RespFrame {
                 let frame = RespSet::decode(buf)?;
                 Ok(frame.into())
             }
+            Some(b'>') => {
+                let frame = RespPush::decode(buf)?;
+                Ok(frame.into())
+            }
             None => Err(RespError::NotComplete),
             _ => Err(RespError::InvalidFrameType(format!(
                 "expect_length: unknown frame type: {:?}",
@@ -113,6 +119,7 @@ impl RespDecode for RespFrame {
         match iter.peek() {
             Some(b'*') => RespArray::expect_length(buf),
             Some(b'~') => RespSet::expect_length(buf),
+            Some(b'>') => RespPush::expect_length(buf),
             Some(b'%') => RespMap::expect_length(buf),
             Some(b'$') => BulkString::expect_length(buf),
             Some(b':') => i64::expect_length(buf),
@@ -126,6 +133,56 @@ impl RespDecode for RespFrame {
     }
 }
 
+impl RespFrame {
+    /// Downgrade the RESP3 types for a RESP2 connection, like redis:
+    /// a map becomes a flat array of its keys and values, a set becomes an array,
+    /// a boolean becomes 1 or 0, a double becomes a bulk string and a null
+    /// becomes a null bulk string.
+    pub fn into_resp2(self) -> RespFrame {
+        match self {
+            RespFrame::Array(array) => RespArray::new(into_resp2(array.0)).into(),
+            RespFrame::Set(set) => RespArray::new(into_resp2(set.0)).into(),
+            RespFrame::Map(map) => {
+                let frames = map
+                    .0
+                    .into_iter()
+                    .flat_map(|(key, value)| [BulkString::new(key).into(), value.into_resp2()])
+                    .collect::<Vec<_>>();
+                RespArray::new(frames).into()
+            }
+            RespFrame::Boolean(b) => RespFrame::Integer(b as i64),
+            RespFrame::Double(f) => BulkString::new(f.to_string()).into(),
+            RespFrame::Null(_) => RespNullBulkString.into(),
+            frame => frame,
+        }
+    }
+
+    /// RESP3 has a single null type, a null array or a null bulk string becomes a null.
+    pub fn into_resp3(self) -> RespFrame {
+        match self {
+            RespFrame::Array(array) => RespArray::new(into_resp3(array.0)).into(),
+            RespFrame::Set(set) => RespSet::new(into_resp3(set.0)).into(),
+            RespFrame::Push(push) => RespPush::new(into_resp3(push.0)).into(),
+            RespFrame::Map(mut map) => {
+                for value in map.0.values_mut() {
+                    *value = std::mem::replace(value, RespNull.into()).into_resp3();
+                }
+                map.into()
+            }
+            RespFrame::NullArray(_) | RespFrame::NullBulkString(_) => RespNull.into(),
+            frame => frame,
+        }
+    }
+}
+
+fn into_resp2(frames: Vec<RespFrame>) -> Vec<RespFrame> {
+    frames.into_iter().map(RespFrame::into_resp2).collect()
+}
+
+fn into_resp3(frames: Vec<RespFrame>) -> Vec<RespFrame> {
+    frames.into_iter().map(RespFrame::into_resp3).collect()
+}
+
 impl From<&str> for RespFrame {
     fn from(s: &str) -> Self {
         SimpleString(s.to_string()).into()
@@ -147,6 +204,7 @@ impl<const N: usize> From<&[u8; N]> for RespFrame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::RespEncode;
     use anyhow::Result;
 
     #[test]
@@ -217,4 +275,31 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn test_resp_frame_into_resp2() {
+        let mut map = RespMap::new();
+        map.insert("ok".to_string(), true.into());
+        map.insert("score".to_string(), 1.5f64.into());
+        let frame = RespFrame::from(map).into_resp2();
+        assert_eq!(
+            frame,
+            RespArray::new([
+                BulkString::new("ok").into(),
+                1i64.into(),
+                BulkString::new("score").into(),
+                BulkString::new("1.5").into(),
+            ])
+            .into()
+        );
+
+        assert_eq!(RespFrame::from(RespNull).into_resp2().encode(), b"$-1\r\n");
+        assert_eq!(
+            RespFrame::NullArray(RespNullArray).into_resp2().encode(),
+            b"*-1\r\n"
+        );
+        assert_eq!(
+            RespFrame::NullArray(RespNullArray).into_resp3(),
+            RespNull.into()
+        );
+    }
 }
@@ -6,8 +6,8 @@ use std::{
 use bytes::{Buf, BytesMut};
 
 use super::{
-    calc_total_length, parse_length, simple_string::SimpleString, RespDecode, RespEncode,
-    RespError, RespFrame, BUF_CAP, CRLF_LEN,
+    calc_total_length, parse_length, BulkString, RespDecode, RespEncode, RespError, RespFrame,
+    BUF_CAP, CRLF_LEN,
 };
 
 // 改为 BTreeMap, 用于有序的 key-value 数据
@@ -26,8 +26,8 @@ impl RespEncode for RespMap {
         let mut buf = Vec::with_capacity(BUF_CAP);
         buf.extend_from_slice(&format!("%{}\r\n", self.len()).into_bytes());
         for (key, value) in self.0 {
-            // 因为 key 不是 SimpleString 类型, 因此不能直接调用 encode 方法
-            buf.extend_from_slice(&SimpleString::new(key).encode());
+            // like redis, the keys are bulk strings, a hash field may contain a CRLF
+            buf.extend_from_slice(&BulkString::new(key).encode());
             buf.extend_from_slice(&value.encode());
         }
         buf
@@ -49,9 +49,18 @@ impl RespDecode for RespMap {
 
         let mut frames = RespMap::new();
         for _ in 0..len {
-            let key = SimpleString::decode(buf)?;
+            let key = match RespFrame::decode(buf)? {
+                RespFrame::SimpleString(key) => key.0,
+                RespFrame::BulkString(key) => String::from_utf8_lossy(&key).into_owned(),
+                key => {
+                    return Err(RespError::InvalidFrameType(format!(
+                        "expect: map key, got: {:?}",
+                        key
+                    )))
+                }
+            };
             let value = RespFrame::decode(buf)?;
-            frames.insert(key.0, value);
+            frames.insert(key, value);
         }
 
         Ok(frames)
@@ -95,14 +104,14 @@ mod tests {
         let frame: RespFrame = map.into();
         assert_eq!(
             &frame.encode(),
-            b"%2\r\n+foo\r\n,-123456.789\r\n+hello\r\n$5\r\nworld\r\n"
+            b"%2\r\n$3\r\nfoo\r\n,-123456.789\r\n$5\r\nhello\r\n$5\r\nworld\r\n"
         );
     }
 
     #[test]
     fn test_map_decode() -> Result<()> {
         let mut buf = BytesMut::new();
-        buf.extend_from_slice(b"%2\r\n+hello\r\n$5\r\nworld\r\n+foo\r\n$3\r\nbar\r\n");
+        buf.extend_from_slice(b"%2\r\n+hello\r\n$5\r\nworld\r\n$3\r\nfoo\r\n$3\r\nbar\r\n");
 
         let frame = RespMap::decode(&mut buf)?;
         let mut map = RespMap::new();
@@ -6,6 +6,7 @@ mod frame;
 mod integer;
 mod map;
 mod null;
+mod push;
 mod set;
 mod simple_error;
 mod simple_string;
@@ -16,7 +17,8 @@ use thiserror::Error;
 
 pub(crate) use self::{
     array::RespArray, bulk_string::BulkString, frame::RespFrame, map::RespMap, null::RespNull,
-    set::RespSet, simple_error::SimpleError, simple_string::SimpleString,
+    null::RespNullArray, null::RespNullBulkString, push::RespPush, set::RespSet,
+    simple_error::SimpleError, simple_string::SimpleString,
 };
 
 const CRLF: &[u8] = b"\r\n";
@@ -192,8 +194,8 @@ fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result
     let mut total = end + CRLF_LEN;
     let mut data = &buf[total..];
     match prefix {
-        "*" | "~" => {
-            // find nth CRLF in the buffer, for array and set, we need to find 1 CRLF for each element
+        "*" | "~" | ">" => {
+            // find nth CRLF in the buffer, for array, set and push, we need to find 1 CRLF for each element
             for _ in 0..len {
                 let len = RespFrame::expect_length(data)?;
                 data = &data[len..];
@@ -202,9 +204,10 @@ fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result
             Ok(total)
         }
         "%" => {
-            // find nth CRLF in the buffer. For map, we need to find 2 CRLF for each key-value pair
+            // find nth CRLF in the buffer. For map, we need to find 2 CRLF for each key-value pair,
+            // a key is a simple or a bulk string
             for _ in 0..len {
-                let len = SimpleString::expect_length(data)?;
+                let len = RespFrame::expect_length(data)?;
 
                 data = &data[len..];
                 total += len;
@@ -14,6 +14,32 @@ impl RespEncode for RespNull {
     }
 }
 
+/// The RESP2 null of a command which otherwise replies an array, e.g. BLPOP
+/// on timeout, it is replied as a null to a RESP3 connection.
+/// It is never decoded, "*-1" is decoded as an empty array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespNullArray;
+
+// - null array: "*-1\r\n"
+impl RespEncode for RespNullArray {
+    fn encode(self) -> Vec<u8> {
+        b"*-1\r\n".to_vec()
+    }
+}
+
+/// The RESP2 null of a command which otherwise replies a bulk string, e.g. GET
+/// on a missing key, a RESP3 null is downgraded to it.
+/// It is never decoded, "$-1" is decoded as an empty bulk string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespNullBulkString;
+
+// - null bulk string: "$-1\r\n"
+impl RespEncode for RespNullBulkString {
+    fn encode(self) -> Vec<u8> {
+        b"$-1\r\n".to_vec()
+    }
+}
+
 impl RespDecode for RespNull {
     const PREFIX: &'static str = "_";
     fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {